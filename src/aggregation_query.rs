@@ -0,0 +1,270 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    StructuredAggregationQuery, StructuredQuery, structured_aggregation_query, structured_query,
+};
+
+use crate::{FieldPath, Query, Result};
+
+/// A Firestore aggregation.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredAggregationQuery.Aggregation>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_aggregation() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{Aggregation, FieldPath};
+/// let aggregation1: Aggregation = Aggregation::count();
+/// let aggregation2: Aggregation = Aggregation::count_up_to(100).alias("count_up_to_100");
+/// let aggregation3: Aggregation = Aggregation::sum(FieldPath::raw("field1"));
+/// let aggregation4: Aggregation = Aggregation::avg(FieldPath::raw("field1")).alias("avg_field1");
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Aggregation(structured_aggregation_query::Aggregation);
+
+impl Aggregation {
+    /// Creates a new `Count` aggregation.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredAggregationQuery.Aggregation.Count>
+    pub fn count() -> Self {
+        Self::new(structured_aggregation_query::aggregation::Operator::Count(
+            structured_aggregation_query::aggregation::Count { up_to: None },
+        ))
+    }
+
+    /// Creates a new `Count` aggregation with the `up_to` constraint.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredAggregationQuery.Aggregation.Count>
+    pub fn count_up_to(up_to: i64) -> Self {
+        Self::new(structured_aggregation_query::aggregation::Operator::Count(
+            structured_aggregation_query::aggregation::Count { up_to: Some(up_to) },
+        ))
+    }
+
+    /// Creates a new `Sum` aggregation.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredAggregationQuery.Aggregation.Sum>
+    pub fn sum(field_path: FieldPath) -> Self {
+        Self::new(structured_aggregation_query::aggregation::Operator::Sum(
+            structured_aggregation_query::aggregation::Sum {
+                field: Some(structured_query::FieldReference::from(field_path)),
+            },
+        ))
+    }
+
+    /// Creates a new `Avg` aggregation.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredAggregationQuery.Aggregation.Avg>
+    pub fn avg(field_path: FieldPath) -> Self {
+        Self::new(structured_aggregation_query::aggregation::Operator::Avg(
+            structured_aggregation_query::aggregation::Avg {
+                field: Some(structured_query::FieldReference::from(field_path)),
+            },
+        ))
+    }
+
+    /// Sets the specified value to alias and returns the Aggregation.
+    ///
+    /// If the alias is not set, `AggregationQuery::new` assigns a unique alias in the `field_<n>` format.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredAggregationQuery.Aggregation.FIELDS.string.google.firestore.v1.StructuredAggregationQuery.Aggregation.alias>
+    pub fn alias<S>(mut self, alias: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.0.alias = alias.into();
+        self
+    }
+
+    fn new(operator: structured_aggregation_query::aggregation::Operator) -> Self {
+        Self(structured_aggregation_query::Aggregation {
+            alias: String::new(),
+            operator: Some(operator),
+        })
+    }
+}
+
+impl std::convert::From<Aggregation> for structured_aggregation_query::Aggregation {
+    fn from(aggregation: Aggregation) -> Self {
+        aggregation.0
+    }
+}
+
+/// A Firestore aggregation query.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#structuredaggregationquery>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_aggregation_query() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{Aggregation, AggregationQuery, FieldPath, Query};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     structured_aggregation_query, structured_query, StructuredAggregationQuery, StructuredQuery,
+/// };
+/// let query1 = Query::collection("collection_id1");
+/// let aggregation_query1 = AggregationQuery::new(
+///     query1.clone(),
+///     [
+///         Aggregation::count().alias("count"),
+///         Aggregation::sum(FieldPath::raw("field1")),
+///         Aggregation::avg(FieldPath::raw("field1")),
+///     ],
+/// )?;
+/// assert_eq!(
+///     StructuredAggregationQuery::from(aggregation_query1),
+///     StructuredAggregationQuery {
+///         aggregations: vec![
+///             structured_aggregation_query::Aggregation {
+///                 alias: "count".to_string(),
+///                 operator: Some(structured_aggregation_query::aggregation::Operator::Count(
+///                     structured_aggregation_query::aggregation::Count { up_to: None },
+///                 )),
+///             },
+///             structured_aggregation_query::Aggregation {
+///                 alias: "field_1".to_string(),
+///                 operator: Some(structured_aggregation_query::aggregation::Operator::Sum(
+///                     structured_aggregation_query::aggregation::Sum {
+///                         field: Some(structured_query::FieldReference {
+///                             field_path: "field1".to_string(),
+///                         }),
+///                     },
+///                 )),
+///             },
+///             structured_aggregation_query::Aggregation {
+///                 alias: "field_2".to_string(),
+///                 operator: Some(structured_aggregation_query::aggregation::Operator::Avg(
+///                     structured_aggregation_query::aggregation::Avg {
+///                         field: Some(structured_query::FieldReference {
+///                             field_path: "field1".to_string(),
+///                         }),
+///                     },
+///                 )),
+///             },
+///         ],
+///         query_type: Some(structured_aggregation_query::QueryType::StructuredQuery(
+///             StructuredQuery::from(query1),
+///         )),
+///     }
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregationQuery(StructuredAggregationQuery);
+
+impl AggregationQuery {
+    /// The maximum number of aggregations per query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredAggregationQuery.FIELDS.repeated.google.firestore.v1.StructuredAggregationQuery.Aggregation.google.firestore.v1.StructuredAggregationQuery.aggregations>
+    pub const MAX_AGGREGATIONS: usize = 5;
+
+    /// Creates a new `AggregationQuery` over the query.
+    ///
+    /// Aggregations without an alias are assigned a unique alias in the `field_<n>` format.
+    ///
+    /// Returns an error if there are no aggregations, more than `MAX_AGGREGATIONS` aggregations,
+    /// duplicate aliases or a `count_up_to` that is not greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_aggregation_query_new() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{Aggregation, AggregationQuery, FieldPath, Query};
+    /// let query1 = Query::collection("collection_id1");
+    /// assert!(AggregationQuery::new(query1.clone(), [Aggregation::count()]).is_ok());
+    /// assert!(AggregationQuery::new(query1.clone(), []).is_err());
+    /// assert!(AggregationQuery::new(query1.clone(), vec![Aggregation::count(); 6]).is_err());
+    /// assert!(AggregationQuery::new(query1.clone(), [Aggregation::count_up_to(0)]).is_err());
+    /// assert!(
+    ///     AggregationQuery::new(
+    ///         query1.clone(),
+    ///         [
+    ///             Aggregation::count().alias("a"),
+    ///             Aggregation::sum(FieldPath::raw("field1")).alias("a"),
+    ///         ]
+    ///     )
+    ///     .is_err()
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn new<I>(query: Query, aggregations: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = Aggregation>,
+    {
+        let mut aggregations = aggregations
+            .into_iter()
+            .map(|aggregation| aggregation.0)
+            .collect::<Vec<structured_aggregation_query::Aggregation>>();
+        if aggregations.is_empty() {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "aggregations",
+                "aggregations must not be empty",
+            )]));
+        }
+        if aggregations.len() > Self::MAX_AGGREGATIONS {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "aggregations",
+                format!(
+                    "the number of aggregations must be less than or equal to {} (actual: {})",
+                    Self::MAX_AGGREGATIONS,
+                    aggregations.len()
+                ),
+            )]));
+        }
+
+        let mut aliases = std::collections::BTreeSet::new();
+        for (index, aggregation) in aggregations.iter().enumerate() {
+            if let Some(structured_aggregation_query::aggregation::Operator::Count(
+                structured_aggregation_query::aggregation::Count { up_to: Some(up_to) },
+            )) = aggregation.operator
+                && up_to <= 0
+            {
+                return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                    format!("aggregations[{}].count.up_to", index),
+                    format!("count up_to must be greater than zero (actual: {})", up_to),
+                )]));
+            }
+            if aggregation.alias.is_empty() {
+                continue;
+            }
+            if !aliases.insert(aggregation.alias.clone()) {
+                return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                    format!("aggregations[{}].alias", index),
+                    format!("duplicate aggregation alias: {}", aggregation.alias),
+                )]));
+            }
+        }
+
+        let mut next_id = 1_usize;
+        for aggregation in aggregations.iter_mut() {
+            if !aggregation.alias.is_empty() {
+                continue;
+            }
+            let alias = loop {
+                let alias = format!("field_{}", next_id);
+                next_id += 1;
+                if !aliases.contains(&alias) {
+                    break alias;
+                }
+            };
+            aliases.insert(alias.clone());
+            aggregation.alias = alias;
+        }
+
+        Ok(Self(StructuredAggregationQuery {
+            aggregations,
+            query_type: Some(structured_aggregation_query::QueryType::StructuredQuery(
+                StructuredQuery::from(query),
+            )),
+        }))
+    }
+}
+
+impl std::convert::From<AggregationQuery> for StructuredAggregationQuery {
+    fn from(aggregation_query: AggregationQuery) -> Self {
+        aggregation_query.0
+    }
+}
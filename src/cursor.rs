@@ -0,0 +1,54 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{self, Value};
+
+/// A Firestore query cursor.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.Cursor>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_cursor() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::Query;
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Cursor, Value};
+/// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+/// let query1 = Query::collection("collection_id1").start_after([one.clone()]);
+/// let cursor = query1.start_cursor().unwrap();
+/// assert_eq!(cursor.values(), &[one.clone()]);
+/// assert!(!cursor.before());
+/// assert_eq!(
+///     Cursor::from(cursor.clone()),
+///     Cursor {
+///         values: vec![one],
+///         before: false,
+///     }
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cursor(v1::Cursor);
+
+impl Cursor {
+    /// Returns `true` if the position is just before the values (`start_at` or `end_before`),
+    /// `false` if it is just after the values (`start_after` or `end_at`).
+    pub fn before(&self) -> bool {
+        self.0.before
+    }
+
+    /// Returns the values that represent a position, in the order they appear in the order by clause.
+    pub fn values(&self) -> &[Value] {
+        &self.0.values
+    }
+}
+
+impl std::convert::From<v1::Cursor> for Cursor {
+    fn from(cursor: v1::Cursor) -> Self {
+        Self(cursor)
+    }
+}
+
+impl std::convert::From<Cursor> for v1::Cursor {
+    fn from(cursor: Cursor) -> Self {
+        cursor.0
+    }
+}
@@ -0,0 +1,255 @@
+//! Text encodings of Firestore values (standard base64 for bytes and RFC 3339 for timestamps).
+
+use crate::Result;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the bytes with the standard base64 alphabet and padding.
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (u32::from(chunk[0]) << 16)
+            | (u32::from(chunk.get(1).copied().unwrap_or(0)) << 8)
+            | u32::from(chunk.get(2).copied().unwrap_or(0));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(char::from(
+                    BASE64_ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F],
+                ));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Decodes the standard base64 (padding is optional).
+pub(crate) fn decode_base64(s: &str) -> Result<Vec<u8>> {
+    let invalid = || {
+        crate::Error::with_kind(
+            crate::ErrorKind::ValueConversion,
+            format!("invalid base64: {}", s),
+        )
+    };
+    let trimmed = s.trim_end_matches('=');
+    if s.len() - trimmed.len() > 2 || (s.len() != trimmed.len() && !s.len().is_multiple_of(4)) {
+        return Err(invalid());
+    }
+    if trimmed.len() % 4 == 1 {
+        return Err(invalid());
+    }
+    let mut decoded = Vec::with_capacity(trimmed.len() * 3 / 4);
+    let mut n = 0_u32;
+    let mut bits = 0_u32;
+    for b in trimmed.bytes() {
+        let v = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(invalid()),
+        };
+        n = (n << 6) | u32::from(v);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((n >> bits) as u8);
+            n &= (1 << bits) - 1;
+        }
+    }
+    // the remaining bits must be zero
+    if n != 0 {
+        return Err(invalid());
+    }
+    Ok(decoded)
+}
+
+/// The minimum timestamp supported by Firestore (`0001-01-01T00:00:00Z`).
+pub(crate) const MIN_TIMESTAMP_SECONDS: i64 = -62_135_596_800;
+
+/// The maximum timestamp supported by Firestore (`9999-12-31T23:59:59.999999999Z`).
+pub(crate) const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+
+/// Formats the timestamp in RFC 3339 (UTC) with 0, 3, 6 or 9 fractional digits.
+pub(crate) fn format_timestamp(timestamp: &prost_types::Timestamp) -> String {
+    let seconds = timestamp
+        .seconds
+        .saturating_add(i64::from(timestamp.nanos.div_euclid(1_000_000_000)));
+    let nanos = timestamp.nanos.rem_euclid(1_000_000_000);
+    let days = seconds.div_euclid(86_400);
+    let seconds_of_day = seconds.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    let fraction = if nanos == 0 {
+        String::new()
+    } else if nanos % 1_000_000 == 0 {
+        format!(".{:03}", nanos / 1_000_000)
+    } else if nanos % 1_000 == 0 {
+        format!(".{:06}", nanos / 1_000)
+    } else {
+        format!(".{:09}", nanos)
+    };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+        fraction
+    )
+}
+
+/// Parses the RFC 3339 timestamp (e.g. `2006-01-02T15:04:05.999999999+07:00`).
+///
+/// Returns an error if the timestamp is out of the range supported by Firestore.
+pub(crate) fn parse_timestamp(s: &str) -> Result<prost_types::Timestamp> {
+    let invalid = || {
+        crate::Error::with_kind(
+            crate::ErrorKind::ValueConversion,
+            format!("invalid timestamp: {}", s),
+        )
+    };
+    let bytes = s.as_bytes();
+    let number = |range: std::ops::Range<usize>| -> Result<i64> {
+        let digits = bytes.get(range).ok_or_else(invalid)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return Err(invalid());
+        }
+        Ok(digits
+            .iter()
+            .fold(0_i64, |n, d| n * 10 + i64::from(d - b'0')))
+    };
+    let separator = |index: usize, expected: &[u8]| -> Result<()> {
+        match bytes.get(index) {
+            Some(b) if expected.contains(b) => Ok(()),
+            _ => Err(invalid()),
+        }
+    };
+    let year = number(0..4)?;
+    separator(4, b"-")?;
+    let month = number(5..7)?;
+    separator(7, b"-")?;
+    let day = number(8..10)?;
+    separator(10, b"Tt")?;
+    let hour = number(11..13)?;
+    separator(13, b":")?;
+    let minute = number(14..16)?;
+    separator(16, b":")?;
+    let second = number(17..19)?;
+
+    let mut index = 19;
+    let mut nanos = 0_i64;
+    if bytes.get(index) == Some(&b'.') {
+        index += 1;
+        let start = index;
+        while bytes.get(index).is_some_and(u8::is_ascii_digit) {
+            index += 1;
+        }
+        let digits = index - start;
+        if digits == 0 || digits > 9 {
+            return Err(invalid());
+        }
+        nanos = number(start..index)? * 10_i64.pow(9 - digits as u32);
+    }
+    let offset = match bytes.get(index) {
+        Some(b'Z' | b'z') if index + 1 == bytes.len() => 0,
+        Some(sign @ (b'+' | b'-')) if index + 6 == bytes.len() => {
+            separator(index + 3, b":")?;
+            let offset_hour = number(index + 1..index + 3)?;
+            let offset_minute = number(index + 4..index + 6)?;
+            if offset_hour > 23 || offset_minute > 59 {
+                return Err(invalid());
+            }
+            let offset = offset_hour * 3_600 + offset_minute * 60;
+            if *sign == b'-' { -offset } else { offset }
+        }
+        _ => return Err(invalid()),
+    };
+
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => return Err(invalid()),
+    };
+    if day < 1 || day > days_in_month || hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+    let seconds =
+        days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second - offset;
+    if !(MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&seconds) {
+        return Err(crate::Error::with_kind(
+            crate::ErrorKind::ValueConversion,
+            format!(
+                "timestamp must be between 0001-01-01T00:00:00Z and 9999-12-31T23:59:59.999999999Z: {}",
+                s
+            ),
+        ));
+    }
+    Ok(prost_types::Timestamp {
+        seconds,
+        nanos: nanos as i32,
+    })
+}
+
+/// Builds the timestamp from the seconds and nanoseconds since the Unix epoch.
+///
+/// The nanoseconds are truncated to microseconds (the precision of Firestore timestamps).
+/// Returns an error if the timestamp is out of the range supported by Firestore.
+pub(crate) fn timestamp(seconds: i64, nanos: u32) -> Result<prost_types::Timestamp> {
+    let out_of_range = || {
+        crate::Error::with_kind(
+            crate::ErrorKind::ValueConversion,
+            format!(
+                "timestamp must be between 0001-01-01T00:00:00Z and 9999-12-31T23:59:59.999999999Z: {} seconds and {} nanoseconds since the Unix epoch",
+                seconds, nanos
+            ),
+        )
+    };
+    // a leap second may be represented as nanoseconds >= 1_000_000_000
+    let seconds = seconds
+        .checked_add(i64::from(nanos / 1_000_000_000))
+        .ok_or_else(out_of_range)?;
+    let nanos = nanos % 1_000_000_000;
+    if !(MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&seconds) {
+        return Err(out_of_range());
+    }
+    Ok(prost_types::Timestamp {
+        seconds,
+        nanos: (nanos - nanos % 1_000) as i32,
+    })
+}
+
+// <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
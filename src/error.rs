@@ -1,25 +1,193 @@
+use crate::{FieldPath, Violation};
+
 /// A result type for this crate.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The kind of an [`Error`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A value cannot be converted into a Firestore value (e.g. an out of range integer or timestamp, or a `serde` serialization failure).
+    ValueConversion,
+    /// A field path, or a document or collection path of a resource name, is missing or invalid.
+    InvalidFieldPath,
+    /// A cursor cannot be built or applied (e.g. a document does not contain the ordered field).
+    InvalidCursor,
+    /// A query or a filter violates the Firestore query constraints (e.g. too many `in` values). See [`Error::violations`].
+    Violation,
+    /// An operator or a direction is unspecified, unknown or not supported by the operation.
+    UnsupportedOperator,
+    /// Any other error (e.g. a parse error).
+    Other,
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ErrorKind::ValueConversion => "value conversion error",
+            ErrorKind::InvalidFieldPath => "invalid field path",
+            ErrorKind::InvalidCursor => "invalid cursor",
+            ErrorKind::Violation => "violation",
+            ErrorKind::UnsupportedOperator => "unsupported operator",
+            ErrorKind::Other => "other error",
+        })
+    }
+}
+
 /// An error that can occur when working with this crate.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_error() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{ErrorKind, FieldPath, Filter};
+/// let filter = Filter::and([
+///     FieldPath::raw("a").equal(1)?,
+///     FieldPath::raw("b").array_contains(2)?,
+/// ]);
+/// let err = filter.not().unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::UnsupportedOperator);
+/// assert_eq!(err.field_path(), Some(&FieldPath::raw("b")));
+/// assert_eq!(err.operator(), Some("ARRAY_CONTAINS"));
+/// assert_eq!(err.position(), Some("filters[1]"));
+/// assert_eq!(err.to_string(), "ARRAY_CONTAINS filter cannot be negated (field: b)");
+///
+/// let err = FieldPath::raw("c").equal(u64::MAX).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::ValueConversion);
+/// assert_eq!(err.field_path(), Some(&FieldPath::raw("c")));
+/// assert_eq!(err.operator(), Some("EQUAL"));
+/// #     Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Error {
+    kind: ErrorKind,
+    field_path: Option<FieldPath>,
+    operator: Option<String>,
+    position: Option<String>,
+    violations: Vec<Violation>,
     source: Box<dyn std::error::Error + Send + Sync>,
 }
 
 impl Error {
     /// Create a new error from a source error.
+    ///
+    /// The kind of the error is [`ErrorKind::Other`].
     pub fn new<E>(source: E) -> Self
     where
         E: Into<Box<dyn std::error::Error + Send + Sync>>,
     {
         Self::from(source.into())
     }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the field path that caused the error, if any.
+    pub fn field_path(&self) -> Option<&FieldPath> {
+        self.field_path.as_ref()
+    }
+
+    /// Returns the name of the operator that caused the error (e.g. `ARRAY_CONTAINS`), if any.
+    pub fn operator(&self) -> Option<&str> {
+        self.operator.as_deref()
+    }
+
+    /// Returns the position where the error happened, if any.
+    ///
+    /// The position is a dot-separated path in the same format as [`Violation::location`],
+    /// relative to the root filter (e.g. `filters[1].filters[0]`) or to the `StructuredQuery` (e.g. `where.filters[1]`).
+    /// An empty string is the root filter itself.
+    pub fn position(&self) -> Option<&str> {
+        self.position.as_deref()
+    }
+
+    /// Returns the violations of an [`ErrorKind::Violation`] error (e.g. found by [`Query::validate`](crate::Query::validate)).
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    pub(crate) fn with_kind<E>(kind: ErrorKind, source: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self {
+            kind,
+            ..Self::new(source)
+        }
+    }
+
+    pub(crate) fn from_violations(violations: Vec<Violation>) -> Self {
+        let message = format!(
+            "invalid query: {}",
+            violations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join("; ")
+        );
+        Self {
+            violations,
+            ..Self::with_kind(ErrorKind::Violation, message)
+        }
+    }
+
+    /// Sets the field path if it is not set yet.
+    pub(crate) fn with_field_path(mut self, field_path: FieldPath) -> Self {
+        self.field_path.get_or_insert(field_path);
+        self
+    }
+
+    /// Sets the operator if it is not set yet.
+    pub(crate) fn with_operator<S>(mut self, operator: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.operator.get_or_insert_with(|| operator.into());
+        self
+    }
+
+    /// Prepends the segment to the position and to the locations of the violations.
+    pub(crate) fn at<S>(mut self, segment: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let segment = segment.into();
+        let prepend = |location: &str| {
+            if location.is_empty() {
+                segment.clone()
+            } else {
+                format!("{}.{}", segment, location)
+            }
+        };
+        self.violations = self
+            .violations
+            .iter()
+            .map(|violation| Violation::new(prepend(violation.location()), violation.message()))
+            .collect();
+        self.position = Some(prepend(self.position.as_deref().unwrap_or_default()));
+        self
+    }
+
+    /// Sets the position to the root filter if it is not set yet.
+    pub(crate) fn at_root(mut self) -> Self {
+        self.position.get_or_insert_with(String::new);
+        self
+    }
 }
 
 impl std::convert::From<Box<dyn std::error::Error + Send + Sync>> for Error {
     fn from(source: Box<dyn std::error::Error + Send + Sync>) -> Self {
-        Self { source }
+        Self {
+            kind: ErrorKind::Other,
+            field_path: None,
+            operator: None,
+            position: None,
+            violations: vec![],
+            source,
+        }
     }
 }
 
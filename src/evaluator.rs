@@ -0,0 +1,443 @@
+use std::cmp::Ordering;
+
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    Cursor, Document, MapValue, StructuredQuery, Value, structured_query, value::ValueType,
+};
+
+use crate::ordered_value::{compare, is_comparable, is_nan, is_null};
+use crate::{FieldPath, Result};
+
+/// Evaluates the query against the documents in memory and returns the matching documents.
+///
+/// The evaluation follows the Firestore query semantics:
+///
+/// - Documents are matched against the `from` clause. A collection query matches the documents of the root collection,
+///   and a collection group query matches the documents of all collections with the collection ID.
+/// - Documents that do not contain a field of the (implicit) order_by are excluded.
+/// - The documents are sorted by the order_by, the inequality fields and `__name__`.
+/// - The cursors, the offset, the limit and the projection are applied in this order.
+///
+/// `find_nearest` is not supported and results in an error.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_evaluate() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{evaluate, FieldPath, Query};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     value::ValueType, Document, StructuredQuery, Value,
+/// };
+/// let document = |id: &str, age: i64| Document {
+///     name: format!("projects/p/databases/(default)/documents/users/{}", id),
+///     fields: [(
+///         "age".to_string(),
+///         Value {
+///             value_type: Some(ValueType::IntegerValue(age)),
+///         },
+///     )]
+///     .into_iter()
+///     .collect(),
+///     create_time: None,
+///     update_time: None,
+/// };
+/// let documents = vec![document("u1", 20), document("u2", 10), document("u3", 30)];
+/// let query = Query::collection("users")
+///     .r#where(FieldPath::raw("age").greater_than(Value {
+///         value_type: Some(ValueType::IntegerValue(15)),
+///     })?)
+///     .order_by([FieldPath::raw("age").descending()]);
+/// assert_eq!(
+///     evaluate(&StructuredQuery::from(query), documents)?,
+///     vec![document("u3", 30), document("u1", 20)]
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+pub fn evaluate<I>(structured_query: &StructuredQuery, documents: I) -> Result<Vec<Document>>
+where
+    I: IntoIterator<Item = Document>,
+{
+    evaluate_in(structured_query, None, documents)
+}
+
+/// Evaluates the query on the collection(s) under the parent document path (such as `users/u1`).
+pub(crate) fn evaluate_in<I>(
+    structured_query: &StructuredQuery,
+    parent: Option<&str>,
+    documents: I,
+) -> Result<Vec<Document>>
+where
+    I: IntoIterator<Item = Document>,
+{
+    if structured_query.find_nearest.is_some() {
+        return Err(crate::Error::with_kind(
+            crate::ErrorKind::UnsupportedOperator,
+            "find_nearest is not supported by the evaluator",
+        ));
+    }
+    let collection_selector = match structured_query.from.as_slice() {
+        [collection_selector] => collection_selector,
+        _ => {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "from",
+                "the query must have exactly one collection selector",
+            )]));
+        }
+    };
+    let order_by = crate::query::normalized_order_by(structured_query)?
+        .into_iter()
+        .map(|order| {
+            let field = order.field.ok_or_else(|| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::InvalidFieldPath,
+                    "order field is required",
+                )
+            })?;
+            let direction =
+                structured_query::Direction::try_from(order.direction).map_err(|_| {
+                    crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("unknown direction: {}", order.direction),
+                    )
+                    .with_field_path(FieldPath::raw(field.field_path.clone()))
+                    .with_operator(order.direction.to_string())
+                })?;
+            Ok((
+                to_segments(&field)?,
+                direction == structured_query::Direction::Descending,
+            ))
+        })
+        .collect::<Result<Vec<(Vec<String>, bool)>>>()?;
+
+    let mut matched = vec![];
+    for document in documents {
+        if !in_collection(&document.name, parent, collection_selector) {
+            continue;
+        }
+        if let Some(filter) = &structured_query.r#where
+            && !matches(filter, &document).map_err(|e| e.at("where"))?
+        {
+            continue;
+        }
+        let Some(sort_key) = order_by
+            .iter()
+            .map(|(segments, _)| get_value(&document, segments))
+            .collect::<Option<Vec<Value>>>()
+        else {
+            continue;
+        };
+        matched.push((sort_key, document));
+    }
+
+    matched.sort_by(|(a, _), (b, _)| compare_sort_keys(&order_by, a, b));
+
+    let mut documents = vec![];
+    for (sort_key, document) in matched {
+        if let Some(start_at) = &structured_query.start_at {
+            // start_at.before == true means the cursor includes the position.
+            let ordering =
+                compare_cursor(&order_by, start_at, &sort_key).map_err(|e| e.at("start_at"))?;
+            if ordering == Ordering::Greater || (ordering == Ordering::Equal && !start_at.before) {
+                continue;
+            }
+        }
+        if let Some(end_at) = &structured_query.end_at {
+            // end_at.before == false means the cursor includes the position.
+            let ordering =
+                compare_cursor(&order_by, end_at, &sort_key).map_err(|e| e.at("end_at"))?;
+            if ordering == Ordering::Less || (ordering == Ordering::Equal && end_at.before) {
+                continue;
+            }
+        }
+        documents.push(document);
+    }
+
+    if structured_query.offset < 0 {
+        return Err(crate::Error::from_violations(vec![crate::Violation::new(
+            "offset",
+            format!(
+                "offset must not be negative (actual: {})",
+                structured_query.offset
+            ),
+        )]));
+    }
+    let limit = match structured_query.limit {
+        Some(limit) if limit < 0 => {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "limit",
+                format!("limit must not be negative (actual: {})", limit),
+            )]));
+        }
+        Some(limit) => limit as usize,
+        None => usize::MAX,
+    };
+    let documents = documents
+        .into_iter()
+        .skip(structured_query.offset as usize)
+        .take(limit);
+
+    match &structured_query.select {
+        None => Ok(documents.collect()),
+        Some(projection) => {
+            let fields = projection
+                .fields
+                .iter()
+                .map(to_segments)
+                .collect::<Result<Vec<Vec<String>>>>()?;
+            Ok(documents
+                .map(|document| project(document, &fields))
+                .collect())
+        }
+    }
+}
+
+/// Returns `true` if the document matches the filter.
+///
+/// The position of the error is relative to the filter (see [`crate::Error::position`]).
+pub(crate) fn matches(filter: &structured_query::Filter, document: &Document) -> Result<bool> {
+    use structured_query::filter::FilterType;
+    use structured_query::{composite_filter, field_filter, unary_filter};
+    match &filter.filter_type {
+        None => Err(crate::Error::from_violations(vec![crate::Violation::new(
+            "",
+            "filter_type is required",
+        )])
+        .at_root()),
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            let matches_at = |index: usize, filter: &structured_query::Filter| {
+                matches(filter, document).map_err(|e| e.at(format!("filters[{}]", index)))
+            };
+            match composite_filter::Operator::try_from(composite_filter.op) {
+                Ok(composite_filter::Operator::And) => {
+                    for (index, filter) in composite_filter.filters.iter().enumerate() {
+                        if !matches_at(index, filter)? {
+                            return Ok(false);
+                        }
+                    }
+                    Ok(true)
+                }
+                Ok(composite_filter::Operator::Or) => {
+                    for (index, filter) in composite_filter.filters.iter().enumerate() {
+                        if matches_at(index, filter)? {
+                            return Ok(true);
+                        }
+                    }
+                    Ok(false)
+                }
+                _ => Err(crate::Error::with_kind(
+                    crate::ErrorKind::UnsupportedOperator,
+                    format!("unknown composite filter operator: {}", composite_filter.op),
+                )
+                .with_operator(composite_filter.op.to_string())
+                .at_root()),
+            }
+        }
+        Some(FilterType::FieldFilter(field_filter)) => {
+            let field = field_filter.field.as_ref().ok_or_else(|| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::InvalidFieldPath,
+                    "field filter field is required",
+                )
+                .at_root()
+            })?;
+            let field_path = FieldPath::raw(field.field_path.clone());
+            let value = field_filter.value.as_ref().ok_or_else(|| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::ValueConversion,
+                    "field filter value is required",
+                )
+                .with_field_path(field_path.clone())
+                .at_root()
+            })?;
+            let op = field_filter::Operator::try_from(field_filter.op)
+                .ok()
+                .filter(|op| *op != field_filter::Operator::Unspecified)
+                .ok_or_else(|| {
+                    crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("unknown field filter operator: {}", field_filter.op),
+                    )
+                    .with_field_path(field_path.clone())
+                    .with_operator(field_filter.op.to_string())
+                    .at_root()
+                })?;
+            let other = get_value(document, &to_segments(field).map_err(|e| e.at_root())?);
+            Ok(matches_field_filter(op, value, other.as_ref()))
+        }
+        Some(FilterType::UnaryFilter(unary_filter)) => {
+            let field = match &unary_filter.operand_type {
+                Some(unary_filter::OperandType::Field(field)) => field,
+                None => {
+                    return Err(crate::Error::with_kind(
+                        crate::ErrorKind::InvalidFieldPath,
+                        "unary filter field is required",
+                    )
+                    .at_root());
+                }
+            };
+            let other = get_value(document, &to_segments(field).map_err(|e| e.at_root())?);
+            match unary_filter::Operator::try_from(unary_filter.op) {
+                Ok(unary_filter::Operator::IsNan) => Ok(other.as_ref().is_some_and(is_nan)),
+                Ok(unary_filter::Operator::IsNull) => Ok(other.as_ref().is_some_and(is_null)),
+                Ok(unary_filter::Operator::IsNotNan) => {
+                    Ok(other.is_some_and(|other| !is_null(&other) && !is_nan(&other)))
+                }
+                Ok(unary_filter::Operator::IsNotNull) => {
+                    Ok(other.is_some_and(|other| !is_null(&other)))
+                }
+                _ => Err(crate::Error::with_kind(
+                    crate::ErrorKind::UnsupportedOperator,
+                    format!("unknown unary filter operator: {}", unary_filter.op),
+                )
+                .with_field_path(FieldPath::raw(field.field_path.clone()))
+                .with_operator(unary_filter.op.to_string())
+                .at_root()),
+            }
+        }
+    }
+}
+
+fn matches_field_filter(
+    op: structured_query::field_filter::Operator,
+    value: &Value,
+    other: Option<&Value>,
+) -> bool {
+    use structured_query::field_filter::Operator;
+    let Some(other) = other else {
+        return false;
+    };
+    let array_values = |value: &Value| match &value.value_type {
+        Some(ValueType::ArrayValue(array_value)) => Some(array_value.values.clone()),
+        _ => None,
+    };
+    let contains = |values: &[Value], value: &Value| {
+        values.iter().any(|v| compare(v, value) == Ordering::Equal)
+    };
+    match op {
+        Operator::Unspecified => false,
+        Operator::LessThan => is_comparable(other, value) && compare(other, value).is_lt(),
+        Operator::LessThanOrEqual => is_comparable(other, value) && compare(other, value).is_le(),
+        Operator::GreaterThan => is_comparable(other, value) && compare(other, value).is_gt(),
+        Operator::GreaterThanOrEqual => {
+            is_comparable(other, value) && compare(other, value).is_ge()
+        }
+        Operator::Equal => compare(other, value).is_eq(),
+        Operator::NotEqual => !is_null(other) && compare(other, value).is_ne(),
+        Operator::ArrayContains => {
+            array_values(other).is_some_and(|values| contains(&values, value))
+        }
+        Operator::In => array_values(value).is_some_and(|values| contains(&values, other)),
+        Operator::ArrayContainsAny => match (array_values(other), array_values(value)) {
+            (Some(others), Some(values)) => others.iter().any(|other| contains(&values, other)),
+            _ => false,
+        },
+        Operator::NotIn => match array_values(value) {
+            Some(values) => {
+                !values.iter().any(is_null) && !is_null(other) && !contains(&values, other)
+            }
+            None => false,
+        },
+    }
+}
+
+fn to_segments(field_reference: &structured_query::FieldReference) -> Result<Vec<String>> {
+    FieldPath::raw(field_reference.field_path.as_str()).to_segments()
+}
+
+// "projects/{project_id}/databases/{database_id}/documents/{document_path}"
+fn in_collection(
+    name: &str,
+    parent: Option<&str>,
+    collection_selector: &structured_query::CollectionSelector,
+) -> bool {
+    let mut segments = name.split('/').skip(5).collect::<Vec<&str>>();
+    if let Some(parent) = parent {
+        let parent_segments = parent.split('/').collect::<Vec<&str>>();
+        if !segments.starts_with(&parent_segments) {
+            return false;
+        }
+        segments.drain(..parent_segments.len());
+    }
+    if segments.len() < 2 || segments.len() % 2 != 0 {
+        return false;
+    }
+    (collection_selector.all_descendants || segments.len() == 2)
+        && segments[segments.len() - 2] == collection_selector.collection_id
+}
+
+pub(crate) fn get_value(document: &Document, segments: &[String]) -> Option<Value> {
+    if segments.len() == 1 && segments[0] == "__name__" {
+        return Some(Value {
+            value_type: Some(ValueType::ReferenceValue(document.name.clone())),
+        });
+    }
+    let (first, rest) = segments.split_first()?;
+    let mut value = document.fields.get(first)?;
+    for segment in rest {
+        value = match &value.value_type {
+            Some(ValueType::MapValue(map_value)) => map_value.fields.get(segment)?,
+            _ => return None,
+        };
+    }
+    Some(value.clone())
+}
+
+fn compare_sort_keys(order_by: &[(Vec<String>, bool)], a: &[Value], b: &[Value]) -> Ordering {
+    for ((_, descending), (a, b)) in order_by.iter().zip(a.iter().zip(b.iter())) {
+        let ordering = compare(a, b);
+        let ordering = if *descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+// Compares the cursor position with the document position.
+fn compare_cursor(
+    order_by: &[(Vec<String>, bool)],
+    cursor: &Cursor,
+    sort_key: &[Value],
+) -> Result<Ordering> {
+    if cursor.values.len() > order_by.len() {
+        return Err(crate::Error::with_kind(
+            crate::ErrorKind::InvalidCursor,
+            "the cursor has more values than the order_by fields",
+        ));
+    }
+    Ok(compare_sort_keys(order_by, &cursor.values, sort_key))
+}
+
+fn project(mut document: Document, fields: &[Vec<String>]) -> Document {
+    let mut projected = MapValue::default();
+    for segments in fields {
+        if segments.len() == 1 && segments[0] == "__name__" {
+            continue;
+        }
+        if let Some(value) = get_value(&document, segments) {
+            insert_value(&mut projected, segments, value);
+        }
+    }
+    document.fields = projected.fields;
+    document
+}
+
+fn insert_value(map_value: &mut MapValue, segments: &[String], value: Value) {
+    let Some((first, rest)) = segments.split_first() else {
+        return;
+    };
+    if rest.is_empty() {
+        map_value.fields.insert(first.clone(), value);
+        return;
+    }
+    let entry = map_value.fields.entry(first.clone()).or_insert(Value {
+        value_type: Some(ValueType::MapValue(MapValue::default())),
+    });
+    if let Some(ValueType::MapValue(child)) = &mut entry.value_type {
+        insert_value(child, rest, value);
+    }
+}
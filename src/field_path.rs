@@ -1,9 +1,11 @@
-use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query::{
-    self, field_filter, unary_filter,
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ArrayValue, Value,
+    structured_query::{self, field_filter, unary_filter},
+    value::ValueType,
 };
 
 use crate::error::Result;
-use crate::{Filter, IntoValue, Order};
+use crate::{DistanceMeasure, Filter, FindNearest, IntoValue, Order, Vector};
 
 /// A Firestore Field Path.
 ///
@@ -36,11 +38,19 @@ use crate::{Filter, IntoValue, Order};
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct FieldPath(String);
 
-fn is_simple_field_name(s: &str) -> bool {
+pub(crate) fn is_simple_field_name(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
         && !s.starts_with(|c: char| -> bool { c.is_ascii_digit() })
 }
 
+fn escape_segment(s: &str) -> String {
+    if is_simple_field_name(s) {
+        s.to_string()
+    } else {
+        format!("`{}`", s.replace('\\', r#"\\"#).replace('`', r#"\`"#))
+    }
+}
+
 impl FieldPath {
     /// Creates a new field path.
     ///
@@ -91,13 +101,7 @@ impl FieldPath {
             field_names
                 .into_iter()
                 .map(Into::into)
-                .map(|s| {
-                    if is_simple_field_name(&s) {
-                        s
-                    } else {
-                        format!("`{}`", s.replace('\\', r#"\\"#).replace('`', r#"\`"#))
-                    }
-                })
+                .map(|s| escape_segment(&s))
                 .collect::<Vec<String>>()
                 .join("."),
         )
@@ -125,6 +129,242 @@ impl FieldPath {
     {
         Self(field_path.into())
     }
+
+    /// Returns the special field path `__name__` that refers to the document name (ID).
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldReference>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// assert_eq!(FieldPath::document_id(), FieldPath::raw("__name__"));
+    /// ```
+    pub fn document_id() -> Self {
+        Self::raw("__name__")
+    }
+
+    /// Parses the field path.
+    ///
+    /// The field path is a dot-delimited (`.`) string of segments where each segment is either a simple field name
+    /// or a backtick-quoted field name in which the backtick and the backslash are escaped with the backslash.
+    /// Returns an error if the field path contains an empty segment, an unterminated backtick, an invalid escape sequence,
+    /// or an unquoted segment that is not a simple field name.
+    ///
+    /// The parsed field path is stored in the canonical form (only the segments that need it are quoted).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_field_path_parse() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// assert_eq!(FieldPath::parse("a.b")?, FieldPath::new(["a", "b"]));
+    /// assert_eq!(FieldPath::parse("`a`.`x&y`")?, FieldPath::new(["a", "x&y"]));
+    /// assert_eq!(FieldPath::parse(r#"`a\`b`"#)?, FieldPath::new(["a`b"]));
+    /// assert_eq!("a.b".parse::<FieldPath>()?, FieldPath::new(["a", "b"]));
+    /// assert!(FieldPath::parse("a..b").is_err());
+    /// assert!(FieldPath::parse("x&y").is_err());
+    /// assert!(FieldPath::parse("0a").is_err());
+    /// assert!(FieldPath::parse(r#"`a\b`"#).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse(s: &str) -> Result<Self> {
+        Ok(Self::new(parse_segments(s, true)?))
+    }
+
+    /// Returns the field path as a string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// assert_eq!(FieldPath::raw("field1").as_str(), "field1");
+    /// assert_eq!(FieldPath::new(["foo", "x&y"]).as_str(), "foo.`x&y`");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns a new field path with the field name appended as the last segment.
+    ///
+    /// The field name is escaped if necessary.
+    ///
+    /// Returns an error if the field name is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_field_path_child() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// assert_eq!(FieldPath::raw("a").child("b")?, FieldPath::new(["a", "b"]));
+    /// assert_eq!(FieldPath::raw("a").child("x&y")?.as_str(), "a.`x&y`");
+    /// assert!(FieldPath::raw("a").child("").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn child<S>(&self, field_name: S) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let field_name = field_name.into();
+        if field_name.is_empty() {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::InvalidFieldPath,
+                format!("field name must not be empty (parent: {})", self.0),
+            )
+            .with_field_path(self.clone()));
+        }
+        Ok(Self(format!("{}.{}", self.0, escape_segment(&field_name))))
+    }
+
+    /// Returns a new field path with the segments of the other field path appended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// assert_eq!(
+    ///     FieldPath::new(["a", "b"]).join(&FieldPath::new(["c", "x&y"])),
+    ///     FieldPath::new(["a", "b", "c", "x&y"])
+    /// );
+    /// ```
+    pub fn join(&self, other: &FieldPath) -> Self {
+        Self(format!("{}.{}", self.0, other.0))
+    }
+
+    /// Returns the field path without the last segment.
+    ///
+    /// Returns `None` if the field path has a single segment or is not a valid field path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// assert_eq!(
+    ///     FieldPath::new(["a", "x.y", "c"]).parent(),
+    ///     Some(FieldPath::new(["a", "x.y"]))
+    /// );
+    /// assert_eq!(FieldPath::raw("a").parent(), None);
+    /// ```
+    pub fn parent(&self) -> Option<Self> {
+        let segments = self.to_segments().ok()?;
+        match segments.split_last() {
+            Some((_, parent)) if !parent.is_empty() => Some(Self::new(parent)),
+            _ => None,
+        }
+    }
+
+    /// Returns the unescaped segments (field names) of the field path.
+    ///
+    /// Returns an error if the field path is not a valid field path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_field_path_segments() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// assert_eq!(
+    ///     FieldPath::raw(r#"a.`x.y`.`b\`c`"#).segments()?,
+    ///     vec!["a", "x.y", "b`c"]
+    /// );
+    /// assert!(FieldPath::raw("a..b").segments().is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn segments(&self) -> Result<Vec<String>> {
+        self.to_segments()
+    }
+
+    /// Returns `true` if the segments of the other field path are a prefix of the segments of this field path.
+    ///
+    /// Returns `false` if either field path is not a valid field path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_structured_query::FieldPath;
+    ///
+    /// let field_path1 = FieldPath::raw("a.`b`.c");
+    /// assert!(field_path1.starts_with(&FieldPath::raw("a")));
+    /// assert!(field_path1.starts_with(&FieldPath::raw("a.b")));
+    /// assert!(field_path1.starts_with(&field_path1));
+    /// assert!(!field_path1.starts_with(&FieldPath::raw("a.bc")));
+    /// assert!(!FieldPath::raw("ab").starts_with(&FieldPath::raw("a")));
+    /// ```
+    pub fn starts_with(&self, other: &FieldPath) -> bool {
+        match (self.to_segments(), other.to_segments()) {
+            (Ok(segments), Ok(prefix)) => segments.starts_with(&prefix),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn to_segments(&self) -> Result<Vec<String>> {
+        parse_segments(&self.0, false)
+    }
+}
+
+/// Parses the field path into unescaped segments.
+///
+/// If `strict` is `true`, the unquoted segments must be simple field names.
+fn parse_segments(s: &str, strict: bool) -> Result<Vec<String>> {
+    let invalid = |reason: &str| {
+        crate::Error::with_kind(
+            crate::ErrorKind::InvalidFieldPath,
+            format!("invalid field path `{}`: {}", s, reason),
+        )
+        .with_field_path(FieldPath::raw(s))
+    };
+    let mut segments = vec![];
+    let mut chars = s.chars().peekable();
+    loop {
+        let mut segment = String::new();
+        if chars.peek() == Some(&'`') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    None => return Err(invalid("unterminated backtick")),
+                    Some('`') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c @ ('`' | '\\')) => segment.push(c),
+                        _ => return Err(invalid("invalid escape sequence")),
+                    },
+                    Some(c) => segment.push(c),
+                }
+            }
+            if !matches!(chars.peek(), None | Some('.')) {
+                return Err(invalid("unexpected character after backtick"));
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != '.') {
+                if c == '`' {
+                    return Err(invalid("unexpected backtick"));
+                }
+                segment.push(c);
+            }
+            if strict && !is_simple_field_name(&segment) {
+                return Err(invalid(&format!(
+                    "segment `{}` must be quoted with backticks",
+                    segment
+                )));
+            }
+        }
+        if segment.is_empty() {
+            return Err(invalid("empty segment"));
+        }
+        segments.push(segment);
+        match chars.next() {
+            None => return Ok(segments),
+            Some(_) => continue,
+        }
+    }
 }
 
 // for Filter
@@ -180,6 +420,8 @@ impl FieldPath {
 
     /// Creates a new `FieldFilter` with the `ArrayContainsAny` operator.
     ///
+    /// The value must be a non-empty array of up to 30 values.
+    ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter>
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter.Operator.ENUM_VALUES.google.firestore.v1.StructuredQuery.FieldFilter.Operator.ARRAY_CONTAINS_ANY>
     ///
@@ -250,6 +492,8 @@ impl FieldPath {
 
     /// Creates a new `FieldFilter` with the `Equal` operator.
     ///
+    /// `null` and `NaN` values create the `IsNull` and `IsNan` `UnaryFilter`s.
+    ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter>
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter.Operator.ENUM_VALUES.google.firestore.v1.StructuredQuery.FieldFilter.Operator.EQUAL>
     ///
@@ -398,6 +642,8 @@ impl FieldPath {
 
     /// Creates a new `FieldFilter` with the `In` operator.
     ///
+    /// The value must be a non-empty array of up to 30 values.
+    ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter>
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter.Operator.ENUM_VALUES.google.firestore.v1.StructuredQuery.FieldFilter.Operator.IN>
     ///
@@ -696,6 +942,8 @@ impl FieldPath {
 
     /// Creates a new `FieldFilter` with the `NotEqual` operator.
     ///
+    /// `null` and `NaN` values create the `IsNotNull` and `IsNotNan` `UnaryFilter`s.
+    ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter>
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter.Operator.ENUM_VALUES.google.firestore.v1.StructuredQuery.FieldFilter.Operator.NOT_EQUAL>
     ///
@@ -744,6 +992,8 @@ impl FieldPath {
 
     /// Creates a new `FieldFilter` with the `NotIn` operator.
     ///
+    /// The value must be a non-empty array of up to 10 values.
+    ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter>
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter.Operator.ENUM_VALUES.google.firestore.v1.StructuredQuery.FieldFilter.Operator.NOT_IN>
     ///
@@ -809,6 +1059,227 @@ impl FieldPath {
     }
 }
 
+// for document ID Filter
+impl FieldPath {
+    /// Creates a new `FieldFilter` with the `Equal` operator on the document ID.
+    ///
+    /// The `parent` is the parent resource name of the query, such as `projects/{project_id}/databases/{database_id}/documents`
+    /// or `projects/{project_id}/databases/{database_id}/documents/{document_path}`.
+    /// The `document_id` is a bare document ID in the collection `collection_id` under the `parent`,
+    /// or a slash-separated document path relative to the `parent` (for collection group queries) whose collection ID is `collection_id`.
+    ///
+    /// Returns an error if the field path is not `__name__` or the `parent`, the `collection_id` or the `document_id` is invalid.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldReference>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_field_path_id_equal() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::FieldPath;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+    /// let parent = "projects/p/databases/(default)/documents";
+    /// assert_eq!(
+    ///     FieldPath::document_id().id_equal(parent, "users", "u1")?,
+    ///     FieldPath::document_id().equal(Value {
+    ///         value_type: Some(ValueType::ReferenceValue(
+    ///             "projects/p/databases/(default)/documents/users/u1".to_string()
+    ///         )),
+    ///     })?
+    /// );
+    /// // collection group
+    /// assert_eq!(
+    ///     FieldPath::document_id().id_equal(parent, "messages", "rooms/r1/messages/m1")?,
+    ///     FieldPath::document_id().equal(Value {
+    ///         value_type: Some(ValueType::ReferenceValue(
+    ///             "projects/p/databases/(default)/documents/rooms/r1/messages/m1".to_string()
+    ///         )),
+    ///     })?
+    /// );
+    /// assert!(FieldPath::document_id().id_equal(parent, "users", "rooms/r1").is_err());
+    /// assert!(FieldPath::raw("id").id_equal(parent, "users", "u1").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn id_equal(&self, parent: &str, collection_id: &str, document_id: &str) -> Result<Filter> {
+        let value = self.document_reference(parent, collection_id, document_id)?;
+        Filter::field(self.clone(), field_filter::Operator::Equal, value)
+    }
+
+    /// Creates a new `FieldFilter` with the `In` operator on the document IDs.
+    ///
+    /// See [`FieldPath::id_equal`] for the `parent`, the `collection_id` and the `document_ids`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_field_path_id_in() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::FieldPath;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, ArrayValue, Value,
+    /// };
+    /// let parent = "projects/p/databases/(default)/documents";
+    /// let reference = |s: &str| Value {
+    ///     value_type: Some(ValueType::ReferenceValue(format!("{}/{}", parent, s))),
+    /// };
+    /// assert_eq!(
+    ///     FieldPath::document_id().id_in(parent, "users", ["u1", "u2"])?,
+    ///     FieldPath::document_id().r#in(Value {
+    ///         value_type: Some(ValueType::ArrayValue(ArrayValue {
+    ///             values: vec![reference("users/u1"), reference("users/u2")],
+    ///         })),
+    ///     })?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn id_in<I>(&self, parent: &str, collection_id: &str, document_ids: I) -> Result<Filter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let values = document_ids
+            .into_iter()
+            .map(|document_id| self.document_reference(parent, collection_id, document_id.as_ref()))
+            .collect::<Result<Vec<Value>>>()?;
+        Filter::field(
+            self.clone(),
+            field_filter::Operator::In,
+            Value {
+                value_type: Some(ValueType::ArrayValue(ArrayValue { values })),
+            },
+        )
+    }
+
+    /// Creates a new range filter on the document IDs.
+    ///
+    /// The bounds are converted into `GreaterThan`, `GreaterThanOrEqual`, `LessThan` and `LessThanOrEqual` filters.
+    /// If both bounds are specified, the filters are combined with `And`.
+    /// See [`FieldPath::id_equal`] for the `parent`, the `collection_id` and the document IDs.
+    ///
+    /// Returns an error if the range is unbounded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_field_path_id_range() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+    /// let parent = "projects/p/databases/(default)/documents";
+    /// let reference = |s: &str| Value {
+    ///     value_type: Some(ValueType::ReferenceValue(format!("{}/{}", parent, s))),
+    /// };
+    /// assert_eq!(
+    ///     FieldPath::document_id().id_range(parent, "users", "a".."m")?,
+    ///     Filter::and([
+    ///         FieldPath::document_id().greater_than_or_equal(reference("users/a"))?,
+    ///         FieldPath::document_id().less_than(reference("users/m"))?,
+    ///     ])
+    /// );
+    /// assert_eq!(
+    ///     FieldPath::document_id().id_range(parent, "users", ..="m")?,
+    ///     FieldPath::document_id().less_than_or_equal(reference("users/m"))?
+    /// );
+    /// assert!(FieldPath::document_id().id_range::<_, &str>(parent, "users", ..).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn id_range<R, S>(&self, parent: &str, collection_id: &str, range: R) -> Result<Filter>
+    where
+        R: std::ops::RangeBounds<S>,
+        S: AsRef<str>,
+    {
+        use std::ops::Bound;
+        let mut filters = vec![];
+        match range.start_bound() {
+            Bound::Included(start) => filters.push(Filter::field(
+                self.clone(),
+                field_filter::Operator::GreaterThanOrEqual,
+                self.document_reference(parent, collection_id, start.as_ref())?,
+            )?),
+            Bound::Excluded(start) => filters.push(Filter::field(
+                self.clone(),
+                field_filter::Operator::GreaterThan,
+                self.document_reference(parent, collection_id, start.as_ref())?,
+            )?),
+            Bound::Unbounded => {}
+        }
+        match range.end_bound() {
+            Bound::Included(end) => filters.push(Filter::field(
+                self.clone(),
+                field_filter::Operator::LessThanOrEqual,
+                self.document_reference(parent, collection_id, end.as_ref())?,
+            )?),
+            Bound::Excluded(end) => filters.push(Filter::field(
+                self.clone(),
+                field_filter::Operator::LessThan,
+                self.document_reference(parent, collection_id, end.as_ref())?,
+            )?),
+            Bound::Unbounded => {}
+        }
+        match filters.len() {
+            0 => Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "",
+                "id_range requires at least one bound",
+            )])
+            .with_field_path(self.clone())
+            .at_root()),
+            1 => Ok(filters.remove(0)),
+            _ => Ok(Filter::and(filters)),
+        }
+    }
+
+    fn document_reference(
+        &self,
+        parent: &str,
+        collection_id: &str,
+        document_id: &str,
+    ) -> Result<Value> {
+        let value_conversion = |message: String| {
+            crate::Error::with_kind(crate::ErrorKind::ValueConversion, message)
+                .with_field_path(self.clone())
+        };
+        if self.to_segments()? != ["__name__"] {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::InvalidFieldPath,
+                format!(
+                    "document ID filters require the field path __name__ (actual: {})",
+                    self.0
+                ),
+            )
+            .with_field_path(self.clone()));
+        }
+        let (database_name, parent_document) = crate::resource_name::parse_parent(parent)
+            .map_err(|_| value_conversion(format!("invalid parent: {}", parent)))?;
+        crate::validation::check_collection_id(collection_id).map_err(value_conversion)?;
+        let document_name = if document_id.contains('/') {
+            let segments = document_id.split('/').collect::<Vec<&str>>();
+            if segments.len() % 2 != 0 || segments[segments.len() - 2] != collection_id {
+                return Err(value_conversion(format!(
+                    "invalid document path: {} (must be a document in the collection {})",
+                    document_id, collection_id
+                )));
+            }
+            match &parent_document {
+                Some(parent_document) => {
+                    database_name.document(&format!("{}/{}", parent_document.path(), document_id))
+                }
+                None => database_name.document(document_id),
+            }
+        } else {
+            match &parent_document {
+                Some(parent_document) => parent_document.collection(collection_id),
+                None => database_name.collection(collection_id),
+            }
+            .and_then(|collection_name| collection_name.document(document_id))
+        }
+        .map_err(|e| value_conversion(e.to_string()))?;
+        Ok(Value {
+            value_type: Some(ValueType::ReferenceValue(document_name.to_string())),
+        })
+    }
+}
+
 // for Order
 impl FieldPath {
     /// Creates a new `Order` with the `Ascending` direction.
@@ -868,8 +1339,115 @@ impl FieldPath {
     }
 }
 
+// for FindNearest
+impl FieldPath {
+    /// Creates a new `FindNearest` for the vector field.
+    ///
+    /// Returns an error if the `query_vector` is empty, has more than 2048 dimensions or contains non-finite values,
+    /// or if the `limit` is not between 1 and 1000.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FindNearest>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_field_path_find_nearest() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{DistanceMeasure, FieldPath, IntoValue, Vector};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query;
+    /// let find_nearest = FieldPath::raw("embedding").find_nearest(
+    ///     Vector::new([1.0, 2.0, 3.0]),
+    ///     DistanceMeasure::Euclidean,
+    ///     5,
+    /// )?;
+    /// assert_eq!(
+    ///     structured_query::FindNearest::from(find_nearest),
+    ///     structured_query::FindNearest {
+    ///         vector_field: Some(structured_query::FieldReference {
+    ///             field_path: "embedding".to_string(),
+    ///         }),
+    ///         query_vector: Some(Vector::new([1.0, 2.0, 3.0]).into_value()?),
+    ///         distance_measure: structured_query::find_nearest::DistanceMeasure::Euclidean as i32,
+    ///         limit: Some(5),
+    ///         distance_result_field: "".to_string(),
+    ///         distance_threshold: None,
+    ///     }
+    /// );
+    ///
+    /// let vector_field = FieldPath::raw("embedding");
+    /// assert!(vector_field.find_nearest(Vector::new([]), DistanceMeasure::Cosine, 5).is_err());
+    /// assert!(vector_field.find_nearest(Vector::new([f64::NAN]), DistanceMeasure::Cosine, 5).is_err());
+    /// assert!(vector_field.find_nearest(Vector::new([1.0]), DistanceMeasure::Cosine, 0).is_err());
+    /// assert!(vector_field.find_nearest(Vector::new([1.0]), DistanceMeasure::Cosine, 1001).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn find_nearest(
+        &self,
+        query_vector: Vector,
+        distance_measure: DistanceMeasure,
+        limit: i32,
+    ) -> Result<FindNearest> {
+        FindNearest::new(self.clone(), query_vector, distance_measure, limit)
+    }
+}
+
+/// Formats the field path as a string (the same as [`FieldPath::as_str`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_structured_query::FieldPath;
+///
+/// assert_eq!(FieldPath::new(["a", "x&y"]).to_string(), "a.`x&y`");
+/// ```
+impl std::fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Parses the field path (see [`FieldPath::parse`]).
+impl std::str::FromStr for FieldPath {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
 impl std::convert::From<FieldPath> for structured_query::FieldReference {
     fn from(FieldPath(field_path): FieldPath) -> Self {
         structured_query::FieldReference { field_path }
     }
 }
+
+/// Converts a `FieldReference` into a `FieldPath`.
+///
+/// Returns an error if the field path is not a valid field path.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_field_path_try_from() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::FieldPath;
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query;
+/// let field_path = FieldPath::try_from(structured_query::FieldReference {
+///     field_path: "a.`x&y`".to_string(),
+/// })?;
+/// assert_eq!(field_path, FieldPath::new(["a", "x&y"]));
+/// assert!(FieldPath::try_from(structured_query::FieldReference {
+///     field_path: "a..b".to_string(),
+/// })
+/// .is_err());
+/// #     Ok(())
+/// # }
+/// ```
+impl std::convert::TryFrom<structured_query::FieldReference> for FieldPath {
+    type Error = crate::Error;
+
+    fn try_from(field_reference: structured_query::FieldReference) -> Result<Self> {
+        let field_path = Self(field_reference.field_path);
+        field_path.to_segments()?;
+        Ok(field_path)
+    }
+}
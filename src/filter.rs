@@ -1,8 +1,10 @@
 use crate::field_path::FieldPath;
+use crate::ordered_value::{is_nan, is_null};
 use crate::{IntoValue, error::Result};
 
-use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query::{
-    self, field_filter, unary_filter,
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    Value,
+    structured_query::{self, composite_filter, field_filter, unary_filter},
 };
 
 /// A Firestore query filter.
@@ -157,13 +159,790 @@ impl Filter {
     }
 }
 
+impl Filter {
+    /// Returns `true` if the document matches the filter.
+    ///
+    /// See [`evaluate`](crate::evaluate) for the supported semantics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_filter_matches() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Document, MapValue, Value,
+    /// };
+    /// let document = Document {
+    ///     name: "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///     fields: [(
+    ///         "a".to_string(),
+    ///         Value {
+    ///             value_type: Some(ValueType::MapValue(MapValue {
+    ///                 fields: [(
+    ///                     "x&y".to_string(),
+    ///                     Value {
+    ///                         value_type: Some(ValueType::IntegerValue(1)),
+    ///                     },
+    ///                 )]
+    ///                 .into_iter()
+    ///                 .collect(),
+    ///             })),
+    ///         },
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    ///     create_time: None,
+    ///     update_time: None,
+    /// };
+    /// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+    /// let two = Value { value_type: Some(ValueType::DoubleValue(2.0)) };
+    /// assert!(FieldPath::new(["a", "x&y"]).equal(one.clone())?.matches(&document)?);
+    /// assert!(FieldPath::new(["a", "x&y"]).less_than(two.clone())?.matches(&document)?);
+    /// assert!(!FieldPath::raw("b").is_null()?.matches(&document)?);
+    /// assert!(Filter::or([
+    ///     FieldPath::raw("b").equal(one.clone())?,
+    ///     FieldPath::raw("`a`.`x&y`").not_equal(two.clone())?,
+    /// ])
+    /// .matches(&document)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn matches(
+        &self,
+        document: &googleapis_tonic_google_firestore_v1::google::firestore::v1::Document,
+    ) -> Result<bool> {
+        crate::evaluator::matches(&self.0, document)
+    }
+}
+
+impl Filter {
+    /// Returns the number of disjunctions of the filter in disjunctive normal form.
+    ///
+    /// Each value of an `in` or `array_contains_any` filter counts as a disjunction.
+    /// Firestore limits the number of disjunctions to 30.
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/queries#limits_on_or_queries>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_filter_disjunction_count() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, ArrayValue, Value,
+    /// };
+    /// let int = |i: i64| Value { value_type: Some(ValueType::IntegerValue(i)) };
+    /// let filter1 = Filter::and([
+    ///     Filter::or([
+    ///         FieldPath::raw("a").equal(int(1))?,
+    ///         FieldPath::raw("b").equal(int(2))?,
+    ///     ]),
+    ///     FieldPath::raw("c").r#in(Value {
+    ///         value_type: Some(ValueType::ArrayValue(ArrayValue {
+    ///             values: vec![int(1), int(2), int(3)],
+    ///         })),
+    ///     })?,
+    /// ]);
+    /// assert_eq!(filter1.disjunction_count(), 6);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn disjunction_count(&self) -> usize {
+        crate::validation::disjunction_count(&self.0)
+    }
+
+    /// Expands the filter into disjunctive normal form.
+    ///
+    /// The result is simplified (see [`Filter::simplify`]): an `OR` of `AND`s of field and unary filters,
+    /// an `AND` of field and unary filters, or a single field or unary filter.
+    /// The size of the result may grow exponentially with the number of nested composite filters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_filter_disjunctive_normal_form() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// let a = FieldPath::raw("a").is_null()?;
+    /// let b = FieldPath::raw("b").is_null()?;
+    /// let c = FieldPath::raw("c").is_null()?;
+    /// let filter1 = Filter::and([Filter::or([a.clone(), b.clone()]), c.clone()]);
+    /// assert_eq!(
+    ///     filter1.disjunctive_normal_form(),
+    ///     Filter::or([
+    ///         Filter::and([a.clone(), c.clone()]),
+    ///         Filter::and([b.clone(), c.clone()]),
+    ///     ])
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn disjunctive_normal_form(self) -> Self {
+        Self(crate::normalization::disjunctive_normal_form(self.0))
+    }
+
+    /// Merges the nested composite filters that have the same operator as their parent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_filter_flatten() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// let a = FieldPath::raw("a").is_null()?;
+    /// let b = FieldPath::raw("b").is_null()?;
+    /// let c = FieldPath::raw("c").is_null()?;
+    /// let filter1 = Filter::and([Filter::and([a.clone(), b.clone()]), Filter::or([c.clone()])]);
+    /// assert_eq!(
+    ///     filter1.flatten(),
+    ///     Filter::and([a.clone(), b.clone(), Filter::or([c.clone()])])
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn flatten(self) -> Self {
+        Self(crate::normalization::flatten(self.0))
+    }
+
+    /// Returns the logical negation of the filter.
+    ///
+    /// Firestore has no `NOT` filter, so the field and unary filters are replaced with their inverse operators
+    /// (`<` with `>=`, `==` with `!=`, `in` with `not_in`, `is_null` with `is_not_null`, ...)
+    /// and the composite filters are negated using De Morgan's laws.
+    ///
+    /// Note that Firestore filters never match documents that do not contain the field,
+    /// and the range filters only match values of the same type,
+    /// so a filter and its negation may both exclude the same document.
+    ///
+    /// Returns an error if the filter contains an `array_contains` or `array_contains_any` filter,
+    /// or an `in` filter with more values than `not_in` supports (10).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_filter_not() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+    /// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+    /// let filter1 = Filter::and([
+    ///     FieldPath::raw("field1").less_than(one.clone())?,
+    ///     FieldPath::raw("field2").is_null()?,
+    /// ]);
+    /// assert_eq!(
+    ///     filter1.not()?,
+    ///     Filter::or([
+    ///         FieldPath::raw("field1").greater_than_or_equal(one.clone())?,
+    ///         FieldPath::raw("field2").is_not_null()?,
+    ///     ])
+    /// );
+    /// assert_eq!(
+    ///     FieldPath::raw("field1").array_contains(one)?.not().unwrap_err().to_string(),
+    ///     "ARRAY_CONTAINS filter cannot be negated (field: field1)"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Result<Self> {
+        crate::normalization::negate(self.0).map(Self)
+    }
+
+    /// Simplifies the filter.
+    ///
+    /// - merges the nested composite filters that have the same operator as their parent
+    /// - drops the duplicate children
+    /// - unwraps the single-child composite filters
+    /// - treats an empty `AND` as true and an empty `OR` as false
+    ///   (e.g. `AND(x, OR())` is `OR()` and `OR(x, AND())` is `AND()`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_filter_simplify() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// let a = FieldPath::raw("a").is_null()?;
+    /// let b = FieldPath::raw("b").is_null()?;
+    /// let filter1 = Filter::and([
+    ///     Filter::or([a.clone()]),
+    ///     Filter::and([b.clone(), a.clone()]),
+    ///     Filter::and([]),
+    /// ]);
+    /// assert_eq!(filter1.simplify(), Filter::and([a.clone(), b.clone()]));
+    /// assert_eq!(
+    ///     Filter::or([a.clone(), Filter::and([])]).simplify(),
+    ///     Filter::and([])
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn simplify(self) -> Self {
+        Self(crate::normalization::simplify(self.0))
+    }
+}
+
+/// Combines the filters with `AND`, flattening the composite `AND` filters.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_filter_bitand() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Filter};
+/// let a = FieldPath::raw("a").is_null()?;
+/// let b = FieldPath::raw("b").is_null()?;
+/// let c = FieldPath::raw("c").is_null()?;
+/// assert_eq!(a.clone() & b.clone(), Filter::and([a.clone(), b.clone()]));
+/// assert_eq!(
+///     a.clone() & b.clone() & c.clone(),
+///     Filter::and([a.clone(), b.clone(), c.clone()])
+/// );
+/// assert_eq!(
+///     (a.clone() | b.clone()) & c.clone(),
+///     Filter::and([Filter::or([a, b]), c])
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::BitAnd for Filter {
+    type Output = Filter;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(compose(composite_filter::Operator::And, self.0, rhs.0))
+    }
+}
+
+/// Combines the filters with `OR`, flattening the composite `OR` filters.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_filter_bitor() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Filter};
+/// let a = FieldPath::raw("a").is_null()?;
+/// let b = FieldPath::raw("b").is_null()?;
+/// let c = FieldPath::raw("c").is_null()?;
+/// assert_eq!(a.clone() | b.clone(), Filter::or([a.clone(), b.clone()]));
+/// assert_eq!(
+///     a.clone() | b.clone() | c.clone(),
+///     Filter::or([a.clone(), b.clone(), c.clone()])
+/// );
+/// assert_eq!(
+///     a.clone() | (b.clone() & c.clone()),
+///     Filter::or([a, Filter::and([b, c])])
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::BitOr for Filter {
+    type Output = Filter;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(compose(composite_filter::Operator::Or, self.0, rhs.0))
+    }
+}
+
+/// Returns the logical negation of the filter (see [`Filter::not`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_filter_not_operator() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::FieldPath;
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+/// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+/// assert_eq!(
+///     (!FieldPath::raw("field1").equal(one.clone())?)?,
+///     FieldPath::raw("field1").not_equal(one.clone())?
+/// );
+/// assert!((!FieldPath::raw("field1").array_contains(one)?).is_err());
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::Not for Filter {
+    type Output = Result<Filter>;
+
+    fn not(self) -> Self::Output {
+        Filter::not(self)
+    }
+}
+
+/// Combines the filters with the operator.
+///
+/// The children of a composite filter with the same operator are merged into the result.
+pub(crate) fn compose(
+    op: composite_filter::Operator,
+    lhs: structured_query::Filter,
+    rhs: structured_query::Filter,
+) -> structured_query::Filter {
+    let mut filters = vec![];
+    for filter in [lhs, rhs] {
+        match filter.filter_type {
+            Some(structured_query::filter::FilterType::CompositeFilter(composite_filter))
+                if composite_filter.op == op as i32 =>
+            {
+                filters.extend(composite_filter.filters)
+            }
+            filter_type => filters.push(structured_query::Filter { filter_type }),
+        }
+    }
+    structured_query::Filter {
+        filter_type: Some(structured_query::filter::FilterType::CompositeFilter(
+            structured_query::CompositeFilter {
+                op: op as i32,
+                filters,
+            },
+        )),
+    }
+}
+
 impl From<Filter> for structured_query::Filter {
     fn from(filter: Filter) -> Self {
         filter.0
     }
 }
 
+/// Converts a `structured_query::Filter` into a `Filter`.
+///
+/// Returns an error if the filter (or a nested filter) has no filter type, no field, no value,
+/// an invalid field path, or an unspecified or unknown operator.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_filter_try_from() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Filter};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     structured_query, value::ValueType, Value,
+/// };
+/// let filter1 = Filter::or([
+///     FieldPath::raw("field1").equal(Value { value_type: Some(ValueType::IntegerValue(1)) })?,
+///     FieldPath::raw("field2").is_null()?,
+/// ]);
+/// assert_eq!(
+///     Filter::try_from(structured_query::Filter::from(filter1.clone()))?,
+///     filter1
+/// );
+/// let filter2 = structured_query::Filter {
+///     filter_type: Some(structured_query::filter::FilterType::UnaryFilter(
+///         structured_query::UnaryFilter {
+///             op: 100,
+///             operand_type: Some(structured_query::unary_filter::OperandType::Field(
+///                 structured_query::FieldReference {
+///                     field_path: "field1".to_string(),
+///                 },
+///             )),
+///         },
+///     )),
+/// };
+/// assert!(Filter::try_from(filter2).is_err());
+/// #     Ok(())
+/// # }
+/// ```
+impl std::convert::TryFrom<structured_query::Filter> for Filter {
+    type Error = crate::Error;
+
+    fn try_from(filter: structured_query::Filter) -> Result<Self> {
+        check_filter(&filter)?;
+        Ok(Self(filter))
+    }
+}
+
+/// Checks the filter recursively.
+///
+/// The position of the error is relative to the filter (see [`crate::Error::position`]).
+fn check_filter(filter: &structured_query::Filter) -> Result<()> {
+    use structured_query::filter::FilterType;
+    let check_field = |field_reference: Option<&structured_query::FieldReference>,
+                       operator: &str|
+     -> Result<()> {
+        let field_reference = field_reference.ok_or_else(|| {
+            crate::Error::with_kind(
+                crate::ErrorKind::InvalidFieldPath,
+                "filter field is required",
+            )
+        })?;
+        FieldPath::try_from(field_reference.clone()).map_err(|e| e.with_operator(operator))?;
+        Ok(())
+    };
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            match composite_filter::Operator::try_from(composite_filter.op) {
+                Ok(composite_filter::Operator::And | composite_filter::Operator::Or) => {}
+                Ok(composite_filter::Operator::Unspecified) | Err(_) => {
+                    return Err(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("invalid composite filter operator: {}", composite_filter.op),
+                    )
+                    .with_operator(operator_name(
+                        composite_filter.op,
+                        composite_filter::Operator::try_from(composite_filter.op)
+                            .ok()
+                            .map(|op| op.as_str_name()),
+                    ))
+                    .at_root());
+                }
+            }
+            for (index, filter) in composite_filter.filters.iter().enumerate() {
+                check_filter(filter).map_err(|e| e.at(format!("filters[{}]", index)))?;
+            }
+            Ok(())
+        }
+        Some(FilterType::FieldFilter(field_filter)) => {
+            let operator = operator_name(
+                field_filter.op,
+                field_filter::Operator::try_from(field_filter.op)
+                    .ok()
+                    .map(|op| op.as_str_name()),
+            );
+            let field_path = field_filter
+                .field
+                .as_ref()
+                .map(|field_reference| FieldPath::raw(field_reference.field_path.clone()));
+            let with_context = |e: crate::Error| -> crate::Error {
+                let e = e.with_operator(operator.clone()).at_root();
+                match &field_path {
+                    Some(field_path) => e.with_field_path(field_path.clone()),
+                    None => e,
+                }
+            };
+            match field_filter::Operator::try_from(field_filter.op) {
+                Ok(field_filter::Operator::Unspecified) | Err(_) => {
+                    return Err(with_context(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("invalid field filter operator: {}", field_filter.op),
+                    )));
+                }
+                Ok(_) => {}
+            }
+            check_field(field_filter.field.as_ref(), &operator).map_err(with_context)?;
+            if field_filter.value.is_none() {
+                return Err(with_context(crate::Error::with_kind(
+                    crate::ErrorKind::ValueConversion,
+                    "field filter value is required",
+                )));
+            }
+            Ok(())
+        }
+        Some(FilterType::UnaryFilter(unary_filter)) => {
+            let operator = operator_name(
+                unary_filter.op,
+                unary_filter::Operator::try_from(unary_filter.op)
+                    .ok()
+                    .map(|op| op.as_str_name()),
+            );
+            let field_reference = unary_filter
+                .operand_type
+                .as_ref()
+                .map(|unary_filter::OperandType::Field(field_reference)| field_reference);
+            let with_context = |e: crate::Error| -> crate::Error {
+                let e = e.with_operator(operator.clone()).at_root();
+                match field_reference {
+                    Some(field_reference) => {
+                        e.with_field_path(FieldPath::raw(field_reference.field_path.clone()))
+                    }
+                    None => e,
+                }
+            };
+            match unary_filter::Operator::try_from(unary_filter.op) {
+                Ok(unary_filter::Operator::Unspecified) | Err(_) => {
+                    return Err(with_context(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("invalid unary filter operator: {}", unary_filter.op),
+                    )));
+                }
+                Ok(_) => {}
+            }
+            check_field(field_reference, &operator).map_err(with_context)
+        }
+        None => Err(crate::Error::from_violations(vec![crate::Violation::new(
+            "",
+            "filter type is required",
+        )])
+        .at_root()),
+    }
+}
+
+/// Returns the name of the operator, or the number if the operator is unknown.
+pub(crate) fn operator_name(op: i32, name: Option<&str>) -> String {
+    name.map(str::to_string).unwrap_or_else(|| op.to_string())
+}
+
+/// An operator of a composite filter.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.CompositeFilter.Operator>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CompositeOperator {
+    /// `AND`
+    And,
+    /// `OR`
+    Or,
+}
+
+impl std::convert::From<CompositeOperator> for composite_filter::Operator {
+    fn from(op: CompositeOperator) -> Self {
+        match op {
+            CompositeOperator::And => composite_filter::Operator::And,
+            CompositeOperator::Or => composite_filter::Operator::Or,
+        }
+    }
+}
+
+/// An operator of a field filter.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FieldFilter.Operator>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FieldOperator {
+    /// `LESS_THAN`
+    LessThan,
+    /// `LESS_THAN_OR_EQUAL`
+    LessThanOrEqual,
+    /// `GREATER_THAN`
+    GreaterThan,
+    /// `GREATER_THAN_OR_EQUAL`
+    GreaterThanOrEqual,
+    /// `EQUAL`
+    Equal,
+    /// `NOT_EQUAL`
+    NotEqual,
+    /// `ARRAY_CONTAINS`
+    ArrayContains,
+    /// `IN`
+    In,
+    /// `ARRAY_CONTAINS_ANY`
+    ArrayContainsAny,
+    /// `NOT_IN`
+    NotIn,
+}
+
+impl std::convert::From<FieldOperator> for field_filter::Operator {
+    fn from(op: FieldOperator) -> Self {
+        match op {
+            FieldOperator::LessThan => field_filter::Operator::LessThan,
+            FieldOperator::LessThanOrEqual => field_filter::Operator::LessThanOrEqual,
+            FieldOperator::GreaterThan => field_filter::Operator::GreaterThan,
+            FieldOperator::GreaterThanOrEqual => field_filter::Operator::GreaterThanOrEqual,
+            FieldOperator::Equal => field_filter::Operator::Equal,
+            FieldOperator::NotEqual => field_filter::Operator::NotEqual,
+            FieldOperator::ArrayContains => field_filter::Operator::ArrayContains,
+            FieldOperator::In => field_filter::Operator::In,
+            FieldOperator::ArrayContainsAny => field_filter::Operator::ArrayContainsAny,
+            FieldOperator::NotIn => field_filter::Operator::NotIn,
+        }
+    }
+}
+
+/// An operator of a unary filter.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.UnaryFilter.Operator>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UnaryOperator {
+    /// `IS_NAN`
+    IsNan,
+    /// `IS_NULL`
+    IsNull,
+    /// `IS_NOT_NAN`
+    IsNotNan,
+    /// `IS_NOT_NULL`
+    IsNotNull,
+}
+
+impl std::convert::From<UnaryOperator> for unary_filter::Operator {
+    fn from(op: UnaryOperator) -> Self {
+        match op {
+            UnaryOperator::IsNan => unary_filter::Operator::IsNan,
+            UnaryOperator::IsNull => unary_filter::Operator::IsNull,
+            UnaryOperator::IsNotNan => unary_filter::Operator::IsNotNan,
+            UnaryOperator::IsNotNull => unary_filter::Operator::IsNotNull,
+        }
+    }
+}
+
+/// The typed contents of a [`Filter`].
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_filter_kind() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{
+///     CompositeOperator, FieldOperator, FieldPath, Filter, FilterKind, UnaryOperator,
+/// };
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+/// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+/// let filter1 = FieldPath::raw("field1").equal(one.clone())?;
+/// let filter2 = FieldPath::raw("field2").is_null()?;
+/// assert_eq!(
+///     Filter::or([filter1.clone(), filter2.clone()]).kind(),
+///     FilterKind::Composite {
+///         op: CompositeOperator::Or,
+///         filters: vec![filter1.clone(), filter2.clone()],
+///     }
+/// );
+/// assert_eq!(
+///     filter1.kind(),
+///     FilterKind::Field {
+///         field_path: FieldPath::raw("field1"),
+///         op: FieldOperator::Equal,
+///         value: one,
+///     }
+/// );
+/// assert_eq!(
+///     filter2.kind(),
+///     FilterKind::Unary {
+///         field_path: FieldPath::raw("field2"),
+///         op: UnaryOperator::IsNull,
+///     }
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterKind {
+    /// A `CompositeFilter`.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#compositefilter>
+    Composite {
+        /// The operator for combining the filters.
+        op: CompositeOperator,
+        /// The filters to combine.
+        filters: Vec<Filter>,
+    },
+    /// A `FieldFilter`.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#fieldfilter>
+    Field {
+        /// The field to filter by.
+        field_path: FieldPath,
+        /// The operator to filter by.
+        op: FieldOperator,
+        /// The value to compare to.
+        value: Value,
+    },
+    /// A `UnaryFilter`.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#unaryfilter>
+    Unary {
+        /// The field to which to apply the operator.
+        field_path: FieldPath,
+        /// The unary operator to apply.
+        op: UnaryOperator,
+    },
+}
+
+impl Filter {
+    /// Returns the field paths referenced by the filter (and its nested filters) in order of appearance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_filter_field_paths() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+    /// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+    /// let filter1 = Filter::and([
+    ///     FieldPath::raw("field1").equal(one.clone())?,
+    ///     Filter::or([
+    ///         FieldPath::raw("field2").is_null()?,
+    ///         FieldPath::raw("field1").less_than(one)?,
+    ///     ]),
+    /// ]);
+    /// assert_eq!(
+    ///     filter1.field_paths(),
+    ///     vec![
+    ///         FieldPath::raw("field1"),
+    ///         FieldPath::raw("field2"),
+    ///         FieldPath::raw("field1"),
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn field_paths(&self) -> Vec<FieldPath> {
+        match self.kind() {
+            FilterKind::Composite { filters, .. } => {
+                filters.iter().flat_map(Filter::field_paths).collect()
+            }
+            FilterKind::Field { field_path, .. } | FilterKind::Unary { field_path, .. } => {
+                vec![field_path]
+            }
+        }
+    }
+
+    /// Returns the typed contents of the filter.
+    ///
+    /// See [`FilterKind`] for examples.
+    pub fn kind(&self) -> FilterKind {
+        // The filter type, the fields, the values and the operators are validated when the filter is created.
+        fn invalid<T>(filter: &structured_query::Filter) -> T {
+            unreachable!("invalid filter: {:?}", filter)
+        }
+        let field_path = |field_reference: Option<&structured_query::FieldReference>| {
+            field_reference
+                .map(|field_reference| FieldPath::raw(field_reference.field_path.clone()))
+                .unwrap_or_else(|| invalid(&self.0))
+        };
+        match &self.0.filter_type {
+            Some(structured_query::filter::FilterType::CompositeFilter(composite_filter)) => {
+                FilterKind::Composite {
+                    op: match composite_filter::Operator::try_from(composite_filter.op) {
+                        Ok(composite_filter::Operator::And) => CompositeOperator::And,
+                        Ok(composite_filter::Operator::Or) => CompositeOperator::Or,
+                        Ok(composite_filter::Operator::Unspecified) | Err(_) => invalid(&self.0),
+                    },
+                    filters: composite_filter.filters.iter().cloned().map(Self).collect(),
+                }
+            }
+            Some(structured_query::filter::FilterType::FieldFilter(field_filter)) => {
+                FilterKind::Field {
+                    field_path: field_path(field_filter.field.as_ref()),
+                    op: match field_filter::Operator::try_from(field_filter.op) {
+                        Ok(field_filter::Operator::LessThan) => FieldOperator::LessThan,
+                        Ok(field_filter::Operator::LessThanOrEqual) => {
+                            FieldOperator::LessThanOrEqual
+                        }
+                        Ok(field_filter::Operator::GreaterThan) => FieldOperator::GreaterThan,
+                        Ok(field_filter::Operator::GreaterThanOrEqual) => {
+                            FieldOperator::GreaterThanOrEqual
+                        }
+                        Ok(field_filter::Operator::Equal) => FieldOperator::Equal,
+                        Ok(field_filter::Operator::NotEqual) => FieldOperator::NotEqual,
+                        Ok(field_filter::Operator::ArrayContains) => FieldOperator::ArrayContains,
+                        Ok(field_filter::Operator::In) => FieldOperator::In,
+                        Ok(field_filter::Operator::ArrayContainsAny) => {
+                            FieldOperator::ArrayContainsAny
+                        }
+                        Ok(field_filter::Operator::NotIn) => FieldOperator::NotIn,
+                        Ok(field_filter::Operator::Unspecified) | Err(_) => invalid(&self.0),
+                    },
+                    value: field_filter
+                        .value
+                        .clone()
+                        .unwrap_or_else(|| invalid(&self.0)),
+                }
+            }
+            Some(structured_query::filter::FilterType::UnaryFilter(unary_filter)) => {
+                FilterKind::Unary {
+                    field_path: field_path(
+                        unary_filter.operand_type.as_ref().map(
+                            |unary_filter::OperandType::Field(field_reference)| field_reference,
+                        ),
+                    ),
+                    op: match unary_filter::Operator::try_from(unary_filter.op) {
+                        Ok(unary_filter::Operator::IsNan) => UnaryOperator::IsNan,
+                        Ok(unary_filter::Operator::IsNull) => UnaryOperator::IsNull,
+                        Ok(unary_filter::Operator::IsNotNan) => UnaryOperator::IsNotNan,
+                        Ok(unary_filter::Operator::IsNotNull) => UnaryOperator::IsNotNull,
+                        Ok(unary_filter::Operator::Unspecified) | Err(_) => invalid(&self.0),
+                    },
+                }
+            }
+            None => invalid(&self.0),
+        }
+    }
+}
+
 impl Filter {
+    /// Creates a field filter.
+    ///
+    /// `== null`, `== NaN`, `!= null` and `!= NaN` are converted into the unary filters (as the official SDKs do).
+    /// Returns an error if the value cannot be converted or is invalid for the operator
+    /// (e.g. `in` with a non-array value).
     pub(crate) fn field<T>(
         field_path: FieldPath,
         op: field_filter::Operator,
@@ -172,12 +951,39 @@ impl Filter {
     where
         T: IntoValue,
     {
+        let value = value.into_value().map_err(|e| {
+            e.with_field_path(field_path.clone())
+                .with_operator(op.as_str_name())
+        })?;
+        // `== null`, `== NaN`, `!= null` and `!= NaN` are unary filters
+        let unary_op = match op {
+            field_filter::Operator::Equal if is_null(&value) => {
+                Some(unary_filter::Operator::IsNull)
+            }
+            field_filter::Operator::Equal if is_nan(&value) => Some(unary_filter::Operator::IsNan),
+            field_filter::Operator::NotEqual if is_null(&value) => {
+                Some(unary_filter::Operator::IsNotNull)
+            }
+            field_filter::Operator::NotEqual if is_nan(&value) => {
+                Some(unary_filter::Operator::IsNotNan)
+            }
+            _ => None,
+        };
+        if let Some(unary_op) = unary_op {
+            return Filter::unary(field_path, unary_op);
+        }
+        crate::validation::check_field_filter_value(op, &value).map_err(|message| {
+            crate::Error::from_violations(vec![crate::Violation::new("", message)])
+                .with_field_path(field_path.clone())
+                .with_operator(op.as_str_name())
+                .at_root()
+        })?;
         Ok(Filter(structured_query::Filter {
             filter_type: Some(structured_query::filter::FilterType::FieldFilter(
                 structured_query::FieldFilter {
                     field: Some(structured_query::FieldReference::from(field_path)),
                     op: op as i32,
-                    value: Some(value.into_value()?),
+                    value: Some(value),
                 },
             )),
         }))
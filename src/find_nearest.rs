@@ -0,0 +1,118 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query::{
+    self, find_nearest,
+};
+
+use crate::{FieldPath, IntoValue, Result, Vector};
+
+/// A distance measure used by a Firestore vector search.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FindNearest.DistanceMeasure>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DistanceMeasure {
+    /// `EUCLIDEAN`
+    Euclidean,
+    /// `COSINE`
+    Cosine,
+    /// `DOT_PRODUCT`
+    DotProduct,
+}
+
+impl std::convert::From<DistanceMeasure> for find_nearest::DistanceMeasure {
+    fn from(distance_measure: DistanceMeasure) -> Self {
+        match distance_measure {
+            DistanceMeasure::Euclidean => find_nearest::DistanceMeasure::Euclidean,
+            DistanceMeasure::Cosine => find_nearest::DistanceMeasure::Cosine,
+            DistanceMeasure::DotProduct => find_nearest::DistanceMeasure::DotProduct,
+        }
+    }
+}
+
+/// A Firestore vector search.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FindNearest>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_find_nearest() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{DistanceMeasure, FieldPath, FindNearest, IntoValue, Vector};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query;
+/// let find_nearest: FindNearest = FieldPath::raw("embedding")
+///     .find_nearest(Vector::new([1.0, 2.0]), DistanceMeasure::Cosine, 10)?
+///     .distance_result_field(FieldPath::raw("distance"))
+///     .distance_threshold(0.5);
+/// assert_eq!(
+///     structured_query::FindNearest::from(find_nearest),
+///     structured_query::FindNearest {
+///         vector_field: Some(structured_query::FieldReference {
+///             field_path: "embedding".to_string(),
+///         }),
+///         query_vector: Some(Vector::new([1.0, 2.0]).into_value()?),
+///         distance_measure: structured_query::find_nearest::DistanceMeasure::Cosine as i32,
+///         limit: Some(10),
+///         distance_result_field: "distance".to_string(),
+///         distance_threshold: Some(0.5),
+///     }
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FindNearest(structured_query::FindNearest);
+
+impl FindNearest {
+    /// The maximum number of documents returned by a vector search.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FindNearest.FIELDS.google.protobuf.Int32Value.google.firestore.v1.StructuredQuery.FindNearest.limit>
+    pub const MAX_LIMIT: i32 = 1000;
+
+    pub(crate) fn new(
+        vector_field: FieldPath,
+        query_vector: Vector,
+        distance_measure: DistanceMeasure,
+        limit: i32,
+    ) -> Result<Self> {
+        query_vector.validate()?;
+        if !(1..=Self::MAX_LIMIT).contains(&limit) {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "find_nearest.limit",
+                format!(
+                    "find_nearest limit must be between 1 and {} (actual: {})",
+                    Self::MAX_LIMIT,
+                    limit
+                ),
+            )]));
+        }
+        Ok(Self(structured_query::FindNearest {
+            vector_field: Some(structured_query::FieldReference::from(vector_field)),
+            query_vector: Some(query_vector.into_value()?),
+            distance_measure: find_nearest::DistanceMeasure::from(distance_measure) as i32,
+            limit: Some(limit),
+            distance_result_field: String::new(),
+            distance_threshold: None,
+        }))
+    }
+
+    /// Sets the specified field path to distance_result_field and returns the FindNearest.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FindNearest.FIELDS.string.google.firestore.v1.StructuredQuery.FindNearest.distance_result_field>
+    pub fn distance_result_field(mut self, field_path: FieldPath) -> Self {
+        self.0.distance_result_field =
+            structured_query::FieldReference::from(field_path).field_path;
+        self
+    }
+
+    /// Sets the specified value to distance_threshold and returns the FindNearest.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FindNearest.FIELDS.google.protobuf.DoubleValue.google.firestore.v1.StructuredQuery.FindNearest.distance_threshold>
+    pub fn distance_threshold(mut self, distance_threshold: f64) -> Self {
+        self.0.distance_threshold = Some(distance_threshold);
+        self
+    }
+}
+
+impl std::convert::From<FindNearest> for structured_query::FindNearest {
+    fn from(find_nearest: FindNearest) -> Self {
+        find_nearest.0
+    }
+}
@@ -0,0 +1,1129 @@
+//! A GQL-like text format for queries.
+
+use std::fmt::{self, Formatter};
+
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ArrayValue, Cursor, MapValue, StructuredQuery, Value, structured_query, value::ValueType,
+};
+use googleapis_tonic_google_firestore_v1::google::r#type::LatLng;
+
+use crate::encoding::{decode_base64, encode_base64, format_timestamp, parse_timestamp};
+use crate::field_path::is_simple_field_name;
+use crate::ordered_value::vector_values;
+use crate::{FieldPath, IntoValue, Result, Vector};
+
+const KEYWORDS: &[&str] = &[
+    "AFTER",
+    "AND",
+    "ARRAY_CONTAINS",
+    "ARRAY_CONTAINS_ANY",
+    "ASC",
+    "AT",
+    "BEFORE",
+    "BY",
+    "BYTES",
+    "COLLECTION",
+    "COSINE",
+    "DESC",
+    "DISTANCE",
+    "DOT_PRODUCT",
+    "END",
+    "EUCLIDEAN",
+    "FALSE",
+    "FIELD",
+    "FIND",
+    "FROM",
+    "GEOPOINT",
+    "GROUP",
+    "IN",
+    "INFINITY",
+    "IS",
+    "LAST",
+    "LIMIT",
+    "NAN",
+    "NEAREST",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "OR",
+    "ORDER",
+    "REF",
+    "RESULT",
+    "SELECT",
+    "START",
+    "THRESHOLD",
+    "TIMESTAMP",
+    "TO",
+    "TRUE",
+    "USING",
+    "VECTOR",
+    "WHERE",
+];
+
+fn is_keyword(s: &str) -> bool {
+    KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(s))
+}
+
+pub(crate) fn write_query(
+    f: &mut Formatter<'_>,
+    structured_query: &StructuredQuery,
+    parent: Option<&str>,
+    limit_to_last: bool,
+) -> fmt::Result {
+    f.write_str("SELECT")?;
+    match &structured_query.select {
+        None => f.write_str(" *")?,
+        Some(projection) => {
+            for (index, field_reference) in projection.fields.iter().enumerate() {
+                f.write_str(if index == 0 { " " } else { ", " })?;
+                write_field_path(f, &field_reference.field_path)?;
+            }
+        }
+    }
+
+    f.write_str(" FROM")?;
+    for (index, collection_selector) in structured_query.from.iter().enumerate() {
+        f.write_str(if index == 0 { " " } else { ", " })?;
+        if collection_selector.all_descendants {
+            f.write_str("COLLECTION GROUP ")?;
+        }
+        match parent {
+            // the collection path is always quoted because it contains '/'
+            Some(parent) => write_collection_id(
+                f,
+                &format!("{}/{}", parent, collection_selector.collection_id),
+            )?,
+            None => write_collection_id(f, &collection_selector.collection_id)?,
+        }
+    }
+
+    if let Some(filter) = &structured_query.r#where {
+        f.write_str(" WHERE ")?;
+        write_filter(f, filter, false)?;
+    }
+
+    if let Some(find_nearest) = &structured_query.find_nearest {
+        f.write_str(" FIND NEAREST ")?;
+        write_field_path(
+            f,
+            find_nearest
+                .vector_field
+                .as_ref()
+                .map(|field_reference| field_reference.field_path.as_str())
+                .unwrap_or_default(),
+        )?;
+        f.write_str(" ")?;
+        match &find_nearest.query_vector {
+            Some(value) => write_value(f, value)?,
+            None => f.write_str("NULL")?,
+        }
+        use structured_query::find_nearest::DistanceMeasure;
+        match DistanceMeasure::try_from(find_nearest.distance_measure) {
+            Ok(DistanceMeasure::Euclidean) => f.write_str(" USING EUCLIDEAN")?,
+            Ok(DistanceMeasure::Cosine) => f.write_str(" USING COSINE")?,
+            Ok(DistanceMeasure::DotProduct) => f.write_str(" USING DOT_PRODUCT")?,
+            Ok(DistanceMeasure::Unspecified) | Err(_) => {}
+        }
+        if let Some(limit) = find_nearest.limit {
+            write!(f, " LIMIT {}", limit)?;
+        }
+        if !find_nearest.distance_result_field.is_empty() {
+            f.write_str(" DISTANCE RESULT FIELD ")?;
+            write_field_path(f, &find_nearest.distance_result_field)?;
+        }
+        if let Some(distance_threshold) = find_nearest.distance_threshold {
+            f.write_str(" DISTANCE THRESHOLD ")?;
+            write_double(f, distance_threshold)?;
+        }
+    }
+
+    for (index, order) in structured_query.order_by.iter().enumerate() {
+        f.write_str(if index == 0 { " ORDER BY " } else { ", " })?;
+        write_field_path(
+            f,
+            order
+                .field
+                .as_ref()
+                .map(|field_reference| field_reference.field_path.as_str())
+                .unwrap_or_default(),
+        )?;
+        match structured_query::Direction::try_from(order.direction) {
+            Ok(structured_query::Direction::Ascending) => f.write_str(" ASC")?,
+            Ok(structured_query::Direction::Descending) => f.write_str(" DESC")?,
+            Ok(structured_query::Direction::Unspecified) | Err(_) => {}
+        }
+    }
+
+    if let Some(cursor) = &structured_query.start_at {
+        f.write_str(if cursor.before {
+            " START AT "
+        } else {
+            " START AFTER "
+        })?;
+        write_cursor(f, cursor)?;
+    }
+    if let Some(cursor) = &structured_query.end_at {
+        f.write_str(if cursor.before {
+            " END BEFORE "
+        } else {
+            " END AT "
+        })?;
+        write_cursor(f, cursor)?;
+    }
+    if let Some(limit) = structured_query.limit {
+        if limit_to_last {
+            write!(f, " LIMIT TO LAST {}", limit)?;
+        } else {
+            write!(f, " LIMIT {}", limit)?;
+        }
+    }
+    if structured_query.offset != 0 {
+        write!(f, " OFFSET {}", structured_query.offset)?;
+    }
+    Ok(())
+}
+
+fn write_collection_id(f: &mut Formatter<'_>, collection_id: &str) -> fmt::Result {
+    if !collection_id.is_empty()
+        && collection_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !is_keyword(collection_id)
+    {
+        f.write_str(collection_id)
+    } else {
+        write_quoted(f, collection_id, '`')
+    }
+}
+
+fn write_cursor(f: &mut Formatter<'_>, cursor: &Cursor) -> fmt::Result {
+    f.write_str("(")?;
+    for (index, value) in cursor.values.iter().enumerate() {
+        if index != 0 {
+            f.write_str(", ")?;
+        }
+        write_value(f, value)?;
+    }
+    f.write_str(")")
+}
+
+fn write_double(f: &mut Formatter<'_>, d: f64) -> fmt::Result {
+    if d.is_nan() {
+        f.write_str("NaN")
+    } else if d.is_infinite() {
+        f.write_str(if d > 0.0 { "Infinity" } else { "-Infinity" })
+    } else {
+        // `{:?}` always contains `.` or `e` (e.g. `1.0`, `1e100`)
+        write!(f, "{:?}", d)
+    }
+}
+
+fn write_field_path(f: &mut Formatter<'_>, field_path: &str) -> fmt::Result {
+    match FieldPath::raw(field_path).to_segments() {
+        Ok(segments) => {
+            for (index, segment) in segments.iter().enumerate() {
+                if index != 0 {
+                    f.write_str(".")?;
+                }
+                if is_simple_field_name(segment) && !segment.is_empty() && !is_keyword(segment) {
+                    f.write_str(segment)?;
+                } else {
+                    write_quoted(f, segment, '`')?;
+                }
+            }
+            Ok(())
+        }
+        Err(_) => f.write_str(field_path),
+    }
+}
+
+fn write_filter(
+    f: &mut Formatter<'_>,
+    filter: &structured_query::Filter,
+    nested: bool,
+) -> fmt::Result {
+    use structured_query::filter::FilterType;
+    use structured_query::{composite_filter, field_filter, unary_filter};
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            let op = match composite_filter::Operator::try_from(composite_filter.op) {
+                Ok(composite_filter::Operator::And) => "AND",
+                Ok(composite_filter::Operator::Or) => "OR",
+                Ok(composite_filter::Operator::Unspecified) | Err(_) => "UNSPECIFIED",
+            };
+            if composite_filter.filters.len() < 2 {
+                write!(f, "{}(", op)?;
+                for (index, filter) in composite_filter.filters.iter().enumerate() {
+                    if index != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_filter(f, filter, false)?;
+                }
+                f.write_str(")")
+            } else {
+                if nested {
+                    f.write_str("(")?;
+                }
+                for (index, filter) in composite_filter.filters.iter().enumerate() {
+                    if index != 0 {
+                        write!(f, " {} ", op)?;
+                    }
+                    write_filter(f, filter, true)?;
+                }
+                if nested {
+                    f.write_str(")")?;
+                }
+                Ok(())
+            }
+        }
+        Some(FilterType::FieldFilter(field_filter)) => {
+            write_field_path(
+                f,
+                field_filter
+                    .field
+                    .as_ref()
+                    .map(|field_reference| field_reference.field_path.as_str())
+                    .unwrap_or_default(),
+            )?;
+            let op = match field_filter::Operator::try_from(field_filter.op) {
+                Ok(field_filter::Operator::LessThan) => "<",
+                Ok(field_filter::Operator::LessThanOrEqual) => "<=",
+                Ok(field_filter::Operator::GreaterThan) => ">",
+                Ok(field_filter::Operator::GreaterThanOrEqual) => ">=",
+                Ok(field_filter::Operator::Equal) => "=",
+                Ok(field_filter::Operator::NotEqual) => "!=",
+                Ok(field_filter::Operator::ArrayContains) => "ARRAY_CONTAINS",
+                Ok(field_filter::Operator::In) => "IN",
+                Ok(field_filter::Operator::ArrayContainsAny) => "ARRAY_CONTAINS_ANY",
+                Ok(field_filter::Operator::NotIn) => "NOT IN",
+                Ok(field_filter::Operator::Unspecified) | Err(_) => "UNSPECIFIED",
+            };
+            write!(f, " {} ", op)?;
+            match &field_filter.value {
+                Some(value) => write_value(f, value),
+                None => f.write_str("NULL"),
+            }
+        }
+        Some(FilterType::UnaryFilter(unary_filter)) => {
+            match &unary_filter.operand_type {
+                Some(unary_filter::OperandType::Field(field_reference)) => {
+                    write_field_path(f, &field_reference.field_path)?
+                }
+                None => write_field_path(f, "")?,
+            }
+            f.write_str(match unary_filter::Operator::try_from(unary_filter.op) {
+                Ok(unary_filter::Operator::IsNan) => " IS NAN",
+                Ok(unary_filter::Operator::IsNull) => " IS NULL",
+                Ok(unary_filter::Operator::IsNotNan) => " IS NOT NAN",
+                Ok(unary_filter::Operator::IsNotNull) => " IS NOT NULL",
+                Ok(unary_filter::Operator::Unspecified) | Err(_) => " IS UNSPECIFIED",
+            })
+        }
+        None => f.write_str("UNSPECIFIED"),
+    }
+}
+
+fn write_quoted(f: &mut Formatter<'_>, s: &str, quote: char) -> fmt::Result {
+    use fmt::Write as _;
+    f.write_char(quote)?;
+    for c in s.chars() {
+        match c {
+            '\\' => f.write_str(r"\\")?,
+            c if c == quote => {
+                f.write_char('\\')?;
+                f.write_char(c)?;
+            }
+            // backtick-quoted names only support `\\` and `` \` ``
+            c if quote == '`' => f.write_char(c)?,
+            '\n' => f.write_str(r"\n")?,
+            '\r' => f.write_str(r"\r")?,
+            '\t' => f.write_str(r"\t")?,
+            c if c.is_control() => write!(f, r"\u{{{:x}}}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char(quote)
+}
+
+pub(crate) fn write_value(f: &mut Formatter<'_>, value: &Value) -> fmt::Result {
+    match &value.value_type {
+        None | Some(ValueType::NullValue(_)) => f.write_str("NULL"),
+        Some(ValueType::BooleanValue(b)) => f.write_str(if *b { "TRUE" } else { "FALSE" }),
+        Some(ValueType::IntegerValue(i)) => write!(f, "{}", i),
+        Some(ValueType::DoubleValue(d)) => write_double(f, *d),
+        Some(ValueType::TimestampValue(timestamp)) => {
+            write!(f, "TIMESTAMP(\"{}\")", format_timestamp(timestamp))
+        }
+        Some(ValueType::StringValue(s)) => write_quoted(f, s, '"'),
+        Some(ValueType::BytesValue(b)) => write!(f, "BYTES(\"{}\")", encode_base64(&b[..])),
+        Some(ValueType::ReferenceValue(r)) => {
+            f.write_str("REF(")?;
+            write_quoted(f, r, '"')?;
+            f.write_str(")")
+        }
+        Some(ValueType::GeoPointValue(lat_lng)) => {
+            f.write_str("GEOPOINT(")?;
+            write_double(f, lat_lng.latitude)?;
+            f.write_str(", ")?;
+            write_double(f, lat_lng.longitude)?;
+            f.write_str(")")
+        }
+        Some(ValueType::ArrayValue(array_value)) => {
+            f.write_str("[")?;
+            for (index, value) in array_value.values.iter().enumerate() {
+                if index != 0 {
+                    f.write_str(", ")?;
+                }
+                write_value(f, value)?;
+            }
+            f.write_str("]")
+        }
+        Some(ValueType::MapValue(map_value)) => {
+            if let Some(values) = vector_values(map_value)
+                && map_value.fields.len() == 2
+                && values
+                    .iter()
+                    .all(|value| matches!(value.value_type, Some(ValueType::DoubleValue(_))))
+            {
+                f.write_str("VECTOR([")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_value(f, value)?;
+                }
+                return f.write_str("])");
+            }
+            let mut fields = map_value.fields.iter().collect::<Vec<(&String, &Value)>>();
+            fields.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            f.write_str("{")?;
+            for (index, (key, value)) in fields.into_iter().enumerate() {
+                if index != 0 {
+                    f.write_str(", ")?;
+                }
+                write_quoted(f, key, '"')?;
+                f.write_str(": ")?;
+                write_value(f, value)?;
+            }
+            f.write_str("}")
+        }
+        Some(ValueType::FieldReferenceValue(_))
+        | Some(ValueType::VariableReferenceValue(_))
+        | Some(ValueType::FunctionValue(_))
+        | Some(ValueType::PipelineValue(_)) => f.write_str("UNSUPPORTED"),
+    }
+}
+
+pub(crate) fn parse_query(s: &str) -> Result<(StructuredQuery, bool)> {
+    let mut parser = Parser {
+        input: s,
+        position: 0,
+    };
+    let parsed = parser.query()?;
+    parser.skip_whitespace();
+    if parser.position != s.len() {
+        return Err(parser.error("end of input"));
+    }
+    Ok(parsed)
+}
+
+enum Number {
+    Integer(i64),
+    Double(f64),
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn error(&self, expected: &str) -> crate::Error {
+        let rest = self.rest();
+        let found = if rest.is_empty() {
+            "end of input".to_string()
+        } else {
+            format!("{:?}", rest.chars().take(16).collect::<String>())
+        };
+        crate::Error::new(format!(
+            "expected {} at position {}, found {}",
+            expected, self.position, found
+        ))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        &rest[..len]
+    }
+
+    fn peek_keyword(&mut self, keyword: &str) -> bool {
+        self.word().eq_ignore_ascii_case(keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let word = self.word();
+        if word.eq_ignore_ascii_case(keyword) {
+            self.position += word.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(keyword))
+        }
+    }
+
+    fn eat_keywords(&mut self, keywords: &[&str]) -> bool {
+        let position = self.position;
+        if keywords.iter().all(|keyword| self.eat_keyword(keyword)) {
+            true
+        } else {
+            self.position = position;
+            false
+        }
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(symbol) {
+            self.position += symbol.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}'", symbol)))
+        }
+    }
+
+    fn separated<T, F>(&mut self, close: &str, mut item: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> Result<T>,
+    {
+        let mut items = vec![];
+        if self.eat_symbol(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat_symbol(close) {
+                return Ok(items);
+            }
+            self.expect_symbol(",")?;
+        }
+    }
+
+    fn query(&mut self) -> Result<(StructuredQuery, bool)> {
+        self.expect_keyword("SELECT")?;
+        let select = if self.eat_symbol("*") {
+            None
+        } else if self.peek_keyword("FROM") {
+            Some(structured_query::Projection { fields: vec![] })
+        } else {
+            let mut fields = vec![self.field_reference()?];
+            while self.eat_symbol(",") {
+                fields.push(self.field_reference()?);
+            }
+            Some(structured_query::Projection { fields })
+        };
+
+        self.expect_keyword("FROM")?;
+        let mut from = vec![];
+        loop {
+            let all_descendants = self.eat_keywords(&["COLLECTION", "GROUP"]);
+            from.push(structured_query::CollectionSelector {
+                collection_id: self.collection_id()?,
+                all_descendants,
+            });
+            if !self.eat_symbol(",") {
+                break;
+            }
+        }
+
+        let r#where = if self.eat_keyword("WHERE") {
+            Some(self.filter()?)
+        } else {
+            None
+        };
+
+        let find_nearest = if self.eat_keywords(&["FIND", "NEAREST"]) {
+            Some(self.find_nearest()?)
+        } else {
+            None
+        };
+
+        let mut order_by = vec![];
+        if self.eat_keywords(&["ORDER", "BY"]) {
+            loop {
+                let field = self.field_reference()?;
+                // a missing direction is kept unspecified (Firestore orders it ascending)
+                let direction = if self.eat_keyword("DESC") {
+                    structured_query::Direction::Descending
+                } else if self.eat_keyword("ASC") {
+                    structured_query::Direction::Ascending
+                } else {
+                    structured_query::Direction::Unspecified
+                };
+                order_by.push(structured_query::Order {
+                    field: Some(field),
+                    direction: direction as i32,
+                });
+                if !self.eat_symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        let mut structured_query = StructuredQuery {
+            select,
+            from,
+            r#where,
+            order_by,
+            start_at: None,
+            end_at: None,
+            offset: 0_i32,
+            limit: None,
+            find_nearest,
+        };
+        let mut limit_to_last = false;
+        let mut offset = None;
+        loop {
+            self.skip_whitespace();
+            let position = self.position;
+            let clause = self.word().to_ascii_uppercase();
+            let duplicate = match clause.as_str() {
+                "START" => {
+                    self.expect_keyword("START")?;
+                    let before = if self.eat_keyword("AT") {
+                        true
+                    } else {
+                        self.expect_keyword("AFTER")?;
+                        false
+                    };
+                    let cursor = self.cursor(before)?;
+                    structured_query.start_at.replace(cursor).is_some()
+                }
+                "END" => {
+                    self.expect_keyword("END")?;
+                    let before = if self.eat_keyword("BEFORE") {
+                        true
+                    } else {
+                        self.expect_keyword("AT")?;
+                        false
+                    };
+                    let cursor = self.cursor(before)?;
+                    structured_query.end_at.replace(cursor).is_some()
+                }
+                "LIMIT" => {
+                    self.expect_keyword("LIMIT")?;
+                    limit_to_last = self.eat_keywords(&["TO", "LAST"]);
+                    let limit = self.int32()?;
+                    structured_query.limit.replace(limit).is_some()
+                }
+                "OFFSET" => {
+                    self.expect_keyword("OFFSET")?;
+                    let value = self.int32()?;
+                    structured_query.offset = value;
+                    offset.replace(value).is_some()
+                }
+                _ => break,
+            };
+            if duplicate {
+                self.position = position;
+                return Err(crate::Error::new(format!(
+                    "duplicate {} clause at position {}",
+                    clause, position
+                )));
+            }
+        }
+        Ok((structured_query, limit_to_last))
+    }
+
+    fn collection_id(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        if self.rest().starts_with('`') {
+            return self.quoted('`');
+        }
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("collection ID"));
+        }
+        self.position += len;
+        Ok(rest[..len].to_string())
+    }
+
+    fn cursor(&mut self, before: bool) -> Result<Cursor> {
+        self.expect_symbol("(")?;
+        let values = self.separated(")", Self::value)?;
+        Ok(Cursor { values, before })
+    }
+
+    fn field_reference(&mut self) -> Result<structured_query::FieldReference> {
+        self.skip_whitespace();
+        let mut segments = vec![];
+        loop {
+            if self.rest().starts_with('`') {
+                segments.push(self.quoted('`')?);
+            } else {
+                let rest = self.rest();
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                if len == 0 {
+                    return Err(self.error("field path"));
+                }
+                segments.push(rest[..len].to_string());
+                self.position += len;
+            }
+            // no whitespace is allowed in a field path
+            if !self.rest().starts_with('.') {
+                break;
+            }
+            self.position += 1;
+        }
+        Ok(structured_query::FieldReference::from(FieldPath::new(
+            segments,
+        )))
+    }
+
+    fn filter(&mut self) -> Result<structured_query::Filter> {
+        let mut filters = vec![self.conjunction()?];
+        while self.eat_keyword("OR") {
+            filters.push(self.conjunction()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            composite_filter(structured_query::composite_filter::Operator::Or, filters)
+        })
+    }
+
+    fn conjunction(&mut self) -> Result<structured_query::Filter> {
+        let mut filters = vec![self.primary_filter()?];
+        while self.eat_keyword("AND") {
+            filters.push(self.primary_filter()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            composite_filter(structured_query::composite_filter::Operator::And, filters)
+        })
+    }
+
+    fn primary_filter(&mut self) -> Result<structured_query::Filter> {
+        use structured_query::{composite_filter, field_filter, unary_filter};
+        if self.eat_symbol("(") {
+            let filter = self.filter()?;
+            self.expect_symbol(")")?;
+            return Ok(filter);
+        }
+        for (keyword, op) in [
+            ("AND", composite_filter::Operator::And),
+            ("OR", composite_filter::Operator::Or),
+        ] {
+            let position = self.position;
+            if self.eat_keyword(keyword) {
+                if self.eat_symbol("(") {
+                    let filters = self.separated(")", Self::filter)?;
+                    return Ok(self::composite_filter(op, filters));
+                }
+                self.position = position;
+            }
+        }
+
+        let field = self.field_reference()?;
+        if self.eat_keyword("IS") {
+            let not = self.eat_keyword("NOT");
+            let op = if self.eat_keyword("NULL") {
+                if not {
+                    unary_filter::Operator::IsNotNull
+                } else {
+                    unary_filter::Operator::IsNull
+                }
+            } else if self.eat_keyword("NAN") {
+                if not {
+                    unary_filter::Operator::IsNotNan
+                } else {
+                    unary_filter::Operator::IsNan
+                }
+            } else {
+                return Err(self.error("NULL or NAN"));
+            };
+            return Ok(structured_query::Filter {
+                filter_type: Some(structured_query::filter::FilterType::UnaryFilter(
+                    structured_query::UnaryFilter {
+                        op: op as i32,
+                        operand_type: Some(unary_filter::OperandType::Field(field)),
+                    },
+                )),
+            });
+        }
+
+        let op = if self.eat_keywords(&["NOT", "IN"]) {
+            field_filter::Operator::NotIn
+        } else if self.eat_keyword("IN") {
+            field_filter::Operator::In
+        } else if self.eat_keyword("ARRAY_CONTAINS") {
+            field_filter::Operator::ArrayContains
+        } else if self.eat_keyword("ARRAY_CONTAINS_ANY") {
+            field_filter::Operator::ArrayContainsAny
+        } else if self.eat_symbol("<=") {
+            field_filter::Operator::LessThanOrEqual
+        } else if self.eat_symbol("<") {
+            field_filter::Operator::LessThan
+        } else if self.eat_symbol(">=") {
+            field_filter::Operator::GreaterThanOrEqual
+        } else if self.eat_symbol(">") {
+            field_filter::Operator::GreaterThan
+        } else if self.eat_symbol("!=") {
+            field_filter::Operator::NotEqual
+        } else if self.eat_symbol("==") || self.eat_symbol("=") {
+            field_filter::Operator::Equal
+        } else {
+            return Err(self.error("operator"));
+        };
+        Ok(structured_query::Filter {
+            filter_type: Some(structured_query::filter::FilterType::FieldFilter(
+                structured_query::FieldFilter {
+                    field: Some(field),
+                    op: op as i32,
+                    value: Some(self.value()?),
+                },
+            )),
+        })
+    }
+
+    fn find_nearest(&mut self) -> Result<structured_query::FindNearest> {
+        use structured_query::find_nearest::DistanceMeasure;
+        let vector_field = self.field_reference()?;
+        let query_vector = self.value()?;
+        let distance_measure = if self.eat_keyword("USING") {
+            if self.eat_keyword("EUCLIDEAN") {
+                DistanceMeasure::Euclidean
+            } else if self.eat_keyword("COSINE") {
+                DistanceMeasure::Cosine
+            } else if self.eat_keyword("DOT_PRODUCT") {
+                DistanceMeasure::DotProduct
+            } else {
+                return Err(self.error("EUCLIDEAN, COSINE or DOT_PRODUCT"));
+            }
+        } else {
+            DistanceMeasure::Unspecified
+        };
+        let limit = if self.eat_keyword("LIMIT") {
+            Some(self.int32()?)
+        } else {
+            None
+        };
+        let distance_result_field = if self.eat_keywords(&["DISTANCE", "RESULT", "FIELD"]) {
+            self.field_reference()?.field_path
+        } else {
+            String::new()
+        };
+        let distance_threshold = if self.eat_keywords(&["DISTANCE", "THRESHOLD"]) {
+            Some(self.double()?)
+        } else {
+            None
+        };
+        Ok(structured_query::FindNearest {
+            vector_field: Some(vector_field),
+            query_vector: Some(query_vector),
+            distance_measure: distance_measure as i32,
+            limit,
+            distance_result_field,
+            distance_threshold,
+        })
+    }
+
+    fn int32(&mut self) -> Result<i32> {
+        self.skip_whitespace();
+        let position = self.position;
+        match self.number()? {
+            Number::Integer(i) => i32::try_from(i).map_err(|_| {
+                self.position = position;
+                self.error("32-bit integer")
+            }),
+            Number::Double(_) => {
+                self.position = position;
+                Err(self.error("integer"))
+            }
+        }
+    }
+
+    fn double(&mut self) -> Result<f64> {
+        Ok(match self.number()? {
+            Number::Integer(i) => i as f64,
+            Number::Double(d) => d,
+        })
+    }
+
+    fn number(&mut self) -> Result<Number> {
+        self.skip_whitespace();
+        let start = self.position;
+        let negative = self.rest().starts_with('-');
+        if negative {
+            self.position += 1;
+        }
+        let word = self.word();
+        if word.eq_ignore_ascii_case("INFINITY") {
+            self.position += word.len();
+            return Ok(Number::Double(if negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }));
+        }
+        if !negative && word.eq_ignore_ascii_case("NAN") {
+            self.position += word.len();
+            return Ok(Number::Double(f64::NAN));
+        }
+
+        let bytes = self.input.as_bytes();
+        let digits = |position: &mut usize| {
+            let start = *position;
+            while bytes.get(*position).is_some_and(u8::is_ascii_digit) {
+                *position += 1;
+            }
+            *position - start
+        };
+        let mut position = start + usize::from(negative);
+        if digits(&mut position) == 0 {
+            return Err(self.error("number"));
+        }
+        let mut is_double = false;
+        if bytes.get(position) == Some(&b'.') {
+            position += 1;
+            is_double = true;
+            if digits(&mut position) == 0 {
+                self.position = position;
+                return Err(self.error("digit"));
+            }
+        }
+        if matches!(bytes.get(position), Some(b'e' | b'E')) {
+            position += 1;
+            is_double = true;
+            if matches!(bytes.get(position), Some(b'+' | b'-')) {
+                position += 1;
+            }
+            if digits(&mut position) == 0 {
+                self.position = position;
+                return Err(self.error("digit"));
+            }
+        }
+        let literal = &self.input[start..position];
+        let number = if is_double {
+            literal.parse::<f64>().map(Number::Double).ok()
+        } else {
+            literal.parse::<i64>().map(Number::Integer).ok()
+        };
+        match number {
+            Some(number) => {
+                self.position = position;
+                Ok(number)
+            }
+            None => {
+                self.position = start;
+                Err(self.error("64-bit integer"))
+            }
+        }
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String> {
+        self.skip_whitespace();
+        let start = self.position;
+        if !self.rest().starts_with(quote) {
+            return Err(self.error(&format!("{}", quote)));
+        }
+        let mut s = String::new();
+        let mut chars = self.rest().char_indices().skip(1);
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    let escaped = match chars.next() {
+                        Some((_, '\\')) => '\\',
+                        Some((_, c)) if c == quote => c,
+                        Some((_, 'n')) if quote == '"' => '\n',
+                        Some((_, 'r')) if quote == '"' => '\r',
+                        Some((_, 't')) if quote == '"' => '\t',
+                        Some((escape_index, 'u')) if quote == '"' => {
+                            let rest = &self.rest()[escape_index + 1..];
+                            let code_point = rest
+                                .strip_prefix('{')
+                                .and_then(|rest| rest.split_once('}'))
+                                .and_then(|(hex, _)| {
+                                    u32::from_str_radix(hex, 16)
+                                        .ok()
+                                        .and_then(char::from_u32)
+                                        .map(|c| (hex.len(), c))
+                                });
+                            match code_point {
+                                Some((len, c)) => {
+                                    // skip `{`, hex digits and `}`
+                                    for _ in 0..len + 2 {
+                                        chars.next();
+                                    }
+                                    c
+                                }
+                                None => {
+                                    self.position = start + index;
+                                    return Err(self.error("unicode escape"));
+                                }
+                            }
+                        }
+                        _ => {
+                            self.position = start + index;
+                            return Err(self.error("escape sequence"));
+                        }
+                    };
+                    s.push(escaped);
+                }
+                c if c == quote => {
+                    self.position = start + index + c.len_utf8();
+                    return Ok(s);
+                }
+                c => s.push(c),
+            }
+        }
+        self.position = self.input.len();
+        Err(self.error(&format!("closing {}", quote)))
+    }
+
+    #[allow(clippy::useless_conversion)]
+    fn value(&mut self) -> Result<Value> {
+        let value_type = |value_type: ValueType| Value {
+            value_type: Some(value_type),
+        };
+        self.skip_whitespace();
+        if self.eat_symbol("[") {
+            let values = self.separated("]", Self::value)?;
+            return Ok(value_type(ValueType::ArrayValue(ArrayValue { values })));
+        }
+        if self.eat_symbol("{") {
+            let fields = self.separated("}", |parser| {
+                let key = parser.quoted('"')?;
+                parser.expect_symbol(":")?;
+                Ok((key, parser.value()?))
+            })?;
+            return Ok(value_type(ValueType::MapValue(MapValue {
+                fields: fields.into_iter().collect(),
+            })));
+        }
+        if self.rest().starts_with('"') {
+            return Ok(value_type(ValueType::StringValue(self.quoted('"')?)));
+        }
+        if self
+            .rest()
+            .starts_with(|c: char| c == '-' || c.is_ascii_digit())
+        {
+            return Ok(value_type(match self.number()? {
+                Number::Integer(i) => ValueType::IntegerValue(i),
+                Number::Double(d) => ValueType::DoubleValue(d),
+            }));
+        }
+
+        let position = self.position;
+        let word = self.word().to_ascii_uppercase();
+        self.position += word.len();
+        Ok(match word.as_str() {
+            "NULL" => value_type(ValueType::NullValue(0)),
+            "TRUE" => value_type(ValueType::BooleanValue(true)),
+            "FALSE" => value_type(ValueType::BooleanValue(false)),
+            "NAN" => value_type(ValueType::DoubleValue(f64::NAN)),
+            "INFINITY" => value_type(ValueType::DoubleValue(f64::INFINITY)),
+            "TIMESTAMP" => {
+                self.expect_symbol("(")?;
+                let argument_position = self.position;
+                let timestamp = parse_timestamp(&self.quoted('"')?).map_err(|e| {
+                    crate::Error::with_kind(
+                        e.kind(),
+                        format!("{} at position {}", e, argument_position),
+                    )
+                })?;
+                self.expect_symbol(")")?;
+                value_type(ValueType::TimestampValue(timestamp))
+            }
+            "BYTES" => {
+                self.expect_symbol("(")?;
+                let argument_position = self.position;
+                let bytes = decode_base64(&self.quoted('"')?).map_err(|e| {
+                    crate::Error::with_kind(
+                        e.kind(),
+                        format!("{} at position {}", e, argument_position),
+                    )
+                })?;
+                self.expect_symbol(")")?;
+                value_type(ValueType::BytesValue(bytes.into()))
+            }
+            "REF" => {
+                self.expect_symbol("(")?;
+                let reference = self.quoted('"')?;
+                self.expect_symbol(")")?;
+                value_type(ValueType::ReferenceValue(reference))
+            }
+            "GEOPOINT" => {
+                self.expect_symbol("(")?;
+                let latitude = self.double()?;
+                self.expect_symbol(",")?;
+                let longitude = self.double()?;
+                self.expect_symbol(")")?;
+                value_type(ValueType::GeoPointValue(LatLng {
+                    latitude,
+                    longitude,
+                }))
+            }
+            "VECTOR" => {
+                self.expect_symbol("(")?;
+                self.expect_symbol("[")?;
+                let values = self.separated("]", Self::double)?;
+                self.expect_symbol(")")?;
+                Vector::new(values).into_value()?
+            }
+            _ => {
+                self.position = position;
+                return Err(self.error("value"));
+            }
+        })
+    }
+}
+
+fn composite_filter(
+    op: structured_query::composite_filter::Operator,
+    filters: Vec<structured_query::Filter>,
+) -> structured_query::Filter {
+    structured_query::Filter {
+        filter_type: Some(structured_query::filter::FilterType::CompositeFilter(
+            structured_query::CompositeFilter {
+                op: op as i32,
+                filters,
+            },
+        )),
+    }
+}
@@ -0,0 +1,423 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    StructuredQuery, Value, structured_query, value::ValueType,
+};
+
+use crate::{Direction, FieldPath, Query, Result};
+
+/// The scope of a composite index.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rest/v1/projects.databases.collectionGroups.indexes#queryscope>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum QueryScope {
+    /// `COLLECTION` (`Query::collection`)
+    Collection,
+    /// `COLLECTION_GROUP` (`Query::collection_group`)
+    CollectionGroup,
+}
+
+impl QueryScope {
+    fn as_str_name(&self) -> &'static str {
+        match self {
+            QueryScope::Collection => "COLLECTION",
+            QueryScope::CollectionGroup => "COLLECTION_GROUP",
+        }
+    }
+}
+
+/// A field of a composite index.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rest/v1/projects.databases.collectionGroups.indexes#indexfield>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexField {
+    /// An ordered field (`"order": "ASCENDING"` or `"order": "DESCENDING"`).
+    Order {
+        /// The field path.
+        field_path: FieldPath,
+        /// The direction.
+        direction: Direction,
+    },
+    /// An array field (`"arrayConfig": "CONTAINS"`).
+    ArrayContains {
+        /// The field path.
+        field_path: FieldPath,
+    },
+    /// A vector field (`"vectorConfig": { "dimension": ..., "flat": {} }`).
+    Vector {
+        /// The field path.
+        field_path: FieldPath,
+        /// The number of dimensions of the vectors.
+        dimension: usize,
+    },
+}
+
+impl IndexField {
+    /// Returns the field path of the index field.
+    pub fn field_path(&self) -> &FieldPath {
+        match self {
+            IndexField::Order { field_path, .. }
+            | IndexField::ArrayContains { field_path }
+            | IndexField::Vector { field_path, .. } => field_path,
+        }
+    }
+}
+
+/// A composite index required by a query.
+///
+/// See [`Query::composite_indexes`] and [`indexes_json`].
+///
+/// <https://firebase.google.com/docs/firestore/query-data/index-overview#composite_indexes>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_index() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{Direction, FieldPath, IndexField, Query, QueryScope};
+/// let query = Query::collection_group("posts")
+///     .r#where(FieldPath::raw("author").equal("bob")?)
+///     .order_by([FieldPath::raw("created_at").descending()]);
+/// let indexes = query.composite_indexes()?;
+/// assert_eq!(indexes.len(), 1);
+/// assert_eq!(indexes[0].collection_group(), "posts");
+/// assert_eq!(indexes[0].query_scope(), QueryScope::CollectionGroup);
+/// assert_eq!(
+///     indexes[0].fields(),
+///     &[
+///         IndexField::Order {
+///             field_path: FieldPath::raw("author"),
+///             direction: Direction::Ascending,
+///         },
+///         IndexField::Order {
+///             field_path: FieldPath::raw("created_at"),
+///             direction: Direction::Descending,
+///         },
+///     ]
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Index {
+    collection_group: String,
+    query_scope: QueryScope,
+    fields: Vec<IndexField>,
+}
+
+impl Index {
+    /// Returns the collection ID of the index.
+    pub fn collection_group(&self) -> &str {
+        &self.collection_group
+    }
+
+    /// Returns the fields of the index in index order.
+    ///
+    /// The implicit `__name__` field at the end of the index is omitted.
+    pub fn fields(&self) -> &[IndexField] {
+        &self.fields
+    }
+
+    /// Returns the query scope of the index.
+    pub fn query_scope(&self) -> QueryScope {
+        self.query_scope
+    }
+}
+
+/// Renders the composite indexes required by the queries as a `firestore.indexes.json` document.
+///
+/// The indexes are deduplicated and listed in order of first appearance.
+/// Returns an error if the indexes of a query cannot be derived (see [`Query::composite_indexes`]).
+///
+/// <https://firebase.google.com/docs/reference/firestore/indexes>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_indexes_json() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Query, indexes_json};
+/// let query1 = Query::collection("users")
+///     .r#where(FieldPath::raw("age").greater_than(20)?)
+///     .order_by([FieldPath::raw("name").ascending()]);
+/// let query2 = Query::collection("users").r#where(FieldPath::raw("name").equal("bob")?);
+/// assert_eq!(
+///     indexes_json([&query1, &query2, &query1])?,
+///     r#"{
+///   "indexes": [
+///     {
+///       "collectionGroup": "users",
+///       "queryScope": "COLLECTION",
+///       "fields": [
+///         {
+///           "fieldPath": "name",
+///           "order": "ASCENDING"
+///         },
+///         {
+///           "fieldPath": "age",
+///           "order": "ASCENDING"
+///         }
+///       ]
+///     }
+///   ],
+///   "fieldOverrides": []
+/// }
+/// "#
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+pub fn indexes_json<'a, I>(queries: I) -> Result<String>
+where
+    I: IntoIterator<Item = &'a Query>,
+{
+    let mut indexes = vec![];
+    for query in queries {
+        for index in query.composite_indexes()? {
+            if !indexes.contains(&index) {
+                indexes.push(index);
+            }
+        }
+    }
+
+    let mut s = String::new();
+    s.push_str("{\n  \"indexes\": [");
+    for (i, index) in indexes.iter().enumerate() {
+        s.push_str(if i == 0 { "\n" } else { ",\n" });
+        s.push_str("    {\n      \"collectionGroup\": ");
+        write_json_string(&mut s, &index.collection_group);
+        s.push_str(",\n      \"queryScope\": \"");
+        s.push_str(index.query_scope.as_str_name());
+        s.push_str("\",\n      \"fields\": [");
+        for (j, field) in index.fields.iter().enumerate() {
+            s.push_str(if j == 0 { "\n" } else { ",\n" });
+            s.push_str("        {\n          \"fieldPath\": ");
+            write_json_string(&mut s, field.field_path().as_str());
+            match field {
+                IndexField::Order { direction, .. } => {
+                    s.push_str(",\n          \"order\": \"");
+                    s.push_str(match direction {
+                        Direction::Ascending => "ASCENDING",
+                        Direction::Descending => "DESCENDING",
+                    });
+                    s.push('"');
+                }
+                IndexField::ArrayContains { .. } => {
+                    s.push_str(",\n          \"arrayConfig\": \"CONTAINS\"");
+                }
+                IndexField::Vector { dimension, .. } => {
+                    s.push_str(",\n          \"vectorConfig\": {\n            \"dimension\": ");
+                    s.push_str(&dimension.to_string());
+                    s.push_str(",\n            \"flat\": {}\n          }");
+                }
+            }
+            s.push_str("\n        }");
+        }
+        s.push_str(if index.fields.is_empty() {
+            "]\n    }"
+        } else {
+            "\n      ]\n    }"
+        });
+    }
+    s.push_str(if indexes.is_empty() { "]" } else { "\n  ]" });
+    s.push_str(",\n  \"fieldOverrides\": []\n}\n");
+    Ok(s)
+}
+
+fn write_json_string(s: &mut String, value: &str) {
+    use std::fmt::Write as _;
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(s, "\\u{:04x}", u32::from(c));
+            }
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+/// Returns the composite indexes required by the query.
+///
+/// See [`Query::composite_indexes`].
+///
+/// Each conjunction of the filter in disjunctive normal form is served by its own index.
+/// An index lists the equality fields (in field path order), the array field,
+/// the fields of the normalized order_by (see [`Query::normalize_order_by`]) and the vector field.
+/// No index is returned if the single-field indexes are sufficient
+/// (at most one field, or only equality and array filters which Firestore serves by merging single-field indexes).
+pub(crate) fn composite_indexes(structured_query: &StructuredQuery) -> Result<Vec<Index>> {
+    use structured_query::filter::FilterType;
+    use structured_query::{field_filter, unary_filter};
+
+    let (collection_group, query_scope) = match structured_query.from.first() {
+        Some(collection_selector) => (
+            collection_selector.collection_id.clone(),
+            if collection_selector.all_descendants {
+                QueryScope::CollectionGroup
+            } else {
+                QueryScope::Collection
+            },
+        ),
+        None => {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "from",
+                "collection selector is required",
+            )]));
+        }
+    };
+
+    let document_id = FieldPath::document_id().segments()?;
+    let mut orders = vec![];
+    for order in crate::query::normalized_order_by(structured_query)? {
+        let order = crate::Order::try_from(order)?;
+        let field_path = order.field_path();
+        orders.push((field_path.segments()?, field_path, order.direction()));
+    }
+    // every index implicitly ends with `__name__` in the direction of the last field
+    if let Some((segments, _, direction)) = orders.last()
+        && segments == &document_id
+        && (orders.len() == 1 || orders[orders.len() - 2].2 == *direction)
+    {
+        orders.pop();
+    }
+
+    let vector = match &structured_query.find_nearest {
+        Some(find_nearest) => {
+            let field_reference = find_nearest.vector_field.as_ref().ok_or_else(|| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::InvalidFieldPath,
+                    "find_nearest vector_field is required",
+                )
+            })?;
+            let dimension = match &find_nearest.query_vector {
+                Some(Value {
+                    value_type: Some(ValueType::MapValue(map_value)),
+                }) => crate::ordered_value::vector_values(map_value).map(<[Value]>::len),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::ValueConversion,
+                    "find_nearest query_vector must be a vector value",
+                )
+            })?;
+            Some(IndexField::Vector {
+                field_path: FieldPath::try_from(field_reference.clone())?,
+                dimension,
+            })
+        }
+        None => None,
+    };
+
+    let conjunctions = match &structured_query.r#where {
+        Some(filter) => {
+            // the expansion grows exponentially, so check the limit before expanding
+            let disjunctions = crate::validation::disjunction_count(filter);
+            if disjunctions > crate::validation::MAX_DISJUNCTIONS {
+                return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                    "where",
+                    format!(
+                        "the number of disjunctions in disjunctive normal form must be less than or equal to {} (actual: {})",
+                        crate::validation::MAX_DISJUNCTIONS,
+                        disjunctions
+                    ),
+                )]));
+            }
+            match crate::normalization::disjunctive_normal_form(filter.clone()).filter_type {
+                Some(FilterType::CompositeFilter(composite_filter))
+                    if composite_filter.op
+                        == structured_query::composite_filter::Operator::Or as i32 =>
+                {
+                    composite_filter.filters
+                }
+                filter_type => vec![structured_query::Filter { filter_type }],
+            }
+        }
+        None => vec![structured_query::Filter { filter_type: None }],
+    };
+
+    let mut indexes = vec![];
+    for conjunction in conjunctions {
+        let filters = match conjunction.filter_type {
+            Some(FilterType::CompositeFilter(composite_filter)) => composite_filter.filters,
+            filter_type => vec![structured_query::Filter { filter_type }],
+        };
+        let mut equality_fields = std::collections::BTreeMap::new();
+        let mut array_field = None;
+        for filter in filters {
+            let (field_reference, is_array) = match &filter.filter_type {
+                Some(FilterType::FieldFilter(field_filter)) => {
+                    match field_filter::Operator::try_from(field_filter.op) {
+                        Ok(field_filter::Operator::Equal | field_filter::Operator::In) => {
+                            (field_filter.field.as_ref(), false)
+                        }
+                        Ok(
+                            field_filter::Operator::ArrayContains
+                            | field_filter::Operator::ArrayContainsAny,
+                        ) => (field_filter.field.as_ref(), true),
+                        _ => continue,
+                    }
+                }
+                Some(FilterType::UnaryFilter(unary_filter)) => {
+                    match unary_filter::Operator::try_from(unary_filter.op) {
+                        Ok(unary_filter::Operator::IsNull | unary_filter::Operator::IsNan) => (
+                            unary_filter.operand_type.as_ref().map(
+                                |unary_filter::OperandType::Field(field_reference)| field_reference,
+                            ),
+                            false,
+                        ),
+                        _ => continue,
+                    }
+                }
+                _ => continue,
+            };
+            let Some(field_reference) = field_reference else {
+                continue;
+            };
+            let field_path = FieldPath::try_from(field_reference.clone())?;
+            let segments = field_path.segments()?;
+            if is_array {
+                array_field.get_or_insert(field_path);
+            } else if segments != document_id
+                && !orders.iter().any(|(ordered, _, _)| ordered == &segments)
+            {
+                equality_fields.insert(segments, field_path);
+            }
+        }
+
+        let mut fields = equality_fields
+            .into_values()
+            .map(|field_path| IndexField::Order {
+                field_path,
+                direction: Direction::Ascending,
+            })
+            .collect::<Vec<IndexField>>();
+        fields.extend(array_field.map(|field_path| IndexField::ArrayContains { field_path }));
+        fields.extend(
+            orders
+                .iter()
+                .map(|(_, field_path, direction)| IndexField::Order {
+                    field_path: field_path.clone(),
+                    direction: *direction,
+                }),
+        );
+        fields.extend(vector.clone());
+
+        // single-field indexes serve a query on one field, and equality and array filters are merged
+        if vector.is_none() && (fields.len() <= 1 || orders.is_empty()) {
+            continue;
+        }
+        let index = Index {
+            collection_group: collection_group.clone(),
+            query_scope,
+            fields,
+        };
+        if !indexes.contains(&index) {
+            indexes.push(index);
+        }
+    }
+    Ok(indexes)
+}
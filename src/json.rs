@@ -0,0 +1,1201 @@
+//! The proto3 JSON mapping of the Firestore messages (the format of the Firestore REST API).
+//!
+//! <https://protobuf.dev/programming-guides/json/>
+//!
+//! - The field names are lowerCamelCase (the original snake_case names are also accepted).
+//! - The fields with the default values are omitted.
+//! - `int64` values are strings (numbers are also accepted).
+//! - Enum values are names (numbers are also accepted).
+//! - Bytes are standard base64 (URL-safe base64 is also accepted).
+//! - Timestamps are RFC 3339 strings.
+
+use googleapis_tonic_google_firestore_v1::google::{
+    firestore::v1::{
+        ArrayValue, Cursor, Function, MapValue, Pipeline, StructuredQuery, Value, pipeline,
+        structured_query::{
+            self, CollectionSelector, FieldReference, Projection, composite_filter, field_filter,
+            filter::FilterType, find_nearest, unary_filter,
+        },
+        value::ValueType,
+    },
+    r#type::LatLng,
+};
+
+use crate::{Filter, Order, Query, Result};
+
+/// A Firestore value that implements `serde::Serialize` and `serde::Deserialize` with the proto3 JSON mapping.
+///
+/// This is a wrapper because `Value` is defined in another crate.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_json_value() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{firestore_value, JsonValue};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::Value;
+/// let value = firestore_value!({"a": [1, "b", null]})?;
+/// let json = serde_json::to_string(&JsonValue::from(value.clone())).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"mapValue":{"fields":{"a":{"arrayValue":{"values":[{"integerValue":"1"},{"stringValue":"b"},{"nullValue":null}]}}}}}"#
+/// );
+/// assert_eq!(Value::from(serde_json::from_str::<JsonValue>(&json).unwrap()), value);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonValue(Value);
+
+impl std::convert::From<Value> for JsonValue {
+    fn from(value: Value) -> Self {
+        Self(value)
+    }
+}
+
+impl std::convert::From<JsonValue> for Value {
+    fn from(value: JsonValue) -> Self {
+        value.0
+    }
+}
+
+impl serde::Serialize for JsonValue {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        value_to_json(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for JsonValue {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let json = Json::deserialize(deserializer)?;
+        value_from_json(json)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes the query as a `StructuredQuery` in the proto3 JSON mapping.
+///
+/// The parent of the query is not a part of the `StructuredQuery` (it is a part of the request URL).
+impl serde::Serialize for Query {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        structured_query_to_json(&StructuredQuery::from(self.clone())).serialize(serializer)
+    }
+}
+
+/// Deserializes the query from a `StructuredQuery` in the proto3 JSON mapping.
+///
+/// The query is validated in the same way as [`Query::try_from`](#impl-TryFrom%3CStructuredQuery%3E-for-Query).
+impl<'de> serde::Deserialize<'de> for Query {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let json = Json::deserialize(deserializer)?;
+        structured_query_from_json(json)
+            .and_then(Query::try_from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes the filter in the proto3 JSON mapping.
+impl serde::Serialize for Filter {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        filter_to_json(&structured_query::Filter::from(self.clone())).serialize(serializer)
+    }
+}
+
+/// Deserializes the filter from the proto3 JSON mapping.
+impl<'de> serde::Deserialize<'de> for Filter {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let json = Json::deserialize(deserializer)?;
+        filter_from_json(json)
+            .and_then(Filter::try_from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes the order in the proto3 JSON mapping.
+impl serde::Serialize for Order {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        order_to_json(&structured_query::Order::from(self.clone())).serialize(serializer)
+    }
+}
+
+/// Deserializes the order from the proto3 JSON mapping.
+impl<'de> serde::Deserialize<'de> for Order {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let json = Json::deserialize(deserializer)?;
+        order_from_json(json)
+            .and_then(Order::try_from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A JSON value (the object keeps the order of the members).
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Unsigned(u64),
+    Double(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl serde::Serialize for Json {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{SerializeMap, SerializeSeq};
+        match self {
+            Json::Null => serializer.serialize_unit(),
+            Json::Bool(b) => serializer.serialize_bool(*b),
+            Json::Integer(n) => serializer.serialize_i64(*n),
+            Json::Unsigned(n) => serializer.serialize_u64(*n),
+            Json::Double(f) => serializer.serialize_f64(*f),
+            Json::String(s) => serializer.serialize_str(s),
+            Json::Array(elements) => {
+                let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+                for element in elements {
+                    seq.serialize_element(element)?;
+                }
+                seq.end()
+            }
+            Json::Object(members) => {
+                let mut map = serializer.serialize_map(Some(members.len()))?;
+                for (name, value) in members {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Json {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct JsonVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for JsonVisitor {
+            type Value = Json;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a JSON value")
+            }
+
+            fn visit_bool<E>(self, v: bool) -> std::result::Result<Json, E> {
+                Ok(Json::Bool(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Json, E> {
+                Ok(Json::Integer(v))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Json, E> {
+                Ok(Json::Unsigned(v))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> std::result::Result<Json, E> {
+                Ok(Json::Double(v))
+            }
+
+            fn visit_str<E>(self, v: &str) -> std::result::Result<Json, E> {
+                Ok(Json::String(v.to_string()))
+            }
+
+            fn visit_string<E>(self, v: String) -> std::result::Result<Json, E> {
+                Ok(Json::String(v))
+            }
+
+            fn visit_unit<E>(self) -> std::result::Result<Json, E> {
+                Ok(Json::Null)
+            }
+
+            fn visit_none<E>(self) -> std::result::Result<Json, E> {
+                Ok(Json::Null)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> std::result::Result<Json, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                serde::Deserialize::deserialize(deserializer)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Json, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut elements = vec![];
+                while let Some(element) = seq.next_element()? {
+                    elements.push(element);
+                }
+                Ok(Json::Array(elements))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> std::result::Result<Json, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut members = vec![];
+                while let Some((name, value)) = map.next_entry::<String, Json>()? {
+                    members.push((name, value));
+                }
+                Ok(Json::Object(members))
+            }
+        }
+
+        deserializer.deserialize_any(JsonVisitor)
+    }
+}
+
+/// The members of a JSON object that are being decoded into a message.
+struct Fields {
+    message: &'static str,
+    members: Vec<(String, Json)>,
+}
+
+impl Fields {
+    fn new(message: &'static str, json: Json) -> Result<Self> {
+        match json {
+            Json::Object(members) => Ok(Self { message, members }),
+            _ => Err(crate::Error::new(format!("{} must be an object", message))),
+        }
+    }
+
+    /// Takes the field by the lowerCamelCase name or the original name (`null` is returned as is).
+    fn take_raw(&mut self, json_name: &str, name: &str) -> Result<Option<Json>> {
+        let mut found = None;
+        let mut index = 0;
+        while index < self.members.len() {
+            if self.members[index].0 == json_name || self.members[index].0 == name {
+                if found.is_some() {
+                    return Err(crate::Error::new(format!(
+                        "duplicate field `{}` in {}",
+                        json_name, self.message
+                    )));
+                }
+                found = Some(self.members.remove(index).1);
+            } else {
+                index += 1;
+            }
+        }
+        Ok(found)
+    }
+
+    /// Takes the field by the lowerCamelCase name or the original name (`null` is treated as absent).
+    fn take(&mut self, json_name: &str, name: &str) -> Result<Option<Json>> {
+        Ok(self
+            .take_raw(json_name, name)?
+            .filter(|json| json != &Json::Null))
+    }
+
+    /// Returns an error if any unknown field remains.
+    fn finish(self) -> Result<()> {
+        match self.members.first() {
+            Some((name, _)) => Err(crate::Error::new(format!(
+                "unknown field `{}` in {}",
+                name, self.message
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn invalid(name: &str, expected: &str) -> crate::Error {
+    crate::Error::new(format!("{} must be {}", name, expected))
+}
+
+fn object(members: Vec<(&str, Option<Json>)>) -> Json {
+    Json::Object(
+        members
+            .into_iter()
+            .filter_map(|(name, json)| json.map(|json| (name.to_string(), json)))
+            .collect(),
+    )
+}
+
+fn non_default<T>(value: T) -> Option<T>
+where
+    T: Default + PartialEq,
+{
+    (value != T::default()).then_some(value)
+}
+
+fn enum_to_json(value: i32, name: Option<&str>) -> Json {
+    match name {
+        Some(name) => Json::String(name.to_string()),
+        // unknown enum values are serialized as numbers
+        None => Json::Integer(i64::from(value)),
+    }
+}
+
+fn double_to_json(f: f64) -> Json {
+    if f.is_nan() {
+        Json::String("NaN".to_string())
+    } else if f == f64::INFINITY {
+        Json::String("Infinity".to_string())
+    } else if f == f64::NEG_INFINITY {
+        Json::String("-Infinity".to_string())
+    } else {
+        Json::Double(f)
+    }
+}
+
+fn bool_from_json(json: Json, name: &str) -> Result<bool> {
+    match json {
+        Json::Bool(b) => Ok(b),
+        _ => Err(invalid(name, "a boolean")),
+    }
+}
+
+fn int64_from_json(json: Json, name: &str) -> Result<i64> {
+    match json {
+        Json::Integer(n) => Ok(n),
+        Json::Unsigned(n) => i64::try_from(n).map_err(|_| invalid(name, "an int64")),
+        Json::Double(f) if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 => {
+            Ok(f as i64)
+        }
+        Json::String(s) => s.parse::<i64>().map_err(|_| invalid(name, "an int64")),
+        _ => Err(invalid(name, "an int64")),
+    }
+}
+
+fn int32_from_json(json: Json, name: &str) -> Result<i32> {
+    i32::try_from(int64_from_json(json, name)?).map_err(|_| invalid(name, "an int32"))
+}
+
+fn double_from_json(json: Json, name: &str) -> Result<f64> {
+    match json {
+        Json::Integer(n) => Ok(n as f64),
+        Json::Unsigned(n) => Ok(n as f64),
+        Json::Double(f) => Ok(f),
+        Json::String(s) => match s.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "Infinity" => Ok(f64::INFINITY),
+            "-Infinity" => Ok(f64::NEG_INFINITY),
+            _ => s.parse::<f64>().map_err(|_| invalid(name, "a double")),
+        },
+        _ => Err(invalid(name, "a double")),
+    }
+}
+
+fn string_from_json(json: Json, name: &str) -> Result<String> {
+    match json {
+        Json::String(s) => Ok(s),
+        _ => Err(invalid(name, "a string")),
+    }
+}
+
+fn array_from_json(json: Json, name: &str) -> Result<Vec<Json>> {
+    match json {
+        Json::Array(elements) => Ok(elements),
+        _ => Err(invalid(name, "an array")),
+    }
+}
+
+fn enum_from_json<T, F>(json: Json, name: &str, from_str_name: F) -> Result<i32>
+where
+    T: Into<i32>,
+    F: Fn(&str) -> Option<T>,
+{
+    match json {
+        Json::String(s) => from_str_name(&s).map(Into::into).ok_or_else(|| {
+            crate::Error::with_kind(
+                crate::ErrorKind::UnsupportedOperator,
+                format!("unknown {} value: {}", name, s),
+            )
+        }),
+        json => int32_from_json(json, name),
+    }
+}
+
+fn structured_query_to_json(structured_query: &StructuredQuery) -> Json {
+    object(vec![
+        (
+            "select",
+            structured_query.select.as_ref().map(|projection| {
+                object(vec![(
+                    "fields",
+                    non_default(projection.fields.clone()).map(|fields| {
+                        Json::Array(fields.iter().map(field_reference_to_json).collect())
+                    }),
+                )])
+            }),
+        ),
+        (
+            "from",
+            non_default(structured_query.from.clone()).map(|from| {
+                Json::Array(
+                    from.iter()
+                        .map(|collection_selector| {
+                            object(vec![
+                                (
+                                    "collectionId",
+                                    non_default(collection_selector.collection_id.clone())
+                                        .map(Json::String),
+                                ),
+                                (
+                                    "allDescendants",
+                                    non_default(collection_selector.all_descendants)
+                                        .map(Json::Bool),
+                                ),
+                            ])
+                        })
+                        .collect(),
+                )
+            }),
+        ),
+        (
+            "where",
+            structured_query.r#where.as_ref().map(filter_to_json),
+        ),
+        (
+            "orderBy",
+            non_default(structured_query.order_by.clone())
+                .map(|orders| Json::Array(orders.iter().map(order_to_json).collect())),
+        ),
+        (
+            "startAt",
+            structured_query.start_at.as_ref().map(cursor_to_json),
+        ),
+        (
+            "endAt",
+            structured_query.end_at.as_ref().map(cursor_to_json),
+        ),
+        (
+            "offset",
+            non_default(structured_query.offset).map(|offset| Json::Integer(i64::from(offset))),
+        ),
+        (
+            "limit",
+            structured_query
+                .limit
+                .map(|limit| Json::Integer(i64::from(limit))),
+        ),
+        (
+            "findNearest",
+            structured_query
+                .find_nearest
+                .as_ref()
+                .map(find_nearest_to_json),
+        ),
+    ])
+}
+
+fn structured_query_from_json(json: Json) -> Result<StructuredQuery> {
+    let mut fields = Fields::new("StructuredQuery", json)?;
+    let select = fields
+        .take("select", "select")?
+        .map(|json| {
+            let mut fields = Fields::new("Projection", json)?;
+            let projection = Projection {
+                fields: fields
+                    .take("fields", "fields")?
+                    .map(|json| array_from_json(json, "fields"))
+                    .transpose()?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(field_reference_from_json)
+                    .collect::<Result<Vec<_>>>()?,
+            };
+            fields.finish()?;
+            Ok::<_, crate::Error>(projection)
+        })
+        .transpose()?;
+    let from = fields
+        .take("from", "from")?
+        .map(|json| array_from_json(json, "from"))
+        .transpose()?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|json| {
+            let mut fields = Fields::new("CollectionSelector", json)?;
+            let collection_selector = CollectionSelector {
+                collection_id: fields
+                    .take("collectionId", "collection_id")?
+                    .map(|json| string_from_json(json, "collectionId"))
+                    .transpose()?
+                    .unwrap_or_default(),
+                all_descendants: fields
+                    .take("allDescendants", "all_descendants")?
+                    .map(|json| bool_from_json(json, "allDescendants"))
+                    .transpose()?
+                    .unwrap_or_default(),
+            };
+            fields.finish()?;
+            Ok(collection_selector)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let r#where = fields
+        .take("where", "where")?
+        .map(filter_from_json)
+        .transpose()?;
+    let order_by = fields
+        .take("orderBy", "order_by")?
+        .map(|json| array_from_json(json, "orderBy"))
+        .transpose()?
+        .unwrap_or_default()
+        .into_iter()
+        .map(order_from_json)
+        .collect::<Result<Vec<_>>>()?;
+    let start_at = fields
+        .take("startAt", "start_at")?
+        .map(cursor_from_json)
+        .transpose()?;
+    let end_at = fields
+        .take("endAt", "end_at")?
+        .map(cursor_from_json)
+        .transpose()?;
+    let offset = fields
+        .take("offset", "offset")?
+        .map(|json| int32_from_json(json, "offset"))
+        .transpose()?
+        .unwrap_or_default();
+    let limit = fields
+        .take("limit", "limit")?
+        .map(|json| int32_from_json(json, "limit"))
+        .transpose()?;
+    let find_nearest = fields
+        .take("findNearest", "find_nearest")?
+        .map(find_nearest_from_json)
+        .transpose()?;
+    fields.finish()?;
+    Ok(StructuredQuery {
+        select,
+        from,
+        r#where,
+        order_by,
+        start_at,
+        end_at,
+        offset,
+        limit,
+        find_nearest,
+    })
+}
+
+fn field_reference_to_json(field_reference: &FieldReference) -> Json {
+    object(vec![(
+        "fieldPath",
+        non_default(field_reference.field_path.clone()).map(Json::String),
+    )])
+}
+
+fn field_reference_from_json(json: Json) -> Result<FieldReference> {
+    let mut fields = Fields::new("FieldReference", json)?;
+    let field_path = fields
+        .take("fieldPath", "field_path")?
+        .map(|json| string_from_json(json, "fieldPath"))
+        .transpose()?
+        .unwrap_or_default();
+    fields.finish()?;
+    Ok(FieldReference { field_path })
+}
+
+fn filter_to_json(filter: &structured_query::Filter) -> Json {
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => object(vec![(
+            "compositeFilter",
+            Some(object(vec![
+                (
+                    "op",
+                    non_default(composite_filter.op).map(|op| {
+                        enum_to_json(
+                            op,
+                            composite_filter::Operator::try_from(op)
+                                .ok()
+                                .map(|op| op.as_str_name()),
+                        )
+                    }),
+                ),
+                (
+                    "filters",
+                    non_default(composite_filter.filters.clone())
+                        .map(|filters| Json::Array(filters.iter().map(filter_to_json).collect())),
+                ),
+            ])),
+        )]),
+        Some(FilterType::FieldFilter(field_filter)) => object(vec![(
+            "fieldFilter",
+            Some(object(vec![
+                (
+                    "field",
+                    field_filter.field.as_ref().map(field_reference_to_json),
+                ),
+                (
+                    "op",
+                    non_default(field_filter.op).map(|op| {
+                        enum_to_json(
+                            op,
+                            field_filter::Operator::try_from(op)
+                                .ok()
+                                .map(|op| op.as_str_name()),
+                        )
+                    }),
+                ),
+                ("value", field_filter.value.as_ref().map(value_to_json)),
+            ])),
+        )]),
+        Some(FilterType::UnaryFilter(unary_filter)) => object(vec![(
+            "unaryFilter",
+            Some(object(vec![
+                (
+                    "op",
+                    non_default(unary_filter.op).map(|op| {
+                        enum_to_json(
+                            op,
+                            unary_filter::Operator::try_from(op)
+                                .ok()
+                                .map(|op| op.as_str_name()),
+                        )
+                    }),
+                ),
+                (
+                    "field",
+                    unary_filter.operand_type.as_ref().map(
+                        |unary_filter::OperandType::Field(field_reference)| {
+                            field_reference_to_json(field_reference)
+                        },
+                    ),
+                ),
+            ])),
+        )]),
+        None => Json::Object(vec![]),
+    }
+}
+
+fn filter_from_json(json: Json) -> Result<structured_query::Filter> {
+    let mut fields = Fields::new("Filter", json)?;
+    let composite_filter = fields.take("compositeFilter", "composite_filter")?;
+    let field_filter = fields.take("fieldFilter", "field_filter")?;
+    let unary_filter = fields.take("unaryFilter", "unary_filter")?;
+    fields.finish()?;
+    let filter_type = match (composite_filter, field_filter, unary_filter) {
+        (Some(json), None, None) => {
+            let mut fields = Fields::new("CompositeFilter", json)?;
+            let op = fields
+                .take("op", "op")?
+                .map(|json| enum_from_json(json, "op", composite_filter::Operator::from_str_name))
+                .transpose()?
+                .unwrap_or_default();
+            let filters = fields
+                .take("filters", "filters")?
+                .map(|json| array_from_json(json, "filters"))
+                .transpose()?
+                .unwrap_or_default()
+                .into_iter()
+                .map(filter_from_json)
+                .collect::<Result<Vec<_>>>()?;
+            fields.finish()?;
+            Some(FilterType::CompositeFilter(
+                structured_query::CompositeFilter { op, filters },
+            ))
+        }
+        (None, Some(json), None) => {
+            let mut fields = Fields::new("FieldFilter", json)?;
+            let field = fields
+                .take("field", "field")?
+                .map(field_reference_from_json)
+                .transpose()?;
+            let op = fields
+                .take("op", "op")?
+                .map(|json| enum_from_json(json, "op", field_filter::Operator::from_str_name))
+                .transpose()?
+                .unwrap_or_default();
+            let value = fields
+                .take("value", "value")?
+                .map(value_from_json)
+                .transpose()?;
+            fields.finish()?;
+            Some(FilterType::FieldFilter(structured_query::FieldFilter {
+                field,
+                op,
+                value,
+            }))
+        }
+        (None, None, Some(json)) => {
+            let mut fields = Fields::new("UnaryFilter", json)?;
+            let op = fields
+                .take("op", "op")?
+                .map(|json| enum_from_json(json, "op", unary_filter::Operator::from_str_name))
+                .transpose()?
+                .unwrap_or_default();
+            let operand_type = fields
+                .take("field", "field")?
+                .map(field_reference_from_json)
+                .transpose()?
+                .map(unary_filter::OperandType::Field);
+            fields.finish()?;
+            Some(FilterType::UnaryFilter(structured_query::UnaryFilter {
+                op,
+                operand_type,
+            }))
+        }
+        (None, None, None) => None,
+        _ => {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "",
+                "Filter must have at most one of compositeFilter, fieldFilter and unaryFilter",
+            )]));
+        }
+    };
+    Ok(structured_query::Filter { filter_type })
+}
+
+fn order_to_json(order: &structured_query::Order) -> Json {
+    object(vec![
+        ("field", order.field.as_ref().map(field_reference_to_json)),
+        (
+            "direction",
+            non_default(order.direction).map(|direction| {
+                enum_to_json(
+                    direction,
+                    structured_query::Direction::try_from(direction)
+                        .ok()
+                        .map(|direction| direction.as_str_name()),
+                )
+            }),
+        ),
+    ])
+}
+
+fn order_from_json(json: Json) -> Result<structured_query::Order> {
+    let mut fields = Fields::new("Order", json)?;
+    let field = fields
+        .take("field", "field")?
+        .map(field_reference_from_json)
+        .transpose()?;
+    let direction = fields
+        .take("direction", "direction")?
+        .map(|json| {
+            enum_from_json(
+                json,
+                "direction",
+                structured_query::Direction::from_str_name,
+            )
+        })
+        .transpose()?
+        .unwrap_or_default();
+    fields.finish()?;
+    Ok(structured_query::Order { field, direction })
+}
+
+fn cursor_to_json(cursor: &Cursor) -> Json {
+    object(vec![
+        (
+            "values",
+            non_default(cursor.values.clone())
+                .map(|values| Json::Array(values.iter().map(value_to_json).collect())),
+        ),
+        ("before", non_default(cursor.before).map(Json::Bool)),
+    ])
+}
+
+fn cursor_from_json(json: Json) -> Result<Cursor> {
+    let mut fields = Fields::new("Cursor", json)?;
+    let values = fields
+        .take("values", "values")?
+        .map(|json| array_from_json(json, "values"))
+        .transpose()?
+        .unwrap_or_default()
+        .into_iter()
+        .map(value_from_json)
+        .collect::<Result<Vec<_>>>()?;
+    let before = fields
+        .take("before", "before")?
+        .map(|json| bool_from_json(json, "before"))
+        .transpose()?
+        .unwrap_or_default();
+    fields.finish()?;
+    Ok(Cursor { values, before })
+}
+
+fn find_nearest_to_json(find_nearest: &structured_query::FindNearest) -> Json {
+    object(vec![
+        (
+            "vectorField",
+            find_nearest
+                .vector_field
+                .as_ref()
+                .map(field_reference_to_json),
+        ),
+        (
+            "queryVector",
+            find_nearest.query_vector.as_ref().map(value_to_json),
+        ),
+        (
+            "distanceMeasure",
+            non_default(find_nearest.distance_measure).map(|distance_measure| {
+                enum_to_json(
+                    distance_measure,
+                    find_nearest::DistanceMeasure::try_from(distance_measure)
+                        .ok()
+                        .map(|distance_measure| distance_measure.as_str_name()),
+                )
+            }),
+        ),
+        (
+            "limit",
+            find_nearest
+                .limit
+                .map(|limit| Json::Integer(i64::from(limit))),
+        ),
+        (
+            "distanceResultField",
+            non_default(find_nearest.distance_result_field.clone()).map(Json::String),
+        ),
+        (
+            "distanceThreshold",
+            find_nearest.distance_threshold.map(double_to_json),
+        ),
+    ])
+}
+
+fn find_nearest_from_json(json: Json) -> Result<structured_query::FindNearest> {
+    let mut fields = Fields::new("FindNearest", json)?;
+    let vector_field = fields
+        .take("vectorField", "vector_field")?
+        .map(field_reference_from_json)
+        .transpose()?;
+    let query_vector = fields
+        .take("queryVector", "query_vector")?
+        .map(value_from_json)
+        .transpose()?;
+    let distance_measure = fields
+        .take("distanceMeasure", "distance_measure")?
+        .map(|json| {
+            enum_from_json(
+                json,
+                "distanceMeasure",
+                find_nearest::DistanceMeasure::from_str_name,
+            )
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let limit = fields
+        .take("limit", "limit")?
+        .map(|json| int32_from_json(json, "limit"))
+        .transpose()?;
+    let distance_result_field = fields
+        .take("distanceResultField", "distance_result_field")?
+        .map(|json| string_from_json(json, "distanceResultField"))
+        .transpose()?
+        .unwrap_or_default();
+    let distance_threshold = fields
+        .take("distanceThreshold", "distance_threshold")?
+        .map(|json| double_from_json(json, "distanceThreshold"))
+        .transpose()?;
+    fields.finish()?;
+    Ok(structured_query::FindNearest {
+        vector_field,
+        query_vector,
+        distance_measure,
+        limit,
+        distance_result_field,
+        distance_threshold,
+    })
+}
+
+fn value_to_json(value: &Value) -> Json {
+    let (name, json) = match &value.value_type {
+        None => return Json::Object(vec![]),
+        Some(ValueType::NullValue(_)) => ("nullValue", Json::Null),
+        Some(ValueType::BooleanValue(b)) => ("booleanValue", Json::Bool(*b)),
+        Some(ValueType::IntegerValue(n)) => ("integerValue", Json::String(n.to_string())),
+        Some(ValueType::DoubleValue(f)) => ("doubleValue", double_to_json(*f)),
+        Some(ValueType::TimestampValue(timestamp)) => (
+            "timestampValue",
+            Json::String(crate::encoding::format_timestamp(timestamp)),
+        ),
+        Some(ValueType::StringValue(s)) => ("stringValue", Json::String(s.clone())),
+        Some(ValueType::BytesValue(bytes)) => (
+            "bytesValue",
+            Json::String(crate::encoding::encode_base64(bytes)),
+        ),
+        Some(ValueType::ReferenceValue(s)) => ("referenceValue", Json::String(s.clone())),
+        Some(ValueType::GeoPointValue(lat_lng)) => (
+            "geoPointValue",
+            object(vec![
+                (
+                    "latitude",
+                    non_default(lat_lng.latitude).map(double_to_json),
+                ),
+                (
+                    "longitude",
+                    non_default(lat_lng.longitude).map(double_to_json),
+                ),
+            ]),
+        ),
+        Some(ValueType::ArrayValue(array_value)) => (
+            "arrayValue",
+            object(vec![(
+                "values",
+                non_default(array_value.values.clone())
+                    .map(|values| Json::Array(values.iter().map(value_to_json).collect())),
+            )]),
+        ),
+        Some(ValueType::MapValue(map_value)) => (
+            "mapValue",
+            object(vec![(
+                "fields",
+                (!map_value.fields.is_empty()).then(|| map_to_json(&map_value.fields)),
+            )]),
+        ),
+        Some(ValueType::FieldReferenceValue(s)) => ("fieldReferenceValue", Json::String(s.clone())),
+        Some(ValueType::VariableReferenceValue(s)) => {
+            ("variableReferenceValue", Json::String(s.clone()))
+        }
+        Some(ValueType::FunctionValue(function)) => (
+            "functionValue",
+            object(vec![
+                ("name", non_default(function.name.clone()).map(Json::String)),
+                (
+                    "args",
+                    non_default(function.args.clone())
+                        .map(|args| Json::Array(args.iter().map(value_to_json).collect())),
+                ),
+                (
+                    "options",
+                    (!function.options.is_empty()).then(|| map_to_json(&function.options)),
+                ),
+            ]),
+        ),
+        Some(ValueType::PipelineValue(pipeline)) => (
+            "pipelineValue",
+            object(vec![(
+                "stages",
+                non_default(pipeline.stages.clone()).map(|stages| {
+                    Json::Array(
+                        stages
+                            .iter()
+                            .map(|stage| {
+                                object(vec![
+                                    ("name", non_default(stage.name.clone()).map(Json::String)),
+                                    (
+                                        "args",
+                                        non_default(stage.args.clone()).map(|args| {
+                                            Json::Array(args.iter().map(value_to_json).collect())
+                                        }),
+                                    ),
+                                    (
+                                        "options",
+                                        (!stage.options.is_empty())
+                                            .then(|| map_to_json(&stage.options)),
+                                    ),
+                                ])
+                            })
+                            .collect(),
+                    )
+                }),
+            )]),
+        ),
+    };
+    Json::Object(vec![(name.to_string(), json)])
+}
+
+fn map_to_json<'a, I>(fields: I) -> Json
+where
+    I: IntoIterator<Item = (&'a String, &'a Value)>,
+{
+    let mut members = fields
+        .into_iter()
+        .map(|(name, value)| (name.clone(), value_to_json(value)))
+        .collect::<Vec<(String, Json)>>();
+    // the output does not depend on the iteration order of the map
+    members.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Json::Object(members)
+}
+
+fn value_from_json(json: Json) -> Result<Value> {
+    let mut members = match json {
+        Json::Object(members) => members,
+        _ => return Err(crate::Error::new("Value must be an object")),
+    };
+    let (name, json) = match members.len() {
+        0 => return Ok(Value { value_type: None }),
+        1 => members.remove(0),
+        _ => {
+            return Err(crate::Error::new(format!(
+                "Value must have at most one field: {}",
+                members
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )));
+        }
+    };
+    let value_type = match name.as_str() {
+        "nullValue" | "null_value" => match json {
+            Json::Null => ValueType::NullValue(0),
+            json => ValueType::NullValue(
+                enum_from_json(json, "nullValue", prost_types::NullValue::from_str_name).map_err(
+                    |e| crate::Error::with_kind(crate::ErrorKind::ValueConversion, e.to_string()),
+                )?,
+            ),
+        },
+        "booleanValue" | "boolean_value" => {
+            ValueType::BooleanValue(bool_from_json(json, "booleanValue")?)
+        }
+        "integerValue" | "integer_value" => {
+            ValueType::IntegerValue(int64_from_json(json, "integerValue")?)
+        }
+        "doubleValue" | "double_value" => {
+            ValueType::DoubleValue(double_from_json(json, "doubleValue")?)
+        }
+        "timestampValue" | "timestamp_value" => ValueType::TimestampValue(
+            crate::encoding::parse_timestamp(&string_from_json(json, "timestampValue")?)?,
+        ),
+        "stringValue" | "string_value" => {
+            ValueType::StringValue(string_from_json(json, "stringValue")?)
+        }
+        "bytesValue" | "bytes_value" => {
+            let s = string_from_json(json, "bytesValue")?;
+            // URL-safe base64 is also accepted
+            let bytes = crate::encoding::decode_base64(&s.replace('-', "+").replace('_', "/"))?;
+            #[allow(clippy::useless_conversion)]
+            ValueType::BytesValue(bytes.into())
+        }
+        "referenceValue" | "reference_value" => {
+            ValueType::ReferenceValue(string_from_json(json, "referenceValue")?)
+        }
+        "geoPointValue" | "geo_point_value" => {
+            let mut fields = Fields::new("LatLng", json)?;
+            let latitude = fields
+                .take("latitude", "latitude")?
+                .map(|json| double_from_json(json, "latitude"))
+                .transpose()?
+                .unwrap_or_default();
+            let longitude = fields
+                .take("longitude", "longitude")?
+                .map(|json| double_from_json(json, "longitude"))
+                .transpose()?
+                .unwrap_or_default();
+            fields.finish()?;
+            ValueType::GeoPointValue(LatLng {
+                latitude,
+                longitude,
+            })
+        }
+        "arrayValue" | "array_value" => {
+            let mut fields = Fields::new("ArrayValue", json)?;
+            let values = values_from_json(fields.take("values", "values")?, "values")?;
+            fields.finish()?;
+            ValueType::ArrayValue(ArrayValue { values })
+        }
+        "mapValue" | "map_value" => {
+            let mut fields = Fields::new("MapValue", json)?;
+            let map = map_from_json(fields.take("fields", "fields")?, "fields")?;
+            fields.finish()?;
+            ValueType::MapValue(MapValue {
+                fields: map.into_iter().collect(),
+            })
+        }
+        "fieldReferenceValue" | "field_reference_value" => {
+            ValueType::FieldReferenceValue(string_from_json(json, "fieldReferenceValue")?)
+        }
+        "variableReferenceValue" | "variable_reference_value" => {
+            ValueType::VariableReferenceValue(string_from_json(json, "variableReferenceValue")?)
+        }
+        "functionValue" | "function_value" => {
+            let mut fields = Fields::new("Function", json)?;
+            let name = fields
+                .take("name", "name")?
+                .map(|json| string_from_json(json, "name"))
+                .transpose()?
+                .unwrap_or_default();
+            let args = values_from_json(fields.take("args", "args")?, "args")?;
+            let options = map_from_json(fields.take("options", "options")?, "options")?;
+            fields.finish()?;
+            ValueType::FunctionValue(Function {
+                name,
+                args,
+                options: options.into_iter().collect(),
+            })
+        }
+        "pipelineValue" | "pipeline_value" => {
+            let mut fields = Fields::new("Pipeline", json)?;
+            let stages = fields
+                .take("stages", "stages")?
+                .map(|json| array_from_json(json, "stages"))
+                .transpose()?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|json| {
+                    let mut fields = Fields::new("Stage", json)?;
+                    let name = fields
+                        .take("name", "name")?
+                        .map(|json| string_from_json(json, "name"))
+                        .transpose()?
+                        .unwrap_or_default();
+                    let args = values_from_json(fields.take("args", "args")?, "args")?;
+                    let options = map_from_json(fields.take("options", "options")?, "options")?;
+                    fields.finish()?;
+                    Ok(pipeline::Stage {
+                        name,
+                        args,
+                        options: options.into_iter().collect(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            fields.finish()?;
+            ValueType::PipelineValue(Pipeline { stages })
+        }
+        _ => {
+            return Err(crate::Error::new(format!(
+                "unknown field `{}` in Value",
+                name
+            )));
+        }
+    };
+    Ok(Value {
+        value_type: Some(value_type),
+    })
+}
+
+fn values_from_json(json: Option<Json>, name: &str) -> Result<Vec<Value>> {
+    json.map(|json| array_from_json(json, name))
+        .transpose()?
+        .unwrap_or_default()
+        .into_iter()
+        .map(value_from_json)
+        .collect()
+}
+
+fn map_from_json(json: Option<Json>, name: &str) -> Result<Vec<(String, Value)>> {
+    match json {
+        None => Ok(vec![]),
+        Some(Json::Object(members)) => members
+            .into_iter()
+            .map(|(name, json)| Ok((name, value_from_json(json)?)))
+            .collect(),
+        Some(_) => Err(invalid(name, "an object")),
+    }
+}
@@ -4,9 +4,9 @@
 //!
 //! ```rust
 //! # fn example_mod_doc() -> firestore_structured_query::Result<()> {
-//! use firestore_structured_query::{FieldPath, Filter, Query};
+//! use firestore_structured_query::{firestore_value, FieldPath, Filter, Query};
 //! use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
-//!     structured_query, value::ValueType, ArrayValue, Cursor, StructuredQuery, Value,
+//!     structured_query, value::ValueType, Cursor, StructuredQuery, Value,
 //! };
 //!
 //! let _ = StructuredQuery::from(
@@ -22,9 +22,9 @@
 //!             FieldPath::raw("field5").equal(Value { value_type: Some(ValueType::IntegerValue(5)) })?,
 //!             FieldPath::raw("field6").not_equal(Value { value_type: Some(ValueType::IntegerValue(6)) })?,
 //!             FieldPath::raw("field7").array_contains(Value { value_type: Some(ValueType::IntegerValue(7)) })?,
-//!             FieldPath::raw("field8").r#in(Value { value_type: Some(ValueType::ArrayValue(ArrayValue { values: vec![Value { value_type: Some(ValueType::IntegerValue(8)) }] })) })?,
-//!             FieldPath::raw("field9").array_contains_any(Value { value_type: Some(ValueType::ArrayValue(ArrayValue { values: vec![Value { value_type: Some(ValueType::IntegerValue(9)) }] })) })?,
-//!             FieldPath::raw("field10").not_in(Value { value_type: Some(ValueType::ArrayValue(ArrayValue { values: vec![Value { value_type: Some(ValueType::IntegerValue(10)) }] })) })?,
+//!             FieldPath::raw("field8").r#in(firestore_value!([8])?)?,
+//!             FieldPath::raw("field9").array_contains_any(firestore_value!([9])?)?,
+//!             FieldPath::raw("field10").not_in(firestore_value!([10])?)?,
 //!             // unary filters
 //!             FieldPath::raw("field11").is_nan()?,
 //!             FieldPath::raw("field12").is_not_nan()?,
@@ -65,20 +65,54 @@
 //!
 //! Name | Description | Default?
 //! ---|---|---
+//! `bytes` | Use `bytes::Bytes` for the bytes values and enable `IntoValue` for `bytes::Bytes`. | No
+//! `chrono` | Enable `IntoValue` for `chrono::DateTime`. | No
+//! `json` | Enable `serde::Serialize` and `serde::Deserialize` for `Query`, `Filter`, `Order` and `JsonValue` with the proto3 JSON mapping (the Firestore REST API format). | No
 //! `serde` | Enable support for `serde::Serialize` using the `serde_serialize_value` crate. | No
+//! `time` | Enable `IntoValue` for `time::OffsetDateTime`. | No
 //!
+mod aggregation_query;
+mod cursor;
+mod encoding;
 mod error;
+mod evaluator;
 mod field_path;
 mod filter;
+mod find_nearest;
+mod gql;
+mod index;
+#[cfg(feature = "json")]
+mod json;
+mod macros;
+mod normalization;
 mod order;
+mod ordered_value;
 mod query;
+mod query_request;
+mod resource_name;
+mod validation;
 mod value;
+mod vector;
 
-pub use self::error::{Error, Result};
+pub use self::aggregation_query::{Aggregation, AggregationQuery};
+pub use self::cursor::Cursor;
+pub use self::error::{Error, ErrorKind, Result};
+pub use self::evaluator::evaluate;
 pub use self::field_path::FieldPath;
-pub use self::filter::Filter;
-pub use self::order::Order;
+pub use self::filter::{CompositeOperator, FieldOperator, Filter, FilterKind, UnaryOperator};
+pub use self::find_nearest::{DistanceMeasure, FindNearest};
+pub use self::index::{Index, IndexField, QueryScope, indexes_json};
+#[cfg(feature = "json")]
+pub use self::json::JsonValue;
+#[doc(hidden)]
+pub use self::macros::__private;
+pub use self::order::{Direction, Order};
+pub use self::ordered_value::OrderedValue;
 pub use self::query::Query;
+pub use self::query_request::QueryRequest;
+pub use self::resource_name::{CollectionName, DatabaseName, DocumentName};
+pub use self::validation::Violation;
 pub use self::value::IntoValue;
 #[cfg(feature = "serde")]
 pub use self::value::to_value;
+pub use self::vector::Vector;
@@ -0,0 +1,232 @@
+/// Builds a Firestore value from a JSON-like literal.
+///
+/// The macro returns `Result<Value>`.
+///
+/// Syntax | Firestore value
+/// ---|---
+/// `null` | `null_value`
+/// `[a, b, ...]` | `array_value`
+/// `{"key": a, (key_expr): b, ...}` | `map_value`
+/// `timestamp("2006-01-02T15:04:05Z")`, `timestamp(seconds, nanos)` | `timestamp_value` (truncated to microseconds)
+/// `bytes(b"...")` | `bytes_value`
+/// `geo_point(latitude, longitude)` | `geo_point_value`
+/// `reference("projects/p/databases/d/documents/c/d")` | `reference_value`
+/// `vector([1.0, 2.0])` | vector value (`map_value`)
+/// any other expression | `IntoValue::into_value(expression)`
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_firestore_value() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{firestore_value, FieldPath, IntoValue};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     value::ValueType, ArrayValue, MapValue, Value,
+/// };
+/// let name = "bob";
+/// let value = firestore_value!({
+///     "name": name,
+///     "age": 20,
+///     "tags": ["a", "b"],
+///     "deleted_at": null,
+///     "created_at": timestamp("2006-01-02T15:04:05Z"),
+/// })?;
+/// assert_eq!(
+///     value,
+///     Value {
+///         value_type: Some(ValueType::MapValue(MapValue {
+///             fields: [
+///                 ("name".to_string(), "bob".into_value()?),
+///                 ("age".to_string(), 20.into_value()?),
+///                 ("tags".to_string(), vec!["a", "b"].into_value()?),
+///                 ("deleted_at".to_string(), None::<i64>.into_value()?),
+///                 (
+///                     "created_at".to_string(),
+///                     prost_types::Timestamp { seconds: 1_136_214_245, nanos: 0 }.into_value()?,
+///                 ),
+///             ]
+///             .into_iter()
+///             .collect(),
+///         })),
+///     }
+/// );
+/// let _ = FieldPath::raw("field1").r#in(firestore_value!([1, 2, 3])?)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! firestore_value {
+    ($($value:tt)+) => {
+        $crate::__firestore_value_internal!(@value $($value)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __firestore_value_internal {
+    // values
+    (@value null) => {
+        $crate::__private::null()
+    };
+    (@value timestamp($s:expr $(,)?)) => {
+        $crate::__private::timestamp_from_str($s)
+    };
+    (@value timestamp($seconds:expr, $nanos:expr $(,)?)) => {
+        $crate::__private::timestamp($seconds, $nanos)
+    };
+    (@value bytes($bytes:expr $(,)?)) => {
+        $crate::__private::bytes($bytes)
+    };
+    (@value geo_point($latitude:expr, $longitude:expr $(,)?)) => {
+        $crate::__private::geo_point($latitude, $longitude)
+    };
+    (@value reference($name:expr $(,)?)) => {
+        $crate::__private::reference($name)
+    };
+    (@value vector($values:expr $(,)?)) => {
+        $crate::IntoValue::into_value($crate::Vector::new($values))
+    };
+    (@value [$($elements:tt)*]) => {
+        // a trailing comma ends every element (the empty elements are skipped)
+        $crate::__firestore_value_internal!(@array [] $($elements)* ,)
+    };
+    (@value {$($entries:tt)*}) => {
+        $crate::__firestore_value_internal!(@map [] $($entries)* ,)
+    };
+    (@value $value:expr) => {
+        $crate::IntoValue::into_value($value)
+    };
+
+    // arrays
+    (@array [$($elements:expr,)*]) => {
+        $crate::__private::array(::std::vec![$($elements,)*])
+    };
+    (@array [$($elements:expr,)*] , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@array [$($elements,)*] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] null , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@array [$($elements,)* $crate::__firestore_value_internal!(@value null),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] $form:ident ($($args:tt)*) , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@array [$($elements,)* $crate::__firestore_value_internal!(@value $form($($args)*)),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] [$($array:tt)*] , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@array [$($elements,)* $crate::__firestore_value_internal!(@value [$($array)*]),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] {$($map:tt)*} , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@array [$($elements,)* $crate::__firestore_value_internal!(@value {$($map)*}),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] $next:expr , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@array [$($elements,)* $crate::__firestore_value_internal!(@value $next),] $($rest)*)
+    };
+
+    // maps
+    (@map [$(($keys:expr, $values:expr),)*]) => {
+        $crate::__private::map(::std::vec![$((::std::convert::Into::<::std::string::String>::into($keys), $values),)*])
+    };
+    (@map [$($entries:tt)*] , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@map [$($entries)*] $($rest)*)
+    };
+    (@map [$($entries:tt)*] $key:literal : $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@entry [$($entries)*] ($key) $($rest)*)
+    };
+    (@map [$($entries:tt)*] ($key:expr) : $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@entry [$($entries)*] ($key) $($rest)*)
+    };
+    (@entry [$($entries:tt)*] ($key:expr) null , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@map [$($entries)* ($key, $crate::__firestore_value_internal!(@value null)),] $($rest)*)
+    };
+    (@entry [$($entries:tt)*] ($key:expr) $form:ident ($($args:tt)*) , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@map [$($entries)* ($key, $crate::__firestore_value_internal!(@value $form($($args)*))),] $($rest)*)
+    };
+    (@entry [$($entries:tt)*] ($key:expr) [$($array:tt)*] , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@map [$($entries)* ($key, $crate::__firestore_value_internal!(@value [$($array)*])),] $($rest)*)
+    };
+    (@entry [$($entries:tt)*] ($key:expr) {$($map:tt)*} , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@map [$($entries)* ($key, $crate::__firestore_value_internal!(@value {$($map)*})),] $($rest)*)
+    };
+    (@entry [$($entries:tt)*] ($key:expr) $value:expr , $($rest:tt)*) => {
+        $crate::__firestore_value_internal!(@map [$($entries)* ($key, $crate::__firestore_value_internal!(@value $value)),] $($rest)*)
+    };
+}
+
+/// The helpers for the `firestore_value!` macro.
+#[doc(hidden)]
+pub mod __private {
+    use googleapis_tonic_google_firestore_v1::google::{
+        firestore::v1::{ArrayValue, MapValue, Value, value::ValueType},
+        r#type::LatLng,
+    };
+
+    use crate::{DocumentName, IntoValue, Result};
+
+    pub fn null() -> Result<Value> {
+        Ok(Value {
+            value_type: Some(ValueType::NullValue(0)),
+        })
+    }
+
+    pub fn timestamp_from_str(s: &str) -> Result<Value> {
+        crate::encoding::parse_timestamp(s)?.into_value()
+    }
+
+    pub fn timestamp(seconds: i64, nanos: u32) -> Result<Value> {
+        Ok(Value {
+            value_type: Some(ValueType::TimestampValue(crate::encoding::timestamp(
+                seconds, nanos,
+            )?)),
+        })
+    }
+
+    pub fn bytes<B>(bytes: B) -> Result<Value>
+    where
+        B: AsRef<[u8]>,
+    {
+        bytes.as_ref().into_value()
+    }
+
+    pub fn geo_point(latitude: f64, longitude: f64) -> Result<Value> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::ValueConversion,
+                format!("latitude must be in the range [-90.0, +90.0]: {}", latitude),
+            ));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::ValueConversion,
+                format!(
+                    "longitude must be in the range [-180.0, +180.0]: {}",
+                    longitude
+                ),
+            ));
+        }
+        LatLng {
+            latitude,
+            longitude,
+        }
+        .into_value()
+    }
+
+    pub fn reference(name: &str) -> Result<Value> {
+        name.parse::<DocumentName>()?.into_value()
+    }
+
+    pub fn array(values: Vec<Result<Value>>) -> Result<Value> {
+        Ok(Value {
+            value_type: Some(ValueType::ArrayValue(ArrayValue {
+                values: values.into_iter().collect::<Result<Vec<Value>>>()?,
+            })),
+        })
+    }
+
+    pub fn map(entries: Vec<(String, Result<Value>)>) -> Result<Value> {
+        Ok(Value {
+            value_type: Some(ValueType::MapValue(MapValue {
+                fields: entries
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, v?)))
+                    .collect::<Result<_>>()?,
+            })),
+        })
+    }
+}
@@ -0,0 +1,254 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query::{
+    self, composite_filter, field_filter, filter::FilterType, unary_filter,
+};
+
+use crate::Result;
+
+/// Merges the nested composite filters that have the same operator as their parent.
+pub(crate) fn flatten(filter: structured_query::Filter) -> structured_query::Filter {
+    match filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            let op = composite_filter.op;
+            let mut filters = vec![];
+            for filter in composite_filter.filters.into_iter().map(flatten) {
+                match filter.filter_type {
+                    Some(FilterType::CompositeFilter(nested)) if nested.op == op => {
+                        filters.extend(nested.filters)
+                    }
+                    filter_type => filters.push(structured_query::Filter { filter_type }),
+                }
+            }
+            composite(op, filters)
+        }
+        filter_type => structured_query::Filter { filter_type },
+    }
+}
+
+/// Flattens the filter, drops the duplicate children, unwraps the single-child composite filters
+/// and reduces the composite filters that contain an empty composite filter.
+///
+/// An empty `AND` is always true and an empty `OR` is always false.
+pub(crate) fn simplify(filter: structured_query::Filter) -> structured_query::Filter {
+    match filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            let op = composite_filter.op;
+            let mut filters: Vec<structured_query::Filter> = vec![];
+            for filter in composite_filter.filters.into_iter().map(simplify) {
+                match filter.filter_type {
+                    Some(FilterType::CompositeFilter(nested)) if nested.op == op => {
+                        for filter in nested.filters {
+                            if !filters.contains(&filter) {
+                                filters.push(filter);
+                            }
+                        }
+                    }
+                    // `x AND false` is false and `x OR true` is true
+                    Some(FilterType::CompositeFilter(nested)) if nested.filters.is_empty() => {
+                        return composite(nested.op, vec![]);
+                    }
+                    filter_type => {
+                        let filter = structured_query::Filter { filter_type };
+                        if !filters.contains(&filter) {
+                            filters.push(filter);
+                        }
+                    }
+                }
+            }
+            if filters.len() == 1 {
+                filters.remove(0)
+            } else {
+                composite(op, filters)
+            }
+        }
+        filter_type => structured_query::Filter { filter_type },
+    }
+}
+
+/// Expands the filter into disjunctive normal form (an `OR` of `AND`s of field and unary filters).
+///
+/// The result is simplified.
+pub(crate) fn disjunctive_normal_form(
+    filter: structured_query::Filter,
+) -> structured_query::Filter {
+    let mut conjunctions: Vec<Vec<structured_query::Filter>> = vec![];
+    for conjunction in conjunctions_of(simplify(filter)) {
+        // `x OR true` is true
+        if conjunction.is_empty() {
+            return composite(composite_filter::Operator::And as i32, vec![]);
+        }
+        if !conjunctions.contains(&conjunction) {
+            conjunctions.push(conjunction);
+        }
+    }
+    simplify(composite(
+        composite_filter::Operator::Or as i32,
+        conjunctions
+            .into_iter()
+            .map(|filters| composite(composite_filter::Operator::And as i32, filters))
+            .collect(),
+    ))
+}
+
+/// Returns the logical negation of the filter using De Morgan's laws and the inverse operators.
+///
+/// Returns an error if the filter contains an `array_contains` or `array_contains_any` filter,
+/// or an `in` filter with more values than `not_in` supports.
+/// The position of the error is relative to the filter (see [`crate::Error::position`]).
+pub(crate) fn negate(filter: structured_query::Filter) -> Result<structured_query::Filter> {
+    match filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            let op = match composite_filter::Operator::try_from(composite_filter.op) {
+                Ok(composite_filter::Operator::And) => composite_filter::Operator::Or,
+                Ok(composite_filter::Operator::Or) => composite_filter::Operator::And,
+                Ok(composite_filter::Operator::Unspecified) | Err(_) => {
+                    return Err(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("invalid composite filter operator: {}", composite_filter.op),
+                    )
+                    .with_operator(composite_filter.op.to_string())
+                    .at_root());
+                }
+            };
+            Ok(composite(
+                op as i32,
+                composite_filter
+                    .filters
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, filter)| {
+                        negate(filter).map_err(|e| e.at(format!("filters[{}]", index)))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            ))
+        }
+        Some(FilterType::FieldFilter(mut field_filter)) => {
+            let field_path = || {
+                crate::FieldPath::raw(
+                    field_filter
+                        .field
+                        .as_ref()
+                        .map(|field_reference| field_reference.field_path.as_str())
+                        .unwrap_or_default(),
+                )
+            };
+            let op = match field_filter::Operator::try_from(field_filter.op) {
+                Ok(field_filter::Operator::LessThan) => field_filter::Operator::GreaterThanOrEqual,
+                Ok(field_filter::Operator::LessThanOrEqual) => field_filter::Operator::GreaterThan,
+                Ok(field_filter::Operator::GreaterThan) => field_filter::Operator::LessThanOrEqual,
+                Ok(field_filter::Operator::GreaterThanOrEqual) => field_filter::Operator::LessThan,
+                Ok(field_filter::Operator::Equal) => field_filter::Operator::NotEqual,
+                Ok(field_filter::Operator::NotEqual) => field_filter::Operator::Equal,
+                Ok(field_filter::Operator::In) => field_filter::Operator::NotIn,
+                Ok(field_filter::Operator::NotIn) => field_filter::Operator::In,
+                Ok(
+                    op @ (field_filter::Operator::ArrayContains
+                    | field_filter::Operator::ArrayContainsAny),
+                ) => {
+                    return Err(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!(
+                            "{} filter cannot be negated (field: {})",
+                            op.as_str_name(),
+                            field_path()
+                        ),
+                    )
+                    .with_field_path(field_path())
+                    .with_operator(op.as_str_name())
+                    .at_root());
+                }
+                Ok(field_filter::Operator::Unspecified) | Err(_) => {
+                    return Err(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("invalid field filter operator: {}", field_filter.op),
+                    )
+                    .with_field_path(field_path())
+                    .with_operator(field_filter.op.to_string())
+                    .at_root());
+                }
+            };
+            // e.g. `in` with more than 10 values cannot be negated into `not_in`
+            let value = field_filter.value.clone().unwrap_or_default();
+            if let Err(message) = crate::validation::check_field_filter_value(op, &value) {
+                return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                    "", message,
+                )])
+                .with_field_path(field_path())
+                .with_operator(op.as_str_name())
+                .at_root());
+            }
+            field_filter.op = op as i32;
+            Ok(structured_query::Filter {
+                filter_type: Some(FilterType::FieldFilter(field_filter)),
+            })
+        }
+        Some(FilterType::UnaryFilter(mut unary_filter)) => {
+            let op = match unary_filter::Operator::try_from(unary_filter.op) {
+                Ok(unary_filter::Operator::IsNan) => unary_filter::Operator::IsNotNan,
+                Ok(unary_filter::Operator::IsNotNan) => unary_filter::Operator::IsNan,
+                Ok(unary_filter::Operator::IsNull) => unary_filter::Operator::IsNotNull,
+                Ok(unary_filter::Operator::IsNotNull) => unary_filter::Operator::IsNull,
+                Ok(unary_filter::Operator::Unspecified) | Err(_) => {
+                    return Err(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!("invalid unary filter operator: {}", unary_filter.op),
+                    )
+                    .with_operator(unary_filter.op.to_string())
+                    .at_root());
+                }
+            };
+            unary_filter.op = op as i32;
+            Ok(structured_query::Filter {
+                filter_type: Some(FilterType::UnaryFilter(unary_filter)),
+            })
+        }
+        None => Err(crate::Error::from_violations(vec![crate::Violation::new(
+            "",
+            "filter type is required",
+        )])
+        .at_root()),
+    }
+}
+
+/// Returns the conjunctions of the filter in disjunctive normal form.
+fn conjunctions_of(filter: structured_query::Filter) -> Vec<Vec<structured_query::Filter>> {
+    match filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter))
+            if composite_filter.op == composite_filter::Operator::Or as i32 =>
+        {
+            composite_filter
+                .filters
+                .into_iter()
+                .flat_map(conjunctions_of)
+                .collect()
+        }
+        Some(FilterType::CompositeFilter(composite_filter)) => composite_filter
+            .filters
+            .into_iter()
+            .map(conjunctions_of)
+            .fold(vec![vec![]], |conjunctions, others| {
+                conjunctions
+                    .iter()
+                    .flat_map(|conjunction| {
+                        others.iter().map(move |other| {
+                            let mut conjunction = conjunction.clone();
+                            for filter in other {
+                                if !conjunction.contains(filter) {
+                                    conjunction.push(filter.clone());
+                                }
+                            }
+                            conjunction
+                        })
+                    })
+                    .collect()
+            }),
+        filter_type => vec![vec![structured_query::Filter { filter_type }]],
+    }
+}
+
+fn composite(op: i32, filters: Vec<structured_query::Filter>) -> structured_query::Filter {
+    structured_query::Filter {
+        filter_type: Some(FilterType::CompositeFilter(
+            structured_query::CompositeFilter { op, filters },
+        )),
+    }
+}
@@ -1,6 +1,6 @@
 use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query;
 
-use crate::FieldPath;
+use crate::{FieldPath, Result};
 
 /// A Firestore query order.
 ///
@@ -45,8 +45,128 @@ impl Order {
     }
 }
 
+/// A sort direction.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#direction>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    /// `ASCENDING`
+    Ascending,
+    /// `DESCENDING`
+    Descending,
+}
+
+impl std::convert::From<Direction> for structured_query::Direction {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Ascending => structured_query::Direction::Ascending,
+            Direction::Descending => structured_query::Direction::Descending,
+        }
+    }
+}
+
+impl Order {
+    /// Returns the direction of the order.
+    ///
+    /// `DIRECTION_UNSPECIFIED` is returned as `Direction::Ascending` (the default of Firestore).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_order_direction() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{Direction, FieldPath};
+    /// assert_eq!(FieldPath::raw("field1").ascending().direction(), Direction::Ascending);
+    /// assert_eq!(FieldPath::raw("field1").descending().direction(), Direction::Descending);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn direction(&self) -> Direction {
+        match structured_query::Direction::try_from(self.0.direction) {
+            Ok(structured_query::Direction::Descending) => Direction::Descending,
+            Ok(
+                structured_query::Direction::Ascending | structured_query::Direction::Unspecified,
+            )
+            | Err(_) => Direction::Ascending,
+        }
+    }
+
+    /// Returns the field path of the order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_order_field_path() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::FieldPath;
+    /// assert_eq!(
+    ///     FieldPath::raw("field1").ascending().field_path(),
+    ///     FieldPath::raw("field1")
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn field_path(&self) -> FieldPath {
+        FieldPath::raw(
+            self.0
+                .field
+                .as_ref()
+                .map(|field_reference| field_reference.field_path.clone())
+                .unwrap_or_default(),
+        )
+    }
+}
+
 impl std::convert::From<Order> for structured_query::Order {
     fn from(order: Order) -> Self {
         order.0
     }
 }
+
+/// Converts a `structured_query::Order` into an `Order`.
+///
+/// Returns an error if the order has no field, an invalid field path or an unknown direction.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_order_try_from() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Order};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::structured_query;
+/// let order1 = FieldPath::raw("field1").descending();
+/// assert_eq!(Order::try_from(structured_query::Order::from(order1.clone()))?, order1);
+/// assert!(Order::try_from(structured_query::Order {
+///     field: None,
+///     direction: structured_query::Direction::Ascending as i32,
+/// })
+/// .is_err());
+/// assert!(Order::try_from(structured_query::Order {
+///     field: Some(structured_query::FieldReference {
+///         field_path: "field1".to_string(),
+///     }),
+///     direction: 3,
+/// })
+/// .is_err());
+/// #     Ok(())
+/// # }
+/// ```
+impl std::convert::TryFrom<structured_query::Order> for Order {
+    type Error = crate::Error;
+
+    fn try_from(order: structured_query::Order) -> Result<Self> {
+        let field_reference = order.field.clone().ok_or_else(|| {
+            crate::Error::with_kind(
+                crate::ErrorKind::InvalidFieldPath,
+                "order field is required",
+            )
+        })?;
+        let field_path = FieldPath::try_from(field_reference)?;
+        if structured_query::Direction::try_from(order.direction).is_err() {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::UnsupportedOperator,
+                format!("invalid order direction: {}", order.direction),
+            )
+            .with_field_path(field_path)
+            .with_operator(order.direction.to_string()));
+        }
+        Ok(Self(order))
+    }
+}
@@ -0,0 +1,358 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    MapValue, Value, value::ValueType,
+};
+
+use crate::{IntoValue, Result};
+
+/// A Firestore value that implements `Ord`, `Eq` and `Hash` with the Firestore value ordering.
+///
+/// <https://firebase.google.com/docs/firestore/manage-data/data-types#value_type_ordering>
+///
+/// Values of different types are ordered as follows:
+///
+/// null < boolean < NaN < numbers < timestamps < strings < bytes < references < geo points < arrays < vectors < maps
+///
+/// Vectors are ordered before maps, although vectors are stored as maps.
+/// This deliberately differs from the "arrays < maps < vectors" order that is sometimes cited:
+/// the Firestore documentation linked above and the Firestore backend put vectors between arrays and maps,
+/// and the results must be sorted the same way as Firestore sorts them.
+///
+/// - Integers and doubles are compared numerically. `NaN` is equal to `NaN`, and `-0.0` is equal to `0.0`.
+/// - Strings and map keys are compared by their UTF-8 encoded bytes.
+/// - References are compared segment by segment.
+/// - Geo points are compared by latitude, then by longitude.
+/// - Arrays are compared element by element, then by length.
+/// - Vectors are compared by dimension, then element by element.
+/// - Maps are compared key by key (in sorted key order) and value by value, then by size.
+/// - Values that cannot be stored in a document (e.g. function values) are ordered last and are equal to each other.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_ordered_value() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::OrderedValue;
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+/// let value = |value_type: ValueType| OrderedValue::new(Value { value_type: Some(value_type) });
+/// let mut values = vec![
+///     value(ValueType::StringValue("a".to_string())),
+///     value(ValueType::DoubleValue(1.5)),
+///     value(ValueType::IntegerValue(1)),
+///     value(ValueType::DoubleValue(f64::NAN)),
+///     value(ValueType::BooleanValue(true)),
+///     value(ValueType::NullValue(0)),
+/// ];
+/// values.sort();
+/// assert_eq!(
+///     values,
+///     vec![
+///         value(ValueType::NullValue(0)),
+///         value(ValueType::BooleanValue(true)),
+///         value(ValueType::DoubleValue(f64::NAN)),
+///         value(ValueType::IntegerValue(1)),
+///         value(ValueType::DoubleValue(1.5)),
+///         value(ValueType::StringValue("a".to_string())),
+///     ]
+/// );
+/// assert_eq!(value(ValueType::IntegerValue(1)), value(ValueType::DoubleValue(1.0)));
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct OrderedValue(Value);
+
+impl OrderedValue {
+    /// Creates a new `OrderedValue`.
+    pub fn new(value: Value) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the inner value.
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Returns the inner value.
+    pub fn into_inner(self) -> Value {
+        self.0
+    }
+}
+
+impl std::convert::From<Value> for OrderedValue {
+    fn from(value: Value) -> Self {
+        Self(value)
+    }
+}
+
+impl std::convert::From<OrderedValue> for Value {
+    fn from(ordered_value: OrderedValue) -> Self {
+        ordered_value.0
+    }
+}
+
+impl Eq for OrderedValue {}
+
+impl Hash for OrderedValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(&self.0, state)
+    }
+}
+
+impl IntoValue for OrderedValue {
+    fn into_value(self) -> Result<Value> {
+        Ok(self.0)
+    }
+}
+
+impl Ord for OrderedValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(&self.0, &other.0)
+    }
+}
+
+impl PartialEq for OrderedValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for OrderedValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// <https://firebase.google.com/docs/firestore/manage-data/data-types#value_type_ordering>
+// Vectors (9) sort between arrays and maps, as in Firestore, not after maps (see `OrderedValue`).
+fn type_order(value: &Value) -> u8 {
+    match &value.value_type {
+        None | Some(ValueType::NullValue(_)) => 0,
+        Some(ValueType::BooleanValue(_)) => 1,
+        Some(ValueType::IntegerValue(_)) | Some(ValueType::DoubleValue(_)) => 2,
+        Some(ValueType::TimestampValue(_)) => 3,
+        Some(ValueType::StringValue(_)) => 4,
+        Some(ValueType::BytesValue(_)) => 5,
+        Some(ValueType::ReferenceValue(_)) => 6,
+        Some(ValueType::GeoPointValue(_)) => 7,
+        Some(ValueType::ArrayValue(_)) => 8,
+        Some(ValueType::MapValue(map_value)) => {
+            if vector_values(map_value).is_some() {
+                9
+            } else {
+                10
+            }
+        }
+        Some(ValueType::FieldReferenceValue(_))
+        | Some(ValueType::VariableReferenceValue(_))
+        | Some(ValueType::FunctionValue(_))
+        | Some(ValueType::PipelineValue(_)) => 11,
+    }
+}
+
+pub(crate) fn vector_values(map_value: &MapValue) -> Option<&[Value]> {
+    match map_value.fields.get("__type__") {
+        Some(Value {
+            value_type: Some(ValueType::StringValue(t)),
+        }) if t == "__vector__" => {}
+        _ => return None,
+    }
+    match map_value.fields.get("value") {
+        Some(Value {
+            value_type: Some(ValueType::ArrayValue(array_value)),
+        }) => Some(array_value.values.as_slice()),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_nan(value: &Value) -> bool {
+    matches!(value.value_type, Some(ValueType::DoubleValue(d)) if d.is_nan())
+}
+
+pub(crate) fn is_null(value: &Value) -> bool {
+    matches!(value.value_type, None | Some(ValueType::NullValue(_)))
+}
+
+/// Returns `true` if both values have the same type order (integers and doubles share one).
+pub(crate) fn is_comparable(a: &Value, b: &Value) -> bool {
+    type_order(a) == type_order(b)
+}
+
+pub(crate) fn compare(a: &Value, b: &Value) -> Ordering {
+    let (ta, tb) = (type_order(a), type_order(b));
+    if ta != tb {
+        return ta.cmp(&tb);
+    }
+    match (&a.value_type, &b.value_type) {
+        (Some(ValueType::BooleanValue(a)), Some(ValueType::BooleanValue(b))) => a.cmp(b),
+        (Some(ValueType::IntegerValue(a)), Some(ValueType::IntegerValue(b))) => a.cmp(b),
+        (Some(ValueType::IntegerValue(a)), Some(ValueType::DoubleValue(b))) => {
+            compare_integer_and_double(*a, *b)
+        }
+        (Some(ValueType::DoubleValue(a)), Some(ValueType::IntegerValue(b))) => {
+            compare_integer_and_double(*b, *a).reverse()
+        }
+        (Some(ValueType::DoubleValue(a)), Some(ValueType::DoubleValue(b))) => {
+            compare_doubles(*a, *b)
+        }
+        (Some(ValueType::TimestampValue(a)), Some(ValueType::TimestampValue(b))) => {
+            (a.seconds, a.nanos).cmp(&(b.seconds, b.nanos))
+        }
+        (Some(ValueType::StringValue(a)), Some(ValueType::StringValue(b))) => {
+            a.as_bytes().cmp(b.as_bytes())
+        }
+        (Some(ValueType::BytesValue(a)), Some(ValueType::BytesValue(b))) => a[..].cmp(&b[..]),
+        (Some(ValueType::ReferenceValue(a)), Some(ValueType::ReferenceValue(b))) => {
+            a.split('/').cmp(b.split('/'))
+        }
+        (Some(ValueType::GeoPointValue(a)), Some(ValueType::GeoPointValue(b))) => {
+            compare_doubles(a.latitude, b.latitude)
+                .then_with(|| compare_doubles(a.longitude, b.longitude))
+        }
+        (Some(ValueType::ArrayValue(a)), Some(ValueType::ArrayValue(b))) => {
+            compare_arrays(&a.values, &b.values)
+        }
+        (Some(ValueType::MapValue(a)), Some(ValueType::MapValue(b))) => {
+            match (vector_values(a), vector_values(b)) {
+                (Some(a), Some(b)) => a.len().cmp(&b.len()).then_with(|| compare_arrays(a, b)),
+                _ => compare_maps(a, b),
+            }
+        }
+        _ => Ordering::Equal,
+    }
+}
+
+fn compare_arrays(a: &[Value], b: &[Value]) -> Ordering {
+    for (a, b) in a.iter().zip(b.iter()) {
+        let ordering = compare(a, b);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+fn compare_maps(a: &MapValue, b: &MapValue) -> Ordering {
+    let mut a = a.fields.iter().collect::<Vec<(&String, &Value)>>();
+    let mut b = b.fields.iter().collect::<Vec<(&String, &Value)>>();
+    a.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+    b.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+    for ((ka, va), (kb, vb)) in a.iter().zip(b.iter()) {
+        let ordering = ka
+            .as_bytes()
+            .cmp(kb.as_bytes())
+            .then_with(|| compare(va, vb));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+// NaN is equal to NaN and less than any other number. -0.0 is equal to 0.0.
+fn compare_doubles(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+    }
+}
+
+fn compare_integer_and_double(i: i64, d: f64) -> Ordering {
+    const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+    if d.is_nan() {
+        return Ordering::Greater;
+    }
+    if d < -TWO_POW_63 {
+        return Ordering::Greater;
+    }
+    if d >= TWO_POW_63 {
+        return Ordering::Less;
+    }
+    let t = d.trunc();
+    // `t` is an integer in the range of i64, so the conversion is exact.
+    i.cmp(&(t as i64)).then_with(|| {
+        if d > t {
+            Ordering::Less
+        } else if d < t {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    })
+}
+
+// The hash must be consistent with `compare`: values that compare equal must have the same hash.
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    let type_order = type_order(value);
+    type_order.hash(state);
+    match &value.value_type {
+        Some(ValueType::BooleanValue(b)) => b.hash(state),
+        Some(ValueType::IntegerValue(i)) => hash_number(Number::Integer(*i), state),
+        Some(ValueType::DoubleValue(d)) => hash_number(Number::Double(*d), state),
+        Some(ValueType::TimestampValue(t)) => (t.seconds, t.nanos).hash(state),
+        Some(ValueType::StringValue(s)) => s.hash(state),
+        Some(ValueType::BytesValue(b)) => b[..].hash(state),
+        Some(ValueType::ReferenceValue(r)) => r.hash(state),
+        Some(ValueType::GeoPointValue(g)) => {
+            hash_number(Number::Double(g.latitude), state);
+            hash_number(Number::Double(g.longitude), state);
+        }
+        Some(ValueType::ArrayValue(a)) => {
+            a.values.len().hash(state);
+            for value in &a.values {
+                hash_value(value, state);
+            }
+        }
+        Some(ValueType::MapValue(m)) => match vector_values(m) {
+            Some(values) => {
+                values.len().hash(state);
+                for value in values {
+                    hash_value(value, state);
+                }
+            }
+            None => {
+                let mut fields = m.fields.iter().collect::<Vec<(&String, &Value)>>();
+                fields.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+                fields.len().hash(state);
+                for (key, value) in fields {
+                    key.hash(state);
+                    hash_value(value, state);
+                }
+            }
+        },
+        None
+        | Some(ValueType::NullValue(_))
+        | Some(ValueType::FieldReferenceValue(_))
+        | Some(ValueType::VariableReferenceValue(_))
+        | Some(ValueType::FunctionValue(_))
+        | Some(ValueType::PipelineValue(_)) => {}
+    }
+}
+
+enum Number {
+    Integer(i64),
+    Double(f64),
+}
+
+fn hash_number<H: Hasher>(number: Number, state: &mut H) {
+    const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+    match number {
+        Number::Integer(i) => {
+            0_u8.hash(state);
+            i.hash(state);
+        }
+        Number::Double(d) if d.is_nan() => 1_u8.hash(state),
+        // integral doubles are equal to the integers (and -0.0 is equal to 0.0)
+        Number::Double(d) if d.trunc() == d && (-TWO_POW_63..TWO_POW_63).contains(&d) => {
+            0_u8.hash(state);
+            (d as i64).hash(state);
+        }
+        Number::Double(d) => {
+            2_u8.hash(state);
+            d.to_bits().hash(state);
+        }
+    }
+}
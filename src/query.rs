@@ -2,6 +2,8 @@ use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
     Cursor, StructuredQuery, structured_query,
 };
 
+use crate::{FieldPath, Order, Result};
+
 /// A Firestore query.
 ///
 /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#structuredquery>
@@ -67,13 +69,22 @@ use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
 /// # }
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct Query(StructuredQuery);
+pub struct Query {
+    structured_query: StructuredQuery,
+    parent: Option<String>,
+    limit_to_last: bool,
+}
 
 impl Query {
     /// Creates a new `Query` for a collection.
     ///
     /// The query that internally holds a `CollectionSelector` with `all_descendants` set to `false`.
     ///
+    /// The `collection_id` may be a slash-separated collection path such as `users/u1/posts`.
+    /// In that case, the last segment is used as the collection ID and the rest is kept as the parent document path
+    /// (see [`Query::parent`]), which is used by `QueryRequest::new` and `Query::evaluate`.
+    /// The parent is not checked here; `QueryRequest::new` and [`Query::validate`] reject a parent that is not a document path.
+    ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.CollectionSelector>
     ///
     /// # Examples
@@ -100,6 +111,9 @@ impl Query {
     ///         find_nearest: None,
     ///     }
     /// );
+    /// let query2 = Query::collection("users/u1/posts");
+    /// assert_eq!(query2.parent(), Some("users/u1"));
+    /// assert_eq!(StructuredQuery::from(query2).from[0].collection_id, "posts");
     /// #     Ok(())
     /// # }
     /// ```
@@ -107,10 +121,11 @@ impl Query {
     where
         S: Into<String>,
     {
-        Self(StructuredQuery {
+        let (parent, collection_id) = split_collection_path(collection_id.into());
+        let mut query = Self::new(StructuredQuery {
             select: None,
             from: vec![structured_query::CollectionSelector {
-                collection_id: collection_id.into(),
+                collection_id,
                 all_descendants: false,
             }],
             r#where: None,
@@ -120,7 +135,9 @@ impl Query {
             offset: 0_i32,
             limit: None,
             find_nearest: None,
-        })
+        });
+        query.parent = parent;
+        query
     }
 
     /// Creates a new `Query` for a collection group.
@@ -160,7 +177,7 @@ impl Query {
     where
         S: Into<String>,
     {
-        Self(StructuredQuery {
+        Self::new(StructuredQuery {
             select: None,
             from: vec![structured_query::CollectionSelector {
                 collection_id: collection_id.into(),
@@ -176,6 +193,47 @@ impl Query {
         })
     }
 
+    /// Adds the filter to the where clause with `AND` and returns the Query.
+    ///
+    /// If the query has no filter, the filter is set to the where clause.
+    /// If the current filter is a composite `AND` filter, the filter (or its children, if it is also a composite `AND` filter) is appended to it.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.Filter.google.firestore.v1.StructuredQuery.where>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_and_where() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter, Query};
+    /// let a = FieldPath::raw("a").is_null()?;
+    /// let b = FieldPath::raw("b").is_null()?;
+    /// let c = FieldPath::raw("c").is_null()?;
+    /// let query1 = Query::collection("collection_id1").and_where(a.clone());
+    /// assert_eq!(query1, Query::collection("collection_id1").r#where(a.clone()));
+    /// let query2 = query1.and_where(b.clone()).and_where(c.clone());
+    /// assert_eq!(
+    ///     query2,
+    ///     Query::collection("collection_id1").r#where(Filter::and([a, b, c]))
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn and_where<F>(mut self, filter: F) -> Self
+    where
+        F: Into<structured_query::Filter>,
+    {
+        let filter = filter.into();
+        self.structured_query.r#where = Some(match self.structured_query.r#where.take() {
+            Some(current) => crate::filter::compose(
+                structured_query::composite_filter::Operator::And,
+                current,
+                filter,
+            ),
+            None => filter,
+        });
+        self
+    }
+
     /// Sets the specified value to end_at and returns the Query.
     ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.Cursor.google.firestore.v1.StructuredQuery.end_at>
@@ -231,13 +289,118 @@ impl Query {
     where
         I: IntoIterator<Item = googleapis_tonic_google_firestore_v1::google::firestore::v1::Value>,
     {
-        self.0.end_at = Some(Cursor {
+        self.structured_query.end_at = Some(Cursor {
             values: values.into_iter().collect(),
             before: false,
         });
         self
     }
 
+    /// Sets the cursor values of the document to end_at as `end_at` and returns the Query.
+    ///
+    /// The values are read from the document for each field of the effective order_by (see [`Query::normalize_order_by`]),
+    /// and the document name is used for `__name__`. Set the filter and order_by before calling this method.
+    ///
+    /// Returns an error if the document does not contain a field of the order_by.
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/query-cursors>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_end_at_document() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, Document, StructuredQuery, Value,
+    /// };
+    /// let document = Document {
+    ///     name: "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///     fields: [(
+    ///         "age".to_string(),
+    ///         Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    ///     create_time: None,
+    ///     update_time: None,
+    /// };
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").descending()])
+    ///     .end_at_document(&document)?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).end_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: false,
+    ///     })
+    /// );
+    /// assert!(Query::collection("users")
+    ///     .order_by([FieldPath::raw("name").ascending()])
+    ///     .end_at_document(&document)
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn end_at_document(
+        self,
+        document: &googleapis_tonic_google_firestore_v1::google::firestore::v1::Document,
+    ) -> Result<Self> {
+        let values = self.document_cursor_values(document)?;
+        Ok(self.end_at(values))
+    }
+
+    /// Sets the cursor values of the struct to end_at as `end_at` and returns the Query.
+    ///
+    /// The struct is serialized as the fields of the document named `name`. See [`Query::end_at_document`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_end_at_struct() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, StructuredQuery, Value,
+    /// };
+    /// #[derive(serde::Serialize)]
+    /// struct User {
+    ///     age: i64,
+    /// }
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").ascending()])
+    ///     .end_at_struct(&User { age: 20 }, "projects/p/databases/(default)/documents/users/u1")?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).end_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: false,
+    ///     })
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn end_at_struct<T>(self, value: &T, name: &str) -> Result<Self>
+    where
+        T: serde::Serialize,
+    {
+        let document = struct_to_document(value, name)?;
+        self.end_at_document(&document)
+    }
+
     /// Sets the specified value to end_at and returns the Query.
     ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.Cursor.google.firestore.v1.StructuredQuery.end_at>
@@ -293,13 +456,215 @@ impl Query {
     where
         I: IntoIterator<Item = googleapis_tonic_google_firestore_v1::google::firestore::v1::Value>,
     {
-        self.0.end_at = Some(Cursor {
+        self.structured_query.end_at = Some(Cursor {
             values: values.into_iter().collect(),
             before: true,
         });
         self
     }
 
+    /// Sets the cursor values of the document to end_at as `end_before` and returns the Query.
+    ///
+    /// The values are read from the document for each field of the effective order_by (see [`Query::normalize_order_by`]),
+    /// and the document name is used for `__name__`. Set the filter and order_by before calling this method.
+    ///
+    /// Returns an error if the document does not contain a field of the order_by.
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/query-cursors>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_end_before_document() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, Document, StructuredQuery, Value,
+    /// };
+    /// let document = Document {
+    ///     name: "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///     fields: [(
+    ///         "age".to_string(),
+    ///         Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    ///     create_time: None,
+    ///     update_time: None,
+    /// };
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").descending()])
+    ///     .end_before_document(&document)?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).end_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: true,
+    ///     })
+    /// );
+    /// assert!(Query::collection("users")
+    ///     .order_by([FieldPath::raw("name").ascending()])
+    ///     .end_before_document(&document)
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn end_before_document(
+        self,
+        document: &googleapis_tonic_google_firestore_v1::google::firestore::v1::Document,
+    ) -> Result<Self> {
+        let values = self.document_cursor_values(document)?;
+        Ok(self.end_before(values))
+    }
+
+    /// Sets the cursor values of the struct to end_at as `end_before` and returns the Query.
+    ///
+    /// The struct is serialized as the fields of the document named `name`. See [`Query::end_before_document`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_end_before_struct() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, StructuredQuery, Value,
+    /// };
+    /// #[derive(serde::Serialize)]
+    /// struct User {
+    ///     age: i64,
+    /// }
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").ascending()])
+    ///     .end_before_struct(&User { age: 20 }, "projects/p/databases/(default)/documents/users/u1")?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).end_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: true,
+    ///     })
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn end_before_struct<T>(self, value: &T, name: &str) -> Result<Self>
+    where
+        T: serde::Serialize,
+    {
+        let document = struct_to_document(value, name)?;
+        self.end_before_document(&document)
+    }
+
+    /// Evaluates the query against the documents in memory and returns the matching documents.
+    ///
+    /// See [`evaluate`](crate::evaluate) for the supported semantics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_evaluate() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Document, Value,
+    /// };
+    /// let document = |id: &str, age: i64| Document {
+    ///     name: format!("projects/p/databases/(default)/documents/users/{}", id),
+    ///     fields: [(
+    ///         "age".to_string(),
+    ///         Value {
+    ///             value_type: Some(ValueType::IntegerValue(age)),
+    ///         },
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    ///     create_time: None,
+    ///     update_time: None,
+    /// };
+    /// let documents = vec![document("u1", 20), document("u2", 10), document("u3", 30)];
+    /// let query = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").ascending()])
+    ///     .offset(1)
+    ///     .limit(1);
+    /// assert_eq!(query.evaluate(documents)?, vec![document("u1", 20)]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate<I>(
+        &self,
+        documents: I,
+    ) -> Result<Vec<googleapis_tonic_google_firestore_v1::google::firestore::v1::Document>>
+    where
+        I: IntoIterator<
+            Item = googleapis_tonic_google_firestore_v1::google::firestore::v1::Document,
+        >,
+    {
+        if self.limit_to_last {
+            let mut documents = crate::evaluator::evaluate_in(
+                &reversed(self.structured_query.clone()),
+                self.parent.as_deref(),
+                documents,
+            )?;
+            documents.reverse();
+            Ok(documents)
+        } else {
+            crate::evaluator::evaluate_in(&self.structured_query, self.parent.as_deref(), documents)
+        }
+    }
+
+    /// Sets the specified value to find_nearest and returns the Query.
+    ///
+    /// The `FindNearest` is created by `FieldPath::find_nearest`, which validates the query vector and the limit.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.FindNearest.google.firestore.v1.StructuredQuery.find_nearest>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_find_nearest() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{DistanceMeasure, FieldPath, Query, Vector};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{structured_query, StructuredQuery};
+    /// let find_nearest = FieldPath::raw("embedding")
+    ///     .find_nearest(Vector::new([1.0, 2.0]), DistanceMeasure::DotProduct, 10)?;
+    /// let query1 = Query::collection("collection_id1").find_nearest(find_nearest.clone());
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1),
+    ///     StructuredQuery {
+    ///         select: None,
+    ///         from: vec![structured_query::CollectionSelector {
+    ///             collection_id: "collection_id1".to_string(),
+    ///             all_descendants: false,
+    ///         }],
+    ///         r#where: None,
+    ///         order_by: vec![],
+    ///         start_at: None,
+    ///         end_at: None,
+    ///         offset: 0_i32,
+    ///         limit: None,
+    ///         find_nearest: Some(structured_query::FindNearest::from(find_nearest)),
+    ///     }
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn find_nearest(mut self, find_nearest: crate::FindNearest) -> Self {
+        self.structured_query.find_nearest =
+            Some(structured_query::FindNearest::from(find_nearest));
+        self
+    }
+
     /// Sets the specified value to limit and returns the Query.
     ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.protobuf.Int32Value.google.firestore.v1.StructuredQuery.limit>
@@ -332,10 +697,122 @@ impl Query {
     /// # }
     /// ```
     pub fn limit(mut self, limit: i32) -> Self {
-        self.0.limit = Some(limit);
+        self.structured_query.limit = Some(limit);
+        self.limit_to_last = false;
+        self
+    }
+
+    /// Sets the specified value to limit as `limit_to_last` and returns the Query.
+    ///
+    /// The query returns the last matching documents in the order of the query.
+    /// When the query is converted into a `StructuredQuery`, it is reversed (see [`Query::reversed`]).
+    /// Firestore returns the documents of the reversed query, so the results must be reversed by the caller.
+    /// [`Query::evaluate`] reverses the results.
+    ///
+    /// The conversion cannot fail, so it reverses the query even if the order_by cannot be normalized
+    /// (e.g. an invalid field path). `QueryRequest::new` and [`Query::evaluate`] return the error instead.
+    ///
+    /// Calling [`Query::limit`] clears the `limit_to_last` mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_limit_to_last() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     structured_query, value::ValueType, Cursor, StructuredQuery, Value,
+    /// };
+    /// let query1 = Query::collection("collection_id1")
+    ///     .order_by([FieldPath::raw("field1").ascending()])
+    ///     .end_before([Value { value_type: Some(ValueType::IntegerValue(1)) }])
+    ///     .limit_to_last(2);
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1),
+    ///     StructuredQuery {
+    ///         select: None,
+    ///         from: vec![structured_query::CollectionSelector {
+    ///             collection_id: "collection_id1".to_string(),
+    ///             all_descendants: false,
+    ///         }],
+    ///         r#where: None,
+    ///         order_by: vec![
+    ///             structured_query::Order::from(FieldPath::raw("field1").descending()),
+    ///             structured_query::Order::from(FieldPath::raw("__name__").descending()),
+    ///         ],
+    ///         start_at: Some(Cursor {
+    ///             values: vec![Value { value_type: Some(ValueType::IntegerValue(1)) }],
+    ///             before: false,
+    ///         }),
+    ///         end_at: None,
+    ///         offset: 0_i32,
+    ///         limit: Some(2_i32),
+    ///         find_nearest: None,
+    ///     }
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn limit_to_last(mut self, limit: i32) -> Self {
+        self.structured_query.limit = Some(limit);
+        self.limit_to_last = true;
         self
     }
 
+    /// Replaces order_by with the effective order_by list and returns the Query.
+    ///
+    /// The effective order_by list is the one that the Firestore client SDKs send:
+    ///
+    /// 1. The explicit orders.
+    /// 2. The fields of inequality filters (`<`, `<=`, `>`, `>=`, `!=`, `not_in`, `is_not_nan` and `is_not_null`)
+    ///    that are not already ordered, in field path order.
+    /// 3. `__name__`, if it is not already ordered.
+    ///
+    /// The implicit orders use the direction of the last explicit order, or ascending if there is none.
+    ///
+    /// Returns an error if a field path of an order or a filter is invalid.
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/order-limit-data>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_normalize_order_by() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     structured_query, value::ValueType, StructuredQuery, Value,
+    /// };
+    /// let int = |i: i64| Value { value_type: Some(ValueType::IntegerValue(i)) };
+    /// let query1 = Query::collection("collection_id1")
+    ///     .r#where(Filter::and([
+    ///         FieldPath::raw("c").greater_than(int(1))?,
+    ///         FieldPath::raw("b").not_equal(int(2))?,
+    ///         FieldPath::raw("a").less_than(int(3))?,
+    ///         FieldPath::raw("d").equal(int(4))?,
+    ///     ]))
+    ///     .order_by([FieldPath::raw("b").descending()])
+    ///     .normalize_order_by()?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).order_by,
+    ///     vec![
+    ///         structured_query::Order::from(FieldPath::raw("b").descending()),
+    ///         structured_query::Order::from(FieldPath::raw("a").descending()),
+    ///         structured_query::Order::from(FieldPath::raw("c").descending()),
+    ///         structured_query::Order::from(FieldPath::raw("__name__").descending()),
+    ///     ]
+    /// );
+    /// let query2 = Query::collection("collection_id1").normalize_order_by()?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query2).order_by,
+    ///     vec![structured_query::Order::from(FieldPath::raw("__name__").ascending())]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn normalize_order_by(mut self) -> Result<Self> {
+        self.structured_query.order_by = normalized_order_by(&self.structured_query)?;
+        Ok(self)
+    }
+
     /// Sets the specified value to offset and returns the Query.
     ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.int32.google.firestore.v1.StructuredQuery.offset>
@@ -368,7 +845,49 @@ impl Query {
     /// # }
     /// ```
     pub fn offset(mut self, offset: i32) -> Self {
-        self.0.offset = offset;
+        self.structured_query.offset = offset;
+        self
+    }
+
+    /// Adds the filter to the where clause with `OR` and returns the Query.
+    ///
+    /// If the query has no filter, the filter is set to the where clause.
+    /// If the current filter is a composite `OR` filter, the filter (or its children, if it is also a composite `OR` filter) is appended to it.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.Filter.google.firestore.v1.StructuredQuery.where>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_or_where() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter, Query};
+    /// let a = FieldPath::raw("a").is_null()?;
+    /// let b = FieldPath::raw("b").is_null()?;
+    /// let c = FieldPath::raw("c").is_null()?;
+    /// let query1 = Query::collection("collection_id1")
+    ///     .r#where(a.clone())
+    ///     .and_where(b.clone())
+    ///     .or_where(c.clone());
+    /// assert_eq!(
+    ///     query1,
+    ///     Query::collection("collection_id1").r#where(Filter::or([Filter::and([a, b]), c]))
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn or_where<F>(mut self, filter: F) -> Self
+    where
+        F: Into<structured_query::Filter>,
+    {
+        let filter = filter.into();
+        self.structured_query.r#where = Some(match self.structured_query.r#where.take() {
+            Some(current) => crate::filter::compose(
+                structured_query::composite_filter::Operator::Or,
+                current,
+                filter,
+            ),
+            None => filter,
+        });
         self
     }
 
@@ -446,10 +965,48 @@ impl Query {
         I: IntoIterator,
         I::Item: Into<structured_query::Order>,
     {
-        self.0.order_by = order_by.into_iter().map(Into::into).collect();
+        self.structured_query.order_by = order_by.into_iter().map(Into::into).collect();
         self
     }
 
+    /// Returns the Query that returns the documents in the reverse order.
+    ///
+    /// The order_by is replaced with the effective order_by list (see [`Query::normalize_order_by`]) with every direction flipped,
+    /// and the `start_at` and `end_at` cursors are swapped and their inclusiveness is inverted.
+    ///
+    /// Returns an error if a field path of an order or a filter is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_reversed() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, StructuredQuery, Value,
+    /// };
+    /// let int = |i: i64| Value { value_type: Some(ValueType::IntegerValue(i)) };
+    /// let query1 = Query::collection("collection_id1")
+    ///     .order_by([FieldPath::raw("field1").descending()])
+    ///     .start_at([int(1)])
+    ///     .end_before([int(2)]);
+    /// let query2 = Query::collection("collection_id1")
+    ///     .order_by([
+    ///         FieldPath::raw("field1").ascending(),
+    ///         FieldPath::raw("__name__").ascending(),
+    ///     ])
+    ///     .start_after([int(2)])
+    ///     .end_at([int(1)]);
+    /// assert_eq!(query1.clone().reversed()?, query2);
+    /// assert_eq!(query1.clone().reversed()?.reversed()?, query1.normalize_order_by()?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn reversed(mut self) -> Result<Self> {
+        normalized_order_by(&self.structured_query)?;
+        self.structured_query = reversed(self.structured_query);
+        Ok(self)
+    }
+
     /// Sets the specified value to select and returns the Query.
     ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.Projection.google.firestore.v1.StructuredQuery.select>
@@ -496,7 +1053,7 @@ impl Query {
         I: IntoIterator,
         I::Item: Into<structured_query::FieldReference>,
     {
-        self.0.select = Some(structured_query::Projection {
+        self.structured_query.select = Some(structured_query::Projection {
             fields: fields.into_iter().map(Into::into).collect(),
         });
         self
@@ -556,39 +1113,144 @@ impl Query {
     where
         I: IntoIterator<Item = googleapis_tonic_google_firestore_v1::google::firestore::v1::Value>,
     {
-        self.0.start_at = Some(Cursor {
+        self.structured_query.start_at = Some(Cursor {
             values: values.into_iter().collect(),
             before: false,
         });
         self
     }
 
-    /// Sets the specified value to start_at and returns the Query.
+    /// Sets the cursor values of the document to start_at as `start_after` and returns the Query.
     ///
-    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.Cursor.google.firestore.v1.StructuredQuery.start_at>
+    /// The values are read from the document for each field of the effective order_by (see [`Query::normalize_order_by`]),
+    /// and the document name is used for `__name__`. Set the filter and order_by before calling this method.
+    ///
+    /// Returns an error if the document does not contain a field of the order_by.
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/query-cursors>
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # fn test_query_start_at() -> firestore_structured_query::Result<()> {
-    /// use firestore_structured_query::Query;
+    /// # fn test_query_start_after_document() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
     /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
-    ///     structured_query, value::ValueType, Cursor, StructuredQuery, Value,
+    ///     value::ValueType, Cursor, Document, StructuredQuery, Value,
     /// };
-    /// let query1 = Query::collection_group("collection_id1").start_at([
-    ///     Value {
-    ///         value_type: Some(ValueType::IntegerValue(1)),
-    ///     },
-    ///     Value {
-    ///         value_type: Some(ValueType::IntegerValue(2)),
-    ///     },
-    /// ]);
+    /// let document = Document {
+    ///     name: "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///     fields: [(
+    ///         "age".to_string(),
+    ///         Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    ///     create_time: None,
+    ///     update_time: None,
+    /// };
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").descending()])
+    ///     .start_after_document(&document)?;
     /// assert_eq!(
-    ///     StructuredQuery::from(query1),
-    ///     StructuredQuery {
-    ///         select: None,
-    ///         from: vec![structured_query::CollectionSelector {
-    ///             collection_id: "collection_id1".to_string(),
+    ///     StructuredQuery::from(query1).start_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: false,
+    ///     })
+    /// );
+    /// assert!(Query::collection("users")
+    ///     .order_by([FieldPath::raw("name").ascending()])
+    ///     .start_after_document(&document)
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn start_after_document(
+        self,
+        document: &googleapis_tonic_google_firestore_v1::google::firestore::v1::Document,
+    ) -> Result<Self> {
+        let values = self.document_cursor_values(document)?;
+        Ok(self.start_after(values))
+    }
+
+    /// Sets the cursor values of the struct to start_at as `start_after` and returns the Query.
+    ///
+    /// The struct is serialized as the fields of the document named `name`. See [`Query::start_after_document`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_start_after_struct() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, StructuredQuery, Value,
+    /// };
+    /// #[derive(serde::Serialize)]
+    /// struct User {
+    ///     age: i64,
+    /// }
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").ascending()])
+    ///     .start_after_struct(&User { age: 20 }, "projects/p/databases/(default)/documents/users/u1")?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).start_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: false,
+    ///     })
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn start_after_struct<T>(self, value: &T, name: &str) -> Result<Self>
+    where
+        T: serde::Serialize,
+    {
+        let document = struct_to_document(value, name)?;
+        self.start_after_document(&document)
+    }
+
+    /// Sets the specified value to start_at and returns the Query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.Cursor.google.firestore.v1.StructuredQuery.start_at>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_start_at() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     structured_query, value::ValueType, Cursor, StructuredQuery, Value,
+    /// };
+    /// let query1 = Query::collection_group("collection_id1").start_at([
+    ///     Value {
+    ///         value_type: Some(ValueType::IntegerValue(1)),
+    ///     },
+    ///     Value {
+    ///         value_type: Some(ValueType::IntegerValue(2)),
+    ///     },
+    /// ]);
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1),
+    ///     StructuredQuery {
+    ///         select: None,
+    ///         from: vec![structured_query::CollectionSelector {
+    ///             collection_id: "collection_id1".to_string(),
     ///             all_descendants: true,
     ///         }],
     ///         r#where: None,
@@ -617,13 +1279,240 @@ impl Query {
     where
         I: IntoIterator<Item = googleapis_tonic_google_firestore_v1::google::firestore::v1::Value>,
     {
-        self.0.start_at = Some(Cursor {
+        self.structured_query.start_at = Some(Cursor {
             values: values.into_iter().collect(),
             before: true,
         });
         self
     }
 
+    /// Sets the cursor values of the document to start_at as `start_at` and returns the Query.
+    ///
+    /// The values are read from the document for each field of the effective order_by (see [`Query::normalize_order_by`]),
+    /// and the document name is used for `__name__`. Set the filter and order_by before calling this method.
+    ///
+    /// Returns an error if the document does not contain a field of the order_by.
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/query-cursors>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_start_at_document() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, Document, StructuredQuery, Value,
+    /// };
+    /// let document = Document {
+    ///     name: "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///     fields: [(
+    ///         "age".to_string(),
+    ///         Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    ///     create_time: None,
+    ///     update_time: None,
+    /// };
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").descending()])
+    ///     .start_at_document(&document)?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).start_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: true,
+    ///     })
+    /// );
+    /// assert!(Query::collection("users")
+    ///     .order_by([FieldPath::raw("name").ascending()])
+    ///     .start_at_document(&document)
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn start_at_document(
+        self,
+        document: &googleapis_tonic_google_firestore_v1::google::firestore::v1::Document,
+    ) -> Result<Self> {
+        let values = self.document_cursor_values(document)?;
+        Ok(self.start_at(values))
+    }
+
+    /// Sets the cursor values of the struct to start_at as `start_at` and returns the Query.
+    ///
+    /// The struct is serialized as the fields of the document named `name`. See [`Query::start_at_document`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_start_at_struct() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, StructuredQuery, Value,
+    /// };
+    /// #[derive(serde::Serialize)]
+    /// struct User {
+    ///     age: i64,
+    /// }
+    /// let query1 = Query::collection("users")
+    ///     .order_by([FieldPath::raw("age").ascending()])
+    ///     .start_at_struct(&User { age: 20 }, "projects/p/databases/(default)/documents/users/u1")?;
+    /// assert_eq!(
+    ///     StructuredQuery::from(query1).start_at,
+    ///     Some(Cursor {
+    ///         values: vec![
+    ///             Value { value_type: Some(ValueType::IntegerValue(20)) },
+    ///             Value {
+    ///                 value_type: Some(ValueType::ReferenceValue(
+    ///                     "projects/p/databases/(default)/documents/users/u1".to_string(),
+    ///                 )),
+    ///             },
+    ///         ],
+    ///         before: true,
+    ///     })
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn start_at_struct<T>(self, value: &T, name: &str) -> Result<Self>
+    where
+        T: serde::Serialize,
+    {
+        let document = struct_to_document(value, name)?;
+        self.start_at_document(&document)
+    }
+
+    /// Appends the specified orders to order_by and returns the Query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.repeated.google.firestore.v1.StructuredQuery.Order.google.firestore.v1.StructuredQuery.order_by>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_then_order_by() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// let query1 = Query::collection("collection_id1")
+    ///     .order_by([FieldPath::raw("field1").ascending()])
+    ///     .then_order_by([FieldPath::raw("field2").descending()]);
+    /// assert_eq!(
+    ///     query1,
+    ///     Query::collection("collection_id1").order_by([
+    ///         FieldPath::raw("field1").ascending(),
+    ///         FieldPath::raw("field2").descending(),
+    ///     ])
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn then_order_by<I>(mut self, order_by: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<structured_query::Order>,
+    {
+        self.structured_query
+            .order_by
+            .extend(order_by.into_iter().map(Into::into));
+        self
+    }
+
+    /// Validates the query against the Firestore query constraints.
+    ///
+    /// Returns an error that describes all violations if the query is invalid. See [`Query::violations`] for the checks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_validate() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// assert!(Query::collection("collection_id1").limit(1).validate().is_ok());
+    /// let err = Query::collection("__collection_id1__").limit(-1).validate().unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "invalid query: from[0].collection_id: collection ID must not match __.*__: __collection_id1__; limit: limit must not be negative (actual: -1)"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let violations = self.violations();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(crate::Error::from_violations(violations))
+    }
+
+    /// Returns all violations of the Firestore query constraints.
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/queries#limitations>
+    ///
+    /// The following constraints are checked:
+    ///
+    /// - Collection IDs must be valid.
+    /// - Field paths must be valid, and `select` must not contain duplicate field paths.
+    /// - Composite filters must not be empty.
+    /// - `in` and `array_contains_any` require a non-empty array of up to 30 values, and `not_in` requires a non-empty array of up to 10 values.
+    /// - A disjunction must not contain more than one `array_contains` or `array_contains_any`.
+    /// - `not_in` must not be combined with `not_equal` or another `not_in`.
+    /// - The filter must not have more than 30 disjunctions in disjunctive normal form.
+    /// - Cursors must not have more values than the (implicit) order_by fields.
+    /// - `offset` and `limit` must not be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_violations() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Filter, Query};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, ArrayValue, Value,
+    /// };
+    /// let int = |i: i64| Value { value_type: Some(ValueType::IntegerValue(i)) };
+    /// let array = |values: Vec<Value>| Value {
+    ///     value_type: Some(ValueType::ArrayValue(ArrayValue { values })),
+    /// };
+    /// let query = Query::collection("collection_id1")
+    ///     .select([FieldPath::raw("field1"), FieldPath::raw("`field1`")])
+    ///     .r#where(Filter::and([
+    ///         FieldPath::raw("field2").not_in(array(vec![int(1), int(2)]))?,
+    ///         FieldPath::raw("field3").not_equal(int(3))?,
+    ///         Filter::or([]),
+    ///     ]))
+    ///     .start_at([int(1), int(2), int(3), int(4)])
+    ///     .offset(-1);
+    /// let violations = query
+    ///     .violations()
+    ///     .into_iter()
+    ///     .map(|violation| violation.location().to_string())
+    ///     .collect::<Vec<String>>();
+    /// assert_eq!(
+    ///     violations,
+    ///     [
+    ///         "select.fields[1]",
+    ///         "where.filters[2]",
+    ///         "where.filters[1]",
+    ///         "start_at",
+    ///         "offset"
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn violations(&self) -> Vec<crate::Violation> {
+        let mut violations = crate::validation::violations(&self.structured_query);
+        if let Some(parent) = &self.parent {
+            violations.extend(crate::validation::parent_violations(parent));
+        }
+        violations
+    }
+
     /// Sets the specified value to where and returns the Query.
     ///
     /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.Filter.google.firestore.v1.StructuredQuery.where>
@@ -701,13 +1590,785 @@ impl Query {
     where
         F: Into<structured_query::Filter>,
     {
-        self.0.r#where = Some(filter.into());
+        self.structured_query.r#where = Some(filter.into());
         self
     }
 }
 
+impl Query {
+    /// Returns `true` if the query selects all descendant collections (`Query::collection_group`).
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.CollectionSelector>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_all_descendants() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// assert!(!Query::collection("collection_id1").all_descendants());
+    /// assert!(Query::collection_group("collection_id1").all_descendants());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn all_descendants(&self) -> bool {
+        self.structured_query
+            .from
+            .first()
+            .is_some_and(|collection_selector| collection_selector.all_descendants)
+    }
+
+    /// Returns the collection ID of the query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.CollectionSelector>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_collection_id() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// assert_eq!(Query::collection("collection_id1").collection_id(), "collection_id1");
+    /// assert_eq!(Query::collection_group("collection_id2").collection_id(), "collection_id2");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_id(&self) -> &str {
+        self.structured_query
+            .from
+            .first()
+            .map(|collection_selector| collection_selector.collection_id.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Returns the composite indexes required by the query.
+    ///
+    /// Each disjunction of an `OR` filter requires its own index.
+    /// An empty list means the automatic single-field indexes serve the query.
+    /// Use [`indexes_json`](crate::indexes_json) to render the indexes of several queries as a `firestore.indexes.json` document.
+    ///
+    /// Returns an error if the query has an invalid field path or order,
+    /// or more than 30 disjunctions in disjunctive normal form (see [`Filter::disjunction_count`](crate::Filter::disjunction_count)).
+    ///
+    /// <https://firebase.google.com/docs/firestore/query-data/index-overview#composite_indexes>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_composite_indexes() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{Direction, FieldPath, Filter, IndexField, Query};
+    /// let query1 = Query::collection("users")
+    ///     .r#where(Filter::or([
+    ///         FieldPath::raw("a").equal(1)?,
+    ///         FieldPath::raw("b").array_contains(2)?,
+    ///     ]))
+    ///     .order_by([FieldPath::raw("c").descending()]);
+    /// let indexes = query1.composite_indexes()?;
+    /// assert_eq!(indexes.len(), 2);
+    /// assert_eq!(
+    ///     indexes[1].fields(),
+    ///     &[
+    ///         IndexField::ArrayContains { field_path: FieldPath::raw("b") },
+    ///         IndexField::Order { field_path: FieldPath::raw("c"), direction: Direction::Descending },
+    ///     ]
+    /// );
+    /// let query2 = Query::collection("users").r#where(Filter::and([
+    ///     FieldPath::raw("a").equal(1)?,
+    ///     FieldPath::raw("b").equal(2)?,
+    /// ]));
+    /// assert!(query2.composite_indexes()?.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn composite_indexes(&self) -> Result<Vec<crate::Index>> {
+        crate::index::composite_indexes(&self.structured_query)
+    }
+
+    /// Returns the end_at cursor of the query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.Cursor.google.firestore.v1.StructuredQuery.end_at>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_end_cursor() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+    /// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+    /// let query1 = Query::collection("collection_id1").end_before([one.clone()]);
+    /// let cursor = query1.end_cursor().unwrap();
+    /// assert_eq!(cursor.values(), &[one]);
+    /// assert!(cursor.before());
+    /// assert!(Query::collection("collection_id1").end_cursor().is_none());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn end_cursor(&self) -> Option<crate::Cursor> {
+        self.structured_query
+            .end_at
+            .clone()
+            .map(crate::Cursor::from)
+    }
+
+    /// Returns the filter of the query.
+    ///
+    /// Returns an error if the filter set by `Query::where` is not a valid `Filter`.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.Filter.google.firestore.v1.StructuredQuery.where>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_filter() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// let filter1 = FieldPath::raw("field1").is_null()?;
+    /// let query1 = Query::collection("collection_id1").r#where(filter1.clone());
+    /// assert_eq!(query1.filter()?, Some(filter1));
+    /// assert_eq!(Query::collection("collection_id1").filter()?, None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn filter(&self) -> Result<Option<crate::Filter>> {
+        self.structured_query
+            .r#where
+            .clone()
+            .map(crate::Filter::try_from)
+            .transpose()
+    }
+
+    /// Returns `true` if the query was created by `Query::limit_to_last`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_is_limit_to_last() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// let query1 = Query::collection("collection_id1")
+    ///     .order_by([FieldPath::raw("field1").ascending()])
+    ///     .limit_to_last(3);
+    /// assert!(query1.is_limit_to_last());
+    /// assert!(!query1.limit(3).is_limit_to_last());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_limit_to_last(&self) -> bool {
+        self.limit_to_last
+    }
+
+    /// Returns the limit of the query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.protobuf.Int32Value.google.firestore.v1.StructuredQuery.limit>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_limit_value() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// assert_eq!(Query::collection("collection_id1").limit_value(), None);
+    /// assert_eq!(Query::collection("collection_id1").limit(3).limit_value(), Some(3));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn limit_value(&self) -> Option<i32> {
+        self.structured_query.limit
+    }
+
+    /// Returns the offset of the query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.int32.google.firestore.v1.StructuredQuery.offset>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_offset_value() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// assert_eq!(Query::collection("collection_id1").offset_value(), 0);
+    /// assert_eq!(Query::collection("collection_id1").offset(3).offset_value(), 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn offset_value(&self) -> i32 {
+        self.structured_query.offset
+    }
+
+    /// Returns the explicit orders of the query.
+    ///
+    /// Returns an error if an order set by `Query::order_by` is not a valid `Order`.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.Order.google.firestore.v1.StructuredQuery.order_by>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_orders() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{Direction, FieldPath, Query};
+    /// let query1 = Query::collection("collection_id1").order_by([
+    ///     FieldPath::raw("field1").ascending(),
+    ///     FieldPath::raw("field2").descending(),
+    /// ]);
+    /// let orders = query1.orders()?;
+    /// assert_eq!(orders.len(), 2);
+    /// assert_eq!(orders[0].field_path(), FieldPath::raw("field1"));
+    /// assert_eq!(orders[0].direction(), Direction::Ascending);
+    /// assert_eq!(orders[1].field_path(), FieldPath::raw("field2"));
+    /// assert_eq!(orders[1].direction(), Direction::Descending);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn orders(&self) -> Result<Vec<Order>> {
+        self.structured_query
+            .order_by
+            .iter()
+            .cloned()
+            .map(Order::try_from)
+            .collect()
+    }
+
+    /// Returns the parent document path of the query (such as `users/u1`), or `None` if the query is on the root of the documents.
+    ///
+    /// The parent is set by `Query::collection` with a slash-separated collection path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_parent() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// assert_eq!(Query::collection("users").parent(), None);
+    /// let query1 = Query::collection("users/u1/posts");
+    /// assert_eq!(query1.parent(), Some("users/u1"));
+    /// assert_eq!(query1.collection_id(), "posts");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// Returns the projection (the selected fields) of the query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.StructuredQuery.Projection.google.firestore.v1.StructuredQuery.select>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_projection() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{FieldPath, Query};
+    /// assert_eq!(Query::collection("collection_id1").projection(), None);
+    /// assert_eq!(
+    ///     Query::collection("collection_id1")
+    ///         .select([FieldPath::raw("field1"), FieldPath::raw("field2")])
+    ///         .projection(),
+    ///     Some(vec![FieldPath::raw("field1"), FieldPath::raw("field2")])
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn projection(&self) -> Option<Vec<FieldPath>> {
+        self.structured_query.select.as_ref().map(|projection| {
+            projection
+                .fields
+                .iter()
+                .map(|field_reference| FieldPath::raw(field_reference.field_path.clone()))
+                .collect()
+        })
+    }
+
+    /// Returns the start_at cursor of the query.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.StructuredQuery.FIELDS.google.firestore.v1.Cursor.google.firestore.v1.StructuredQuery.start_at>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_start_cursor() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::Query;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+    /// let one = Value { value_type: Some(ValueType::IntegerValue(1)) };
+    /// let query1 = Query::collection("collection_id1").start_at([one.clone()]);
+    /// let cursor = query1.start_cursor().unwrap();
+    /// assert_eq!(cursor.values(), &[one]);
+    /// assert!(cursor.before());
+    /// assert!(Query::collection("collection_id1").start_cursor().is_none());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn start_cursor(&self) -> Option<crate::Cursor> {
+        self.structured_query
+            .start_at
+            .clone()
+            .map(crate::Cursor::from)
+    }
+}
+
+impl Query {
+    fn new(structured_query: StructuredQuery) -> Self {
+        Self {
+            structured_query,
+            parent: None,
+            limit_to_last: false,
+        }
+    }
+
+    fn document_cursor_values(
+        &self,
+        document: &googleapis_tonic_google_firestore_v1::google::firestore::v1::Document,
+    ) -> Result<Vec<googleapis_tonic_google_firestore_v1::google::firestore::v1::Value>> {
+        normalized_order_by(&self.structured_query)?
+            .iter()
+            .map(|order| {
+                let field_path = order
+                    .field
+                    .as_ref()
+                    .map(|field_reference| field_reference.field_path.as_str())
+                    .unwrap_or_default();
+                crate::evaluator::get_value(document, &FieldPath::raw(field_path).to_segments()?)
+                    .ok_or_else(|| {
+                        crate::Error::with_kind(
+                            crate::ErrorKind::InvalidCursor,
+                            format!(
+                                "document {} does not contain the field {}",
+                                document.name, field_path
+                            ),
+                        )
+                        .with_field_path(FieldPath::raw(field_path))
+                    })
+            })
+            .collect()
+    }
+}
+
 impl std::convert::From<Query> for StructuredQuery {
     fn from(query: Query) -> Self {
-        query.0
+        if query.limit_to_last {
+            reversed(query.structured_query)
+        } else {
+            query.structured_query
+        }
+    }
+}
+
+/// Converts a `StructuredQuery` into a `Query`.
+///
+/// Returns an error if the query does not have exactly one collection selector,
+/// or if the projection, the filter, the orders or the find_nearest are malformed
+/// (missing fields, invalid field paths, or unspecified or unknown operators, directions and distance measures).
+///
+/// The limit of the converted query is a normal limit (not `limit_to_last`).
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_query_try_from() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Query};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     structured_query, value::ValueType, StructuredQuery, Value,
+/// };
+/// let query1 = Query::collection("collection_id1")
+///     .r#where(FieldPath::raw("field1").equal(Value { value_type: Some(ValueType::IntegerValue(1)) })?)
+///     .order_by([FieldPath::raw("field2").descending()])
+///     .limit(10);
+/// let structured_query1 = StructuredQuery::from(query1.clone());
+/// assert_eq!(Query::try_from(structured_query1.clone())?, query1);
+///
+/// // load, modify and re-emit
+/// let query2 = Query::try_from(structured_query1)?.limit(20);
+/// assert_eq!(StructuredQuery::from(query2).limit, Some(20));
+///
+/// let mut structured_query2 = StructuredQuery::from(Query::collection("collection_id1"));
+/// structured_query2.order_by.push(structured_query::Order {
+///     field: None,
+///     direction: structured_query::Direction::Ascending as i32,
+/// });
+/// assert!(Query::try_from(structured_query2).is_err());
+/// #     Ok(())
+/// # }
+/// ```
+impl std::convert::TryFrom<StructuredQuery> for Query {
+    type Error = crate::Error;
+
+    fn try_from(structured_query: StructuredQuery) -> Result<Self> {
+        if structured_query.from.len() != 1 {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "from",
+                format!(
+                    "the number of collection selectors must be 1 (actual: {})",
+                    structured_query.from.len()
+                ),
+            )]));
+        }
+        if let Some(projection) = &structured_query.select {
+            for (index, field_reference) in projection.fields.iter().enumerate() {
+                FieldPath::try_from(field_reference.clone())
+                    .map_err(|e| e.at(format!("select.fields[{}]", index)))?;
+            }
+        }
+        if let Some(filter) = &structured_query.r#where {
+            crate::Filter::try_from(filter.clone()).map_err(|e| e.at("where"))?;
+        }
+        for (index, order) in structured_query.order_by.iter().enumerate() {
+            Order::try_from(order.clone()).map_err(|e| e.at(format!("order_by[{}]", index)))?;
+        }
+        if let Some(find_nearest) = &structured_query.find_nearest {
+            use structured_query::find_nearest::DistanceMeasure;
+            let vector_field = find_nearest.vector_field.clone().ok_or_else(|| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::InvalidFieldPath,
+                    "find_nearest vector_field is required",
+                )
+                .at("find_nearest.vector_field")
+            })?;
+            let vector_field =
+                FieldPath::try_from(vector_field).map_err(|e| e.at("find_nearest.vector_field"))?;
+            if find_nearest.query_vector.is_none() {
+                return Err(crate::Error::with_kind(
+                    crate::ErrorKind::ValueConversion,
+                    "find_nearest query_vector is required",
+                )
+                .with_field_path(vector_field)
+                .at("find_nearest.query_vector"));
+            }
+            match DistanceMeasure::try_from(find_nearest.distance_measure) {
+                Ok(DistanceMeasure::Unspecified) | Err(_) => {
+                    return Err(crate::Error::with_kind(
+                        crate::ErrorKind::UnsupportedOperator,
+                        format!(
+                            "invalid find_nearest distance_measure: {}",
+                            find_nearest.distance_measure
+                        ),
+                    )
+                    .with_field_path(vector_field)
+                    .with_operator(crate::filter::operator_name(
+                        find_nearest.distance_measure,
+                        DistanceMeasure::try_from(find_nearest.distance_measure)
+                            .ok()
+                            .map(|distance_measure| distance_measure.as_str_name()),
+                    ))
+                    .at("find_nearest.distance_measure"));
+                }
+                Ok(_) => {}
+            }
+            if !find_nearest.distance_result_field.is_empty() {
+                FieldPath::try_from(structured_query::FieldReference {
+                    field_path: find_nearest.distance_result_field.clone(),
+                })
+                .map_err(|e| e.at("find_nearest.distance_result_field"))?;
+            }
+        }
+        Ok(Self::new(structured_query))
+    }
+}
+
+/// Formats the query in the text format (see [`Query::from_str`](#impl-FromStr-for-Query)).
+///
+/// Field paths and collection IDs that are not simple names or that are keywords are quoted with backticks,
+/// and map fields are sorted by their keys.
+/// The collection of a query with a parent (see [`Query::parent`]) is written as a quoted collection path (e.g. `` FROM `users/u1/posts` ``).
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_query_display() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Filter, Query};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+/// let int = |i: i64| Value { value_type: Some(ValueType::IntegerValue(i)) };
+/// let query1 = Query::collection_group("users")
+///     .select([FieldPath::raw("name"), FieldPath::new(["a", "x&y"])])
+///     .r#where(Filter::and([
+///         FieldPath::raw("age").greater_than_or_equal(int(18))?,
+///         Filter::or([
+///             FieldPath::raw("x").equal(int(1))?,
+///             FieldPath::raw("y").is_null()?,
+///         ]),
+///     ]))
+///     .order_by([FieldPath::raw("age").descending()])
+///     .start_after([int(20)])
+///     .limit(10);
+/// assert_eq!(
+///     query1.to_string(),
+///     "SELECT name, a.`x&y` FROM COLLECTION GROUP users WHERE age >= 18 AND (x = 1 OR y IS NULL) ORDER BY age DESC START AFTER (20) LIMIT 10"
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::fmt::Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::gql::write_query(
+            f,
+            &self.structured_query,
+            self.parent.as_deref(),
+            self.limit_to_last,
+        )
+    }
+}
+
+/// Parses the query from a GQL-like text format.
+///
+/// ```text
+/// SELECT { * | field_path [, ...] }
+/// FROM [COLLECTION GROUP] collection_id [, ...]
+/// [WHERE filter]
+/// [FIND NEAREST field_path value [USING { EUCLIDEAN | COSINE | DOT_PRODUCT }] [LIMIT n]
+///     [DISTANCE RESULT FIELD field_path] [DISTANCE THRESHOLD number]]
+/// [ORDER BY field_path [ASC | DESC] [, ...]]
+/// [START { AT | AFTER } (value [, ...])]
+/// [END { AT | BEFORE } (value [, ...])]
+/// [LIMIT [TO LAST] n]
+/// [OFFSET n]
+/// ```
+///
+/// - Keywords are case-insensitive.
+/// - An order without `ASC` or `DESC` has an unspecified direction, which Firestore orders ascending.
+/// - A field path is a dot-separated list of simple names or backtick-quoted names (`` a.`x&y` ``).
+///   A collection ID is a name that may contain `-`, or a backtick-quoted name.
+/// - A filter is a field filter (`field_path op value`, where op is `<`, `<=`, `>`, `>=`, `=`, `!=`,
+///   `ARRAY_CONTAINS`, `IN`, `ARRAY_CONTAINS_ANY` or `NOT IN`), a unary filter (`field_path IS [NOT] { NULL | NAN }`),
+///   filters combined with `AND` / `OR` (`AND` binds tighter than `OR`) and parentheses,
+///   or `AND(filter, ...)` / `OR(filter, ...)` for composite filters with less than two filters.
+/// - A value is `NULL`, `TRUE`, `FALSE`, an integer (`-1`), a double (`1.5`, `1e10`, `NaN`, `Infinity`, `-Infinity`),
+///   a string (`"a\"b"`), `TIMESTAMP("2006-01-02T15:04:05.999999999Z")` (RFC 3339), `BYTES("base64")`,
+///   `REF("projects/p/databases/d/documents/c/d")`, `GEOPOINT(lat, lng)`, an array (`[1, 2]`),
+///   a map (`{"key": value}`) or `VECTOR([1.0, 2.0])`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_query_from_str() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, Filter, Query};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     value::ValueType, ArrayValue, Value,
+/// };
+/// let int = |i: i64| Value { value_type: Some(ValueType::IntegerValue(i)) };
+/// let query1 = "SELECT a, b FROM users WHERE age >= 18 AND (x = 1 OR y IN [1,2]) ORDER BY age DESC LIMIT 10 OFFSET 5"
+///     .parse::<Query>()?;
+/// assert_eq!(
+///     query1,
+///     Query::collection("users")
+///         .select([FieldPath::raw("a"), FieldPath::raw("b")])
+///         .r#where(Filter::and([
+///             FieldPath::raw("age").greater_than_or_equal(int(18))?,
+///             Filter::or([
+///                 FieldPath::raw("x").equal(int(1))?,
+///                 FieldPath::raw("y").r#in(Value {
+///                     value_type: Some(ValueType::ArrayValue(ArrayValue {
+///                         values: vec![int(1), int(2)],
+///                     })),
+///                 })?,
+///             ]),
+///         ]))
+///         .order_by([FieldPath::raw("age").descending()])
+///         .limit(10)
+///         .offset(5)
+/// );
+/// assert!("SELECT * FROM users WHERE".parse::<Query>().is_err());
+/// #     Ok(())
+/// # }
+/// ```
+impl std::str::FromStr for Query {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (mut structured_query, limit_to_last) = crate::gql::parse_query(s)?;
+        let mut parent = None;
+        if let [collection_selector] = structured_query.from.as_mut_slice()
+            && !collection_selector.all_descendants
+        {
+            let (collection_parent, collection_id) =
+                split_collection_path(std::mem::take(&mut collection_selector.collection_id));
+            collection_selector.collection_id = collection_id;
+            parent = collection_parent;
+        }
+        Ok(Self {
+            structured_query,
+            parent,
+            limit_to_last,
+        })
+    }
+}
+
+/// Splits the slash-separated collection path (such as `users/u1/posts`) into the parent document path and the collection ID.
+fn split_collection_path(collection_path: String) -> (Option<String>, String) {
+    match collection_path.rsplit_once('/') {
+        Some((parent, collection_id)) => (Some(parent.to_string()), collection_id.to_string()),
+        None => (None, collection_path),
+    }
+}
+
+/// Returns the query that returns the documents in the reverse order.
+///
+/// The implicit orders are always added before reversing (see [`lenient_normalized_order_by`]),
+/// so the reversed query returns the previous page even if the query has an invalid field path.
+fn reversed(mut structured_query: StructuredQuery) -> StructuredQuery {
+    structured_query.order_by = lenient_normalized_order_by(&structured_query);
+    for order in structured_query.order_by.iter_mut() {
+        order.direction = if order.direction == structured_query::Direction::Descending as i32 {
+            structured_query::Direction::Ascending as i32
+        } else {
+            structured_query::Direction::Descending as i32
+        };
+    }
+    let invert = |cursor: Cursor| Cursor {
+        values: cursor.values,
+        before: !cursor.before,
+    };
+    let start_at = structured_query.start_at.take();
+    structured_query.start_at = structured_query.end_at.take().map(invert);
+    structured_query.end_at = start_at.map(invert);
+    structured_query
+}
+
+/// Returns the order_by list that Firestore applies to the query.
+///
+/// See [`Query::normalize_order_by`].
+///
+/// The explicit orders are followed by the fields of inequality filters (in field path order) and `__name__`.
+/// The implicit orders use the direction of the last explicit order, or ascending if there is none.
+pub(crate) fn normalized_order_by(
+    structured_query: &StructuredQuery,
+) -> Result<Vec<structured_query::Order>> {
+    if structured_query
+        .order_by
+        .iter()
+        .any(|order| order.field.is_none())
+    {
+        return Err(crate::Error::with_kind(
+            crate::ErrorKind::InvalidFieldPath,
+            "order field is required",
+        ));
+    }
+    normalized_order_by_with(structured_query, FieldPath::to_segments)
+}
+
+/// Returns the same order_by list as [`normalized_order_by`], but never fails.
+///
+/// A field path that cannot be parsed is compared by its text instead of its segments,
+/// and an order without a field is kept as is.
+/// Firestore rejects such a query, so the result only has to keep the implicit orders.
+fn lenient_normalized_order_by(structured_query: &StructuredQuery) -> Vec<structured_query::Order> {
+    let Ok(order_by) = normalized_order_by_with(
+        structured_query,
+        |field_path| -> std::result::Result<_, std::convert::Infallible> {
+            Ok(field_path
+                .to_segments()
+                .unwrap_or_else(|_| vec![field_path.as_str().to_string()]))
+        },
+    );
+    order_by
+}
+
+fn normalized_order_by_with<S, E>(
+    structured_query: &StructuredQuery,
+    segments: S,
+) -> std::result::Result<Vec<structured_query::Order>, E>
+where
+    S: Fn(&FieldPath) -> std::result::Result<Vec<String>, E>,
+{
+    let mut order_by = vec![];
+    let mut ordered_fields = std::collections::BTreeSet::new();
+    for order in &structured_query.order_by {
+        if let Some(field_reference) = &order.field {
+            ordered_fields.insert(segments(&FieldPath::raw(
+                field_reference.field_path.as_str(),
+            ))?);
+        }
+        order_by.push(order.clone());
+    }
+    let last_direction = match structured_query.order_by.last() {
+        Some(order) if order.direction == structured_query::Direction::Descending as i32 => {
+            structured_query::Direction::Descending
+        }
+        _ => structured_query::Direction::Ascending,
+    };
+
+    let mut inequality_fields = std::collections::BTreeMap::new();
+    if let Some(filter) = &structured_query.r#where {
+        collect_inequality_fields(filter, &segments, &mut inequality_fields)?;
+    }
+    let document_id = FieldPath::document_id();
+    let document_id_segments = segments(&document_id)?;
+    for (field_segments, field_path) in inequality_fields {
+        if field_segments != document_id_segments && !ordered_fields.contains(&field_segments) {
+            order_by.push(structured_query::Order::from(Order::new(
+                field_path,
+                last_direction,
+            )));
+        }
+    }
+    if !ordered_fields.contains(&document_id_segments) {
+        order_by.push(structured_query::Order::from(Order::new(
+            document_id,
+            last_direction,
+        )));
+    }
+    Ok(order_by)
+}
+
+fn collect_inequality_fields<S, E>(
+    filter: &structured_query::Filter,
+    segments: &S,
+    inequality_fields: &mut std::collections::BTreeMap<Vec<String>, FieldPath>,
+) -> std::result::Result<(), E>
+where
+    S: Fn(&FieldPath) -> std::result::Result<Vec<String>, E>,
+{
+    use structured_query::filter::FilterType;
+    use structured_query::{field_filter, unary_filter};
+    let field_path = match &filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            for filter in &composite_filter.filters {
+                collect_inequality_fields(filter, segments, inequality_fields)?;
+            }
+            return Ok(());
+        }
+        Some(FilterType::FieldFilter(field_filter)) => {
+            match field_filter::Operator::try_from(field_filter.op) {
+                Ok(
+                    field_filter::Operator::LessThan
+                    | field_filter::Operator::LessThanOrEqual
+                    | field_filter::Operator::GreaterThan
+                    | field_filter::Operator::GreaterThanOrEqual
+                    | field_filter::Operator::NotEqual
+                    | field_filter::Operator::NotIn,
+                ) => field_filter.field.as_ref(),
+                _ => None,
+            }
+        }
+        Some(FilterType::UnaryFilter(unary_filter)) => {
+            match unary_filter::Operator::try_from(unary_filter.op) {
+                Ok(unary_filter::Operator::IsNotNan | unary_filter::Operator::IsNotNull) => {
+                    unary_filter
+                        .operand_type
+                        .as_ref()
+                        .map(|unary_filter::OperandType::Field(field_reference)| field_reference)
+                }
+                _ => None,
+            }
+        }
+        None => None,
+    };
+    if let Some(field_reference) = field_path {
+        let field_path = FieldPath::raw(field_reference.field_path.as_str());
+        inequality_fields.insert(segments(&field_path)?, field_path);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+fn struct_to_document<T>(
+    value: &T,
+    name: &str,
+) -> Result<googleapis_tonic_google_firestore_v1::google::firestore::v1::Document>
+where
+    T: serde::Serialize,
+{
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::{Document, value::ValueType};
+    match crate::to_value(value)?.value_type {
+        Some(ValueType::MapValue(map_value)) => Ok(Document {
+            name: name.to_string(),
+            fields: map_value.fields,
+            create_time: None,
+            update_time: None,
+        }),
+        _ => Err(crate::Error::with_kind(
+            crate::ErrorKind::InvalidCursor,
+            "the struct must be serialized as a map",
+        )),
     }
 }
@@ -0,0 +1,210 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ExplainOptions, RunQueryRequest, StructuredQuery, TransactionOptions, run_query_request,
+};
+
+use crate::{DatabaseName, Query, Result};
+
+/// A Firestore `RunQueryRequest` builder.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.RunQueryRequest>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_query_request() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{Query, QueryRequest};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     run_query_request, ExplainOptions, RunQueryRequest, StructuredQuery,
+/// };
+/// let query1 = Query::collection("collection_id1");
+/// let request1 = QueryRequest::new("project_id1", "(default)", query1.clone())?
+///     .parent_document("collection_id0/document_id0")?
+///     .transaction(b"transaction1".to_vec())
+///     .explain_options(ExplainOptions { analyze: true });
+/// assert_eq!(
+///     RunQueryRequest::from(request1),
+///     RunQueryRequest {
+///         parent: "projects/project_id1/databases/(default)/documents/collection_id0/document_id0"
+///             .to_string(),
+///         explain_options: Some(ExplainOptions { analyze: true }),
+///         query_type: Some(run_query_request::QueryType::StructuredQuery(
+///             StructuredQuery::from(query1),
+///         )),
+///         consistency_selector: Some(run_query_request::ConsistencySelector::Transaction(
+///             b"transaction1".to_vec().into(),
+///         )),
+///     }
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryRequest {
+    request: RunQueryRequest,
+    database_name: DatabaseName,
+    // the parent document path of the query (e.g. `"users/u1"` for `Query::collection("users/u1/posts")`)
+    query_parent: Option<String>,
+}
+
+impl QueryRequest {
+    /// Creates a new `QueryRequest` that runs the query against the root documents of the database.
+    ///
+    /// The `database_id` is `"(default)"` for the default database or the ID of a named database.
+    ///
+    /// Returns an error if the `project_id` or the `database_id` is invalid,
+    /// if the parent of the query (see [`Query::parent`]) is not a valid document path
+    /// (e.g. `Query::collection("users/posts")`),
+    /// or if the query is created by [`Query::limit_to_last`] and cannot be reversed (see [`Query::reversed`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_request_new() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{Query, QueryRequest};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     run_query_request, RunQueryRequest, StructuredQuery,
+    /// };
+    /// let query1 = Query::collection("collection_id1");
+    /// let request1 = QueryRequest::new("project_id1", "database_id1", query1.clone())?;
+    /// assert_eq!(
+    ///     RunQueryRequest::from(request1),
+    ///     RunQueryRequest {
+    ///         parent: "projects/project_id1/databases/database_id1/documents".to_string(),
+    ///         explain_options: None,
+    ///         query_type: Some(run_query_request::QueryType::StructuredQuery(
+    ///             StructuredQuery::from(query1),
+    ///         )),
+    ///         consistency_selector: None,
+    ///     }
+    /// );
+    /// assert!(QueryRequest::new("project_id1", "(default)", Query::collection("users/posts")).is_err());
+    /// assert!(QueryRequest::new("project_id1", "(default)", Query::collection("/posts")).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn new<P, D>(project_id: P, database_id: D, query: Query) -> Result<Self>
+    where
+        P: Into<String>,
+        D: Into<String>,
+    {
+        let database_name = DatabaseName::new(project_id, database_id)?;
+        if query.is_limit_to_last() {
+            query.clone().reversed()?;
+        }
+        let query_parent = query.parent().map(ToString::to_string);
+        let parent = match &query_parent {
+            Some(parent) => database_name.document(parent)?.to_string(),
+            None => database_name.documents_path(),
+        };
+        Ok(Self {
+            request: RunQueryRequest {
+                parent,
+                explain_options: None,
+                query_type: Some(run_query_request::QueryType::StructuredQuery(
+                    StructuredQuery::from(query),
+                )),
+                consistency_selector: None,
+            },
+            database_name,
+            query_parent,
+        })
+    }
+
+    /// Sets the specified value to explain_options and returns the QueryRequest.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.RunQueryRequest.FIELDS.google.firestore.v1.ExplainOptions.google.firestore.v1.RunQueryRequest.explain_options>
+    pub fn explain_options(mut self, explain_options: ExplainOptions) -> Self {
+        self.request.explain_options = Some(explain_options);
+        self
+    }
+
+    /// Sets the specified value to consistency_selector as `new_transaction` and returns the QueryRequest.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.RunQueryRequest.FIELDS.google.firestore.v1.TransactionOptions.google.firestore.v1.RunQueryRequest.new_transaction>
+    pub fn new_transaction(mut self, transaction_options: TransactionOptions) -> Self {
+        self.request.consistency_selector = Some(
+            run_query_request::ConsistencySelector::NewTransaction(transaction_options),
+        );
+        self
+    }
+
+    /// Sets the parent document of the query and returns the QueryRequest.
+    ///
+    /// The `document_path` is a slash-separated path relative to the documents root, such as `"chatrooms/my-chatroom"`.
+    ///
+    /// Returns an error if the `document_path` is not a valid document path,
+    /// or if the query already has a different parent document (e.g. `Query::collection("users/u1/posts")`).
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.RunQueryRequest.FIELDS.string.google.firestore.v1.RunQueryRequest.parent>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_query_request_parent_document() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::{Query, QueryRequest};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::RunQueryRequest;
+    /// let request1 = QueryRequest::new("project_id1", "(default)", Query::collection("collection_id2"))?
+    ///     .parent_document("collection_id1/document_id1")?;
+    /// assert_eq!(
+    ///     RunQueryRequest::from(request1).parent,
+    ///     "projects/project_id1/databases/(default)/documents/collection_id1/document_id1"
+    /// );
+    /// let request2 = QueryRequest::new("project_id1", "(default)", Query::collection("collection_id2"))?;
+    /// assert!(request2.clone().parent_document("collection_id1").is_err());
+    /// assert!(request2.clone().parent_document("collection_id1//document_id1").is_err());
+    /// let request3 = QueryRequest::new("project_id1", "(default)", Query::collection("users/u1/posts"))?;
+    /// assert!(request3.clone().parent_document("users/u1").is_ok());
+    /// assert!(request3.clone().parent_document("users/u2").is_err());
+    /// let request4 = QueryRequest::new("project_id1", "(default)", Query::collection("collection_id2"))?
+    ///     .parent_document("collection_id1/documents")?
+    ///     .parent_document("collection_id1/document_id1")?;
+    /// assert_eq!(
+    ///     RunQueryRequest::from(request4).parent,
+    ///     "projects/project_id1/databases/(default)/documents/collection_id1/document_id1"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent_document(mut self, document_path: &str) -> Result<Self> {
+        let document_name = self.database_name.document(document_path)?;
+        if let Some(query_parent) = &self.query_parent
+            && *query_parent != document_name.path()
+        {
+            return Err(crate::Error::from_violations(vec![crate::Violation::new(
+                "parent",
+                format!(
+                    "the query already has a different parent document: {} (actual: {})",
+                    query_parent, document_path
+                ),
+            )]));
+        }
+        self.request.parent = document_name.to_string();
+        Ok(self)
+    }
+
+    /// Sets the specified value to consistency_selector as `read_time` and returns the QueryRequest.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.RunQueryRequest.FIELDS.google.protobuf.Timestamp.google.firestore.v1.RunQueryRequest.read_time>
+    pub fn read_time(mut self, read_time: prost_types::Timestamp) -> Self {
+        self.request.consistency_selector =
+            Some(run_query_request::ConsistencySelector::ReadTime(read_time));
+        self
+    }
+
+    /// Sets the specified value to consistency_selector as `transaction` and returns the QueryRequest.
+    ///
+    /// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.RunQueryRequest.FIELDS.bytes.google.firestore.v1.RunQueryRequest.transaction>
+    #[allow(clippy::useless_conversion)]
+    pub fn transaction(mut self, transaction: Vec<u8>) -> Self {
+        self.request.consistency_selector = Some(
+            run_query_request::ConsistencySelector::Transaction(transaction.into()),
+        );
+        self
+    }
+}
+
+impl std::convert::From<QueryRequest> for RunQueryRequest {
+    fn from(query_request: QueryRequest) -> Self {
+        query_request.request
+    }
+}
@@ -0,0 +1,440 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{Value, value::ValueType};
+
+use crate::{IntoValue, Result};
+
+/// A Firestore database name (`projects/{project_id}/databases/{database_id}`).
+///
+/// <https://firebase.google.com/docs/firestore/reference/rpc/google.firestore.v1#google.firestore.v1.RunQueryRequest.FIELDS.string.google.firestore.v1.RunQueryRequest.parent>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_database_name() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::DatabaseName;
+/// let database_name = DatabaseName::new("project_id1", "(default)")?;
+/// assert_eq!(database_name.to_string(), "projects/project_id1/databases/(default)");
+/// assert_eq!(
+///     "projects/project_id1/databases/(default)".parse::<DatabaseName>()?,
+///     database_name
+/// );
+/// assert_eq!(
+///     database_name.documents_path(),
+///     "projects/project_id1/databases/(default)/documents"
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DatabaseName {
+    project_id: String,
+    database_id: String,
+}
+
+impl DatabaseName {
+    /// Creates a new database name.
+    ///
+    /// Returns an error if the `project_id` or the `database_id` is empty or contains `/`.
+    pub fn new<P, D>(project_id: P, database_id: D) -> Result<Self>
+    where
+        P: Into<String>,
+        D: Into<String>,
+    {
+        let project_id = project_id.into();
+        let database_id = database_id.into();
+        for (kind, id) in [("project ID", &project_id), ("database ID", &database_id)] {
+            if id.is_empty() || id.contains('/') {
+                return Err(crate::Error::with_kind(
+                    crate::ErrorKind::InvalidFieldPath,
+                    format!("invalid {}: {}", kind, id),
+                ));
+            }
+        }
+        Ok(Self {
+            project_id,
+            database_id,
+        })
+    }
+
+    /// Returns the collection name of the slash-separated collection path (such as `users` or `users/u1/posts`).
+    ///
+    /// Returns an error if the `collection_path` is not a valid collection path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_database_name_collection() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::DatabaseName;
+    /// let database_name = DatabaseName::new("p", "(default)")?;
+    /// let collection_name = database_name.collection("users/u1/posts")?;
+    /// assert_eq!(
+    ///     collection_name.to_string(),
+    ///     "projects/p/databases/(default)/documents/users/u1/posts"
+    /// );
+    /// assert!(database_name.collection("users/u1").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection(&self, collection_path: &str) -> Result<CollectionName> {
+        let segments = parse_path(collection_path)?;
+        if segments.len() % 2 == 0 {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::InvalidFieldPath,
+                format!("invalid collection path: {}", collection_path),
+            ));
+        }
+        Ok(CollectionName {
+            database_name: self.clone(),
+            segments,
+        })
+    }
+
+    /// Returns the database ID.
+    pub fn database_id(&self) -> &str {
+        &self.database_id
+    }
+
+    /// Returns the document name of the slash-separated document path (such as `users/u1`).
+    ///
+    /// Returns an error if the `document_path` is not a valid document path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn test_database_name_document() -> firestore_structured_query::Result<()> {
+    /// use firestore_structured_query::DatabaseName;
+    /// let database_name = DatabaseName::new("p", "(default)")?;
+    /// let document_name = database_name.document("users/u1")?;
+    /// assert_eq!(
+    ///     document_name.to_string(),
+    ///     "projects/p/databases/(default)/documents/users/u1"
+    /// );
+    /// assert!(database_name.document("users").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn document(&self, document_path: &str) -> Result<DocumentName> {
+        let segments = parse_path(document_path)?;
+        if segments.len() % 2 != 0 {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::InvalidFieldPath,
+                format!("invalid document path: {}", document_path),
+            ));
+        }
+        Ok(DocumentName {
+            database_name: self.clone(),
+            segments,
+        })
+    }
+
+    /// Returns the name of the root of the documents (`projects/{project_id}/databases/{database_id}/documents`).
+    pub fn documents_path(&self) -> String {
+        format!("{}/documents", self)
+    }
+
+    /// Returns the project ID.
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+}
+
+impl std::fmt::Display for DatabaseName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "projects/{}/databases/{}",
+            self.project_id, self.database_id
+        )
+    }
+}
+
+/// Parses `projects/{project_id}/databases/{database_id}`.
+impl std::str::FromStr for DatabaseName {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split('/').collect::<Vec<&str>>().as_slice() {
+            ["projects", project_id, "databases", database_id] => {
+                Self::new(*project_id, *database_id)
+            }
+            _ => Err(crate::Error::with_kind(
+                crate::ErrorKind::InvalidFieldPath,
+                format!("invalid database name: {}", s),
+            )),
+        }
+    }
+}
+
+/// A Firestore collection name (`projects/{project_id}/databases/{database_id}/documents/{collection_path}`).
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_collection_name() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{CollectionName, DatabaseName};
+/// let collection_name: CollectionName =
+///     "projects/p/databases/(default)/documents/users/u1/posts".parse()?;
+/// assert_eq!(collection_name.database_name(), &DatabaseName::new("p", "(default)")?);
+/// assert_eq!(collection_name.collection_id(), "posts");
+/// assert_eq!(collection_name.path(), "users/u1/posts");
+/// assert_eq!(
+///     collection_name.parent_path(),
+///     "projects/p/databases/(default)/documents/users/u1"
+/// );
+/// assert_eq!(
+///     collection_name.parent().map(|parent| parent.to_string()),
+///     Some("projects/p/databases/(default)/documents/users/u1".to_string())
+/// );
+/// assert_eq!(
+///     collection_name.document("post1")?.to_string(),
+///     "projects/p/databases/(default)/documents/users/u1/posts/post1"
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CollectionName {
+    database_name: DatabaseName,
+    segments: Vec<String>,
+}
+
+impl CollectionName {
+    /// Returns the collection ID (the last segment).
+    pub fn collection_id(&self) -> &str {
+        self.segments.last().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Returns the database name.
+    pub fn database_name(&self) -> &DatabaseName {
+        &self.database_name
+    }
+
+    /// Returns the document name of the document in the collection.
+    ///
+    /// Returns an error if the `document_id` is not a valid document ID.
+    pub fn document(&self, document_id: &str) -> Result<DocumentName> {
+        crate::validation::check_document_id(document_id).map_err(|message| {
+            crate::Error::with_kind(crate::ErrorKind::InvalidFieldPath, message)
+        })?;
+        let mut segments = self.segments.clone();
+        segments.push(document_id.to_string());
+        Ok(DocumentName {
+            database_name: self.database_name.clone(),
+            segments,
+        })
+    }
+
+    /// Returns the parent document name, or `None` if the collection is a root collection.
+    pub fn parent(&self) -> Option<DocumentName> {
+        match self.segments.split_last() {
+            Some((_, parent)) if !parent.is_empty() => Some(DocumentName {
+                database_name: self.database_name.clone(),
+                segments: parent.to_vec(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the parent, which is used as the parent of a query on the collection.
+    ///
+    /// The parent is the parent document name or the root of the documents (`projects/{project_id}/databases/{database_id}/documents`).
+    pub fn parent_path(&self) -> String {
+        match self.parent() {
+            Some(parent) => parent.to_string(),
+            None => self.database_name.documents_path(),
+        }
+    }
+
+    /// Returns the slash-separated collection path relative to the root of the documents.
+    pub fn path(&self) -> String {
+        self.segments.join("/")
+    }
+}
+
+impl std::fmt::Display for CollectionName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.database_name.documents_path(), self.path())
+    }
+}
+
+/// Parses `projects/{project_id}/databases/{database_id}/documents/{collection_path}`.
+impl std::str::FromStr for CollectionName {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (database_name, path) = split_name(s)?;
+        database_name.collection(path)
+    }
+}
+
+/// A Firestore document name (`projects/{project_id}/databases/{database_id}/documents/{document_path}`).
+///
+/// The document name is converted into a `reference_value` by `IntoValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_document_name() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{DocumentName, FieldPath, IntoValue};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+/// let document_name: DocumentName =
+///     "projects/p/databases/(default)/documents/users/u1".parse()?;
+/// assert_eq!(document_name.collection_id(), "users");
+/// assert_eq!(document_name.document_id(), "u1");
+/// assert_eq!(document_name.path(), "users/u1");
+/// assert_eq!(
+///     document_name.parent().to_string(),
+///     "projects/p/databases/(default)/documents/users"
+/// );
+/// assert_eq!(
+///     document_name.collection("posts")?.to_string(),
+///     "projects/p/databases/(default)/documents/users/u1/posts"
+/// );
+/// assert_eq!(
+///     document_name.clone().into_value()?,
+///     Value {
+///         value_type: Some(ValueType::ReferenceValue(
+///             "projects/p/databases/(default)/documents/users/u1".to_string()
+///         )),
+///     }
+/// );
+/// let _ = FieldPath::raw("author").equal(document_name)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DocumentName {
+    database_name: DatabaseName,
+    segments: Vec<String>,
+}
+
+impl DocumentName {
+    /// Returns the collection name of the subcollection of the document.
+    ///
+    /// Returns an error if the `collection_id` is not a valid collection ID.
+    pub fn collection(&self, collection_id: &str) -> Result<CollectionName> {
+        crate::validation::check_collection_id(collection_id).map_err(|message| {
+            crate::Error::with_kind(crate::ErrorKind::InvalidFieldPath, message)
+        })?;
+        let mut segments = self.segments.clone();
+        segments.push(collection_id.to_string());
+        Ok(CollectionName {
+            database_name: self.database_name.clone(),
+            segments,
+        })
+    }
+
+    /// Returns the collection ID of the collection that contains the document.
+    pub fn collection_id(&self) -> &str {
+        self.segments
+            .len()
+            .checked_sub(2)
+            .and_then(|index| self.segments.get(index))
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Returns the database name.
+    pub fn database_name(&self) -> &DatabaseName {
+        &self.database_name
+    }
+
+    /// Returns the document ID (the last segment).
+    pub fn document_id(&self) -> &str {
+        self.segments.last().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Returns the collection name of the collection that contains the document.
+    pub fn parent(&self) -> CollectionName {
+        CollectionName {
+            database_name: self.database_name.clone(),
+            segments: self.segments[..self.segments.len().saturating_sub(1)].to_vec(),
+        }
+    }
+
+    /// Returns the slash-separated document path relative to the root of the documents.
+    pub fn path(&self) -> String {
+        self.segments.join("/")
+    }
+}
+
+impl std::fmt::Display for DocumentName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.database_name.documents_path(), self.path())
+    }
+}
+
+/// Parses `projects/{project_id}/databases/{database_id}/documents/{document_path}`.
+impl std::str::FromStr for DocumentName {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (database_name, path) = split_name(s)?;
+        database_name.document(path)
+    }
+}
+
+impl IntoValue for DocumentName {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value {
+            value_type: Some(ValueType::ReferenceValue(self.to_string())),
+        })
+    }
+}
+
+/// Parses the parent of a collection: the root of the documents (`projects/{project_id}/databases/{database_id}/documents`)
+/// or a document name.
+pub(crate) fn parse_parent(parent: &str) -> Result<(DatabaseName, Option<DocumentName>)> {
+    // a document whose ID is "documents" also ends with "/documents"
+    match parent
+        .strip_suffix("/documents")
+        .and_then(|database_name| database_name.parse::<DatabaseName>().ok())
+    {
+        Some(database_name) => Ok((database_name, None)),
+        None => {
+            let document_name = parent.parse::<DocumentName>()?;
+            Ok((document_name.database_name().clone(), Some(document_name)))
+        }
+    }
+}
+
+/// Splits `projects/{project_id}/databases/{database_id}/documents/{path}` into the database name and the path.
+fn split_name(s: &str) -> Result<(DatabaseName, &str)> {
+    let invalid = || {
+        crate::Error::with_kind(
+            crate::ErrorKind::InvalidFieldPath,
+            format!("invalid resource name: {}", s),
+        )
+    };
+    let mut parts = s.splitn(6, '/');
+    let database_name = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some("projects"), Some(project_id), Some("databases"), Some(database_id)) => {
+            DatabaseName::new(project_id, database_id).map_err(|_| invalid())?
+        }
+        _ => return Err(invalid()),
+    };
+    match (parts.next(), parts.next()) {
+        (Some("documents"), Some(path)) => Ok((database_name, path)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses the slash-separated path of collection IDs and document IDs.
+pub(crate) fn parse_path(path: &str) -> Result<Vec<String>> {
+    path.split('/')
+        .enumerate()
+        .map(|(index, segment)| {
+            if index % 2 == 0 {
+                crate::validation::check_collection_id(segment)
+            } else {
+                crate::validation::check_document_id(segment)
+            }
+            .map(|_| segment.to_string())
+            .map_err(|message| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::InvalidFieldPath,
+                    format!("invalid path {}: {}", path, message),
+                )
+            })
+        })
+        .collect()
+}
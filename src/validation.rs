@@ -0,0 +1,454 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    StructuredQuery, Value, structured_query, value::ValueType,
+};
+
+use crate::FieldPath;
+
+/// The maximum number of values of an `in` or `array_contains_any` filter.
+///
+/// <https://firebase.google.com/docs/firestore/query-data/queries#limitations>
+pub(crate) const MAX_IN_VALUES: usize = 30;
+
+/// The maximum number of values of a `not_in` filter.
+///
+/// <https://firebase.google.com/docs/firestore/query-data/queries#limitations>
+pub(crate) const MAX_NOT_IN_VALUES: usize = 10;
+
+/// The maximum number of disjunctions of a filter in disjunctive normal form.
+///
+/// <https://firebase.google.com/docs/firestore/query-data/queries#limits_on_or_queries>
+pub(crate) const MAX_DISJUNCTIONS: usize = 30;
+
+/// A violation of a Firestore query constraint.
+///
+/// The location is a dot-separated path to the violating part of the `StructuredQuery`,
+/// such as `where.filters[1]`, `order_by[0]` or `limit`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_violation() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::Query;
+/// let violations = Query::collection("collection_id1").limit(-1).violations();
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].location(), "limit");
+/// assert_eq!(violations[0].message(), "limit must not be negative (actual: -1)");
+/// assert_eq!(violations[0].to_string(), "limit: limit must not be negative (actual: -1)");
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Violation {
+    location: String,
+    message: String,
+}
+
+impl Violation {
+    pub(crate) fn new<L, M>(location: L, message: M) -> Self
+    where
+        L: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Returns the location of the violation.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Returns the message of the violation.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.location.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.location, self.message)
+        }
+    }
+}
+
+pub(crate) fn violations(structured_query: &StructuredQuery) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    if let Some(projection) = &structured_query.select {
+        let mut selected = std::collections::BTreeSet::new();
+        for (index, field_reference) in projection.fields.iter().enumerate() {
+            let location = format!("select.fields[{}]", index);
+            if let Some(segments) = check_field_path(field_reference, &location, &mut violations)
+                && !selected.insert(segments)
+            {
+                violations.push(Violation::new(
+                    location,
+                    format!("duplicate field path: {}", field_reference.field_path),
+                ));
+            }
+        }
+    }
+
+    for (index, collection_selector) in structured_query.from.iter().enumerate() {
+        if let Err(message) = check_collection_id(&collection_selector.collection_id) {
+            violations.push(Violation::new(
+                format!("from[{}].collection_id", index),
+                message,
+            ));
+        }
+    }
+
+    if let Some(filter) = &structured_query.r#where {
+        let mut field_filters = vec![];
+        check_filter(filter, "where", &mut field_filters, &mut violations);
+
+        let not_in_filters = field_filters
+            .iter()
+            .filter(|(_, op)| *op == structured_query::field_filter::Operator::NotIn)
+            .count();
+        if not_in_filters > 0 {
+            for (location, _) in field_filters
+                .iter()
+                .filter(|(_, op)| {
+                    matches!(
+                        op,
+                        structured_query::field_filter::Operator::NotIn
+                            | structured_query::field_filter::Operator::NotEqual
+                    )
+                })
+                .skip(1)
+            {
+                violations.push(Violation::new(
+                    location.as_str(),
+                    "not_in cannot be combined with not_equal or another not_in",
+                ));
+            }
+        }
+
+        if max_array_contains_per_conjunction(filter) > 1 {
+            violations.push(Violation::new(
+                "where",
+                "a disjunction cannot contain more than one array_contains or array_contains_any",
+            ));
+        }
+
+        let disjunctions = disjunction_count(filter);
+        if disjunctions > MAX_DISJUNCTIONS {
+            violations.push(Violation::new(
+                "where",
+                format!(
+                    "the number of disjunctions in disjunctive normal form must be less than or equal to {} (actual: {})",
+                    MAX_DISJUNCTIONS, disjunctions
+                ),
+            ));
+        }
+    }
+
+    let mut order_by_is_valid = true;
+    for (index, order) in structured_query.order_by.iter().enumerate() {
+        let location = format!("order_by[{}]", index);
+        match &order.field {
+            Some(field_reference) => {
+                if check_field_path(field_reference, &location, &mut violations).is_none() {
+                    order_by_is_valid = false;
+                }
+            }
+            None => {
+                violations.push(Violation::new(location.as_str(), "field is required"));
+                order_by_is_valid = false;
+            }
+        }
+        if structured_query::Direction::try_from(order.direction).is_err() {
+            violations.push(Violation::new(
+                location,
+                format!("invalid direction: {}", order.direction),
+            ));
+        }
+    }
+
+    if order_by_is_valid && let Ok(order_by) = crate::query::normalized_order_by(structured_query) {
+        for (location, cursor) in [
+            ("start_at", &structured_query.start_at),
+            ("end_at", &structured_query.end_at),
+        ] {
+            if let Some(cursor) = cursor
+                && cursor.values.len() > order_by.len()
+            {
+                violations.push(Violation::new(
+                    location,
+                    format!(
+                        "the number of cursor values must be less than or equal to the number of order_by fields {} (actual: {})",
+                        order_by.len(),
+                        cursor.values.len()
+                    ),
+                ));
+            }
+        }
+    }
+
+    if structured_query.offset < 0 {
+        violations.push(Violation::new(
+            "offset",
+            format!(
+                "offset must not be negative (actual: {})",
+                structured_query.offset
+            ),
+        ));
+    }
+    if let Some(limit) = structured_query.limit
+        && limit < 0
+    {
+        violations.push(Violation::new(
+            "limit",
+            format!("limit must not be negative (actual: {})", limit),
+        ));
+    }
+
+    violations
+}
+
+/// Returns the violations of the parent document path of the query (such as `users/u1`).
+pub(crate) fn parent_violations(parent: &str) -> Vec<Violation> {
+    let mut violations = vec![];
+    match crate::resource_name::parse_path(parent) {
+        Ok(segments) if segments.len() % 2 != 0 => violations.push(Violation::new(
+            "parent",
+            format!("parent must be a document path: {}", parent),
+        )),
+        Ok(_) => {}
+        Err(e) => violations.push(Violation::new("parent", e.to_string())),
+    }
+    violations
+}
+
+// <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+pub(crate) fn check_collection_id(collection_id: &str) -> Result<(), String> {
+    check_id("collection ID", collection_id)
+}
+
+pub(crate) fn check_document_id(document_id: &str) -> Result<(), String> {
+    check_id("document ID", document_id)
+}
+
+fn check_id(kind: &str, id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err(format!("{} must not be empty", kind));
+    }
+    if id.contains('/') {
+        return Err(format!("{} must not contain '/': {}", kind, id));
+    }
+    if id == "." || id == ".." {
+        return Err(format!("{} must not be '.' or '..': {}", kind, id));
+    }
+    if id.len() >= 4 && id.starts_with("__") && id.ends_with("__") {
+        return Err(format!("{} must not match __.*__: {}", kind, id));
+    }
+    if id.len() > 1500 {
+        return Err(format!(
+            "{} must be no longer than 1,500 bytes (actual: {})",
+            kind,
+            id.len()
+        ));
+    }
+    Ok(())
+}
+
+fn check_field_path(
+    field_reference: &structured_query::FieldReference,
+    location: &str,
+    violations: &mut Vec<Violation>,
+) -> Option<Vec<String>> {
+    match FieldPath::raw(field_reference.field_path.as_str()).to_segments() {
+        Ok(segments) => Some(segments),
+        Err(e) => {
+            violations.push(Violation::new(location, e.to_string()));
+            None
+        }
+    }
+}
+
+fn check_filter(
+    filter: &structured_query::Filter,
+    location: &str,
+    field_filters: &mut Vec<(String, structured_query::field_filter::Operator)>,
+    violations: &mut Vec<Violation>,
+) {
+    use structured_query::filter::FilterType;
+    use structured_query::{composite_filter, field_filter, unary_filter};
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            if composite_filter::Operator::try_from(composite_filter.op).is_err()
+                || composite_filter.op == composite_filter::Operator::Unspecified as i32
+            {
+                violations.push(Violation::new(
+                    location,
+                    format!("invalid composite operator: {}", composite_filter.op),
+                ));
+            }
+            if composite_filter.filters.is_empty() {
+                violations.push(Violation::new(
+                    location,
+                    "composite filter must contain at least one filter",
+                ));
+            }
+            for (index, filter) in composite_filter.filters.iter().enumerate() {
+                check_filter(
+                    filter,
+                    &format!("{}.filters[{}]", location, index),
+                    field_filters,
+                    violations,
+                );
+            }
+        }
+        Some(FilterType::FieldFilter(field_filter)) => {
+            match &field_filter.field {
+                Some(field_reference) => {
+                    check_field_path(field_reference, location, violations);
+                }
+                None => violations.push(Violation::new(location, "field is required")),
+            }
+            let op = match field_filter::Operator::try_from(field_filter.op) {
+                Ok(field_filter::Operator::Unspecified) | Err(_) => {
+                    violations.push(Violation::new(
+                        location,
+                        format!("invalid field operator: {}", field_filter.op),
+                    ));
+                    return;
+                }
+                Ok(op) => op,
+            };
+            field_filters.push((location.to_string(), op));
+            let value = field_filter.value.clone().unwrap_or_default();
+            if let Err(message) = check_field_filter_value(op, &value) {
+                violations.push(Violation::new(location, message));
+            }
+        }
+        Some(FilterType::UnaryFilter(unary_filter)) => {
+            if unary_filter.operand_type.is_none() {
+                violations.push(Violation::new(location, "field is required"));
+            }
+            if let Some(unary_filter::OperandType::Field(field_reference)) =
+                &unary_filter.operand_type
+            {
+                check_field_path(field_reference, location, violations);
+            }
+            if matches!(
+                unary_filter::Operator::try_from(unary_filter.op),
+                Ok(unary_filter::Operator::Unspecified) | Err(_)
+            ) {
+                violations.push(Violation::new(
+                    location,
+                    format!("invalid unary operator: {}", unary_filter.op),
+                ));
+            }
+        }
+        None => violations.push(Violation::new(location, "filter_type is required")),
+    }
+}
+
+/// Checks the value of a field filter with the operator.
+///
+/// The array operators (`in`, `not_in` and `array_contains_any`) require a non-empty array value of the allowed size,
+/// and the range operators (`<`, `<=`, `>` and `>=`) do not support vector values.
+pub(crate) fn check_field_filter_value(
+    op: structured_query::field_filter::Operator,
+    value: &Value,
+) -> Result<(), String> {
+    use structured_query::field_filter;
+    let max_values = match op {
+        field_filter::Operator::In | field_filter::Operator::ArrayContainsAny => MAX_IN_VALUES,
+        field_filter::Operator::NotIn => MAX_NOT_IN_VALUES,
+        field_filter::Operator::LessThan
+        | field_filter::Operator::LessThanOrEqual
+        | field_filter::Operator::GreaterThan
+        | field_filter::Operator::GreaterThanOrEqual => {
+            return match &value.value_type {
+                Some(ValueType::MapValue(map_value))
+                    if crate::ordered_value::vector_values(map_value).is_some() =>
+                {
+                    Err(format!(
+                        "{} does not support vector values",
+                        op.as_str_name()
+                    ))
+                }
+                _ => Ok(()),
+            };
+        }
+        _ => return Ok(()),
+    };
+    match array_values(Some(value)) {
+        Some([]) => Err(format!("{} requires a non-empty array", op.as_str_name())),
+        Some(values) if values.len() > max_values => Err(format!(
+            "{} supports up to {} values (actual: {})",
+            op.as_str_name(),
+            max_values,
+            values.len()
+        )),
+        Some(_) => Ok(()),
+        None => Err(format!("{} requires an array value", op.as_str_name())),
+    }
+}
+
+fn array_values(value: Option<&Value>) -> Option<&[Value]> {
+    match value {
+        Some(Value {
+            value_type: Some(ValueType::ArrayValue(array_value)),
+        }) => Some(array_value.values.as_slice()),
+        _ => None,
+    }
+}
+
+/// Returns the number of disjunctions of the filter in disjunctive normal form.
+///
+/// `in` and `array_contains_any` filters count as one disjunction per value.
+pub(crate) fn disjunction_count(filter: &structured_query::Filter) -> usize {
+    use structured_query::filter::FilterType;
+    use structured_query::{composite_filter, field_filter};
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            let counts = composite_filter.filters.iter().map(disjunction_count);
+            if composite_filter.op == composite_filter::Operator::Or as i32 {
+                counts.fold(0_usize, usize::saturating_add)
+            } else {
+                counts.fold(1_usize, usize::saturating_mul)
+            }
+        }
+        Some(FilterType::FieldFilter(field_filter))
+            if field_filter.op == field_filter::Operator::In as i32
+                || field_filter.op == field_filter::Operator::ArrayContainsAny as i32 =>
+        {
+            array_values(field_filter.value.as_ref()).map_or(1, |values| values.len().max(1))
+        }
+        Some(FilterType::FieldFilter(_)) | Some(FilterType::UnaryFilter(_)) | None => 1,
+    }
+}
+
+fn max_array_contains_per_conjunction(filter: &structured_query::Filter) -> usize {
+    use structured_query::filter::FilterType;
+    use structured_query::{composite_filter, field_filter};
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(composite_filter)) => {
+            let counts = composite_filter
+                .filters
+                .iter()
+                .map(max_array_contains_per_conjunction);
+            if composite_filter.op == composite_filter::Operator::Or as i32 {
+                counts.max().unwrap_or(0)
+            } else {
+                counts.sum()
+            }
+        }
+        Some(FilterType::FieldFilter(field_filter))
+            if field_filter.op == field_filter::Operator::ArrayContains as i32
+                || field_filter.op == field_filter::Operator::ArrayContainsAny as i32 =>
+        {
+            1
+        }
+        Some(FilterType::FieldFilter(_)) | Some(FilterType::UnaryFilter(_)) | None => 0,
+    }
+}
@@ -1,3 +1,7 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ArrayValue, MapValue, Value, value::ValueType,
+};
+
 use crate::Result;
 
 /// A Firestore value converter.
@@ -19,11 +23,67 @@ pub fn to_value<T>(
 where
     T: serde::Serialize,
 {
-    Ok(serde_firestore_value::to_value(v)
-        .map_err(Box::<dyn std::error::Error + Send + Sync>::from)?)
+    serde_firestore_value::to_value(v)
+        .map_err(|e| crate::Error::with_kind(crate::ErrorKind::ValueConversion, e))
 }
 
 /// A Firestore value converter trait.
+///
+/// The following types implement `IntoValue` without the `serde` feature:
+///
+/// Type | Firestore value
+/// ---|---
+/// `Value` | as is
+/// `bool` | `boolean_value`
+/// `i8`, `i16`, `i32`, `i64`, `u16`, `u32` | `integer_value`
+/// `u64`, `usize`, `isize`, `i128`, `u128` | `integer_value` (an error if the value overflows `i64`)
+/// `f32`, `f64` | `double_value`
+/// `&str`, `String`, `Box<str>`, `Cow<str>` | `string_value`
+/// `OsString`, `PathBuf` | `string_value` (an error if the value is not valid UTF-8)
+/// `Vec<u8>`, `&[u8]`, `bytes::Bytes` (the `bytes` feature) | `bytes_value`
+/// `LatLng` | `geo_point_value`
+/// `Timestamp`, `SystemTime` | `timestamp_value` (truncated to microseconds, an error if out of range)
+/// `chrono::DateTime<Tz>` (the `chrono` feature) | `timestamp_value` (truncated to microseconds, an error if out of range)
+/// `time::OffsetDateTime` (the `time` feature) | `timestamp_value` (truncated to microseconds, an error if out of range)
+/// `Option<T>` | `null_value` if `None`
+/// `Vec<T>`, `&[T]`, `[T; N]`, tuples (up to 12 elements) | `array_value`
+/// `HashMap<K, V>`, `BTreeMap<K, V>` | `map_value`
+/// `DocumentName` | `reference_value`
+/// `Vector` | vector value (`map_value`)
+///
+/// With the `serde` feature, `&T` also implements `IntoValue` for any `T: serde::Serialize` and is converted by `to_value`.
+/// So a borrowed value (e.g. `equal(&1)`) is converted by `serde`, while an owned value (e.g. `equal(1)`) uses the table above.
+/// Pass owned values to get the native conversion: the `serde` representation of a type can differ from the table above.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_into_value() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{FieldPath, IntoValue};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     value::ValueType, ArrayValue, Value,
+/// };
+/// assert_eq!(
+///     1_i64.into_value()?,
+///     Value { value_type: Some(ValueType::IntegerValue(1)) }
+/// );
+/// assert_eq!(
+///     vec!["a", "b"].into_value()?,
+///     Value {
+///         value_type: Some(ValueType::ArrayValue(ArrayValue {
+///             values: vec![
+///                 Value { value_type: Some(ValueType::StringValue("a".to_string())) },
+///                 Value { value_type: Some(ValueType::StringValue("b".to_string())) },
+///             ],
+///         })),
+///     }
+/// );
+/// assert!(u64::MAX.into_value().is_err());
+/// let _ = FieldPath::raw("field1").equal(1)?;
+/// let _ = FieldPath::raw("field2").r#in(["a", "b"])?;
+/// #     Ok(())
+/// # }
+/// ```
 pub trait IntoValue {
     /// Convert the value into a Firestore value.
     fn into_value(
@@ -44,7 +104,324 @@ impl<T: serde::Serialize> IntoValue for &T {
     fn into_value(
         self,
     ) -> Result<googleapis_tonic_google_firestore_v1::google::firestore::v1::Value> {
-        Ok(serde_firestore_value::to_value(self)
-            .map_err(Box::<dyn std::error::Error + Send + Sync>::from)?)
+        to_value(self)
+    }
+}
+
+fn value(value_type: ValueType) -> Value {
+    Value {
+        value_type: Some(value_type),
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::BooleanValue(self)))
+    }
+}
+
+macro_rules! impl_into_value_for_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoValue for $ty {
+                fn into_value(self) -> Result<Value> {
+                    Ok(value(ValueType::IntegerValue(i64::from(self))))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_value_for_integer!(i8, i16, i32, i64, u16, u32);
+
+macro_rules! impl_into_value_for_large_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoValue for $ty {
+                fn into_value(self) -> Result<Value> {
+                    let integer = i64::try_from(self).map_err(|_| {
+                        crate::Error::with_kind(
+                            crate::ErrorKind::ValueConversion,
+                            format!(
+                                "{} value {} is out of range of integer_value (i64)",
+                                stringify!($ty),
+                                self
+                            ),
+                        )
+                    })?;
+                    Ok(value(ValueType::IntegerValue(integer)))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_value_for_large_integer!(u64, usize, isize, i128, u128);
+
+impl IntoValue for f32 {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::DoubleValue(f64::from(self))))
+    }
+}
+
+impl IntoValue for f64 {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::DoubleValue(self)))
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::StringValue(self.to_string())))
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::StringValue(self)))
+    }
+}
+
+impl IntoValue for Box<str> {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::StringValue(self.into_string())))
+    }
+}
+
+impl IntoValue for std::borrow::Cow<'_, str> {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::StringValue(self.into_owned())))
+    }
+}
+
+impl IntoValue for std::ffi::OsString {
+    fn into_value(self) -> Result<Value> {
+        let string = self.into_string().map_err(|s| {
+            crate::Error::with_kind(
+                crate::ErrorKind::ValueConversion,
+                format!("{} is not valid UTF-8", s.to_string_lossy()),
+            )
+        })?;
+        Ok(value(ValueType::StringValue(string)))
+    }
+}
+
+impl IntoValue for std::path::PathBuf {
+    fn into_value(self) -> Result<Value> {
+        self.into_os_string().into_value()
+    }
+}
+
+impl IntoValue for Vec<u8> {
+    #[allow(clippy::useless_conversion)]
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::BytesValue(self.into())))
+    }
+}
+
+impl IntoValue for &[u8] {
+    #[allow(clippy::useless_conversion)]
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::BytesValue(self.to_vec().into())))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl IntoValue for bytes::Bytes {
+    #[allow(clippy::useless_conversion)]
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::BytesValue(self.into())))
+    }
+}
+
+impl IntoValue for googleapis_tonic_google_firestore_v1::google::r#type::LatLng {
+    fn into_value(self) -> Result<Value> {
+        Ok(value(ValueType::GeoPointValue(self)))
+    }
+}
+
+impl IntoValue for prost_types::Timestamp {
+    fn into_value(self) -> Result<Value> {
+        let seconds = self
+            .seconds
+            .checked_add(i64::from(self.nanos.div_euclid(1_000_000_000)))
+            .ok_or_else(|| {
+                crate::Error::with_kind(
+                    crate::ErrorKind::ValueConversion,
+                    format!("timestamp out of range: {}", self),
+                )
+            })?;
+        let nanos = self.nanos.rem_euclid(1_000_000_000) as u32;
+        timestamp_value(seconds, nanos)
+    }
+}
+
+impl IntoValue for std::time::SystemTime {
+    fn into_value(self) -> Result<Value> {
+        let out_of_range = || {
+            crate::Error::with_kind(
+                crate::ErrorKind::ValueConversion,
+                format!("timestamp out of range: {:?}", self),
+            )
+        };
+        let (seconds, nanos) = match self.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => (
+                i64::try_from(duration.as_secs()).map_err(|_| out_of_range())?,
+                duration.subsec_nanos(),
+            ),
+            Err(e) => {
+                let duration = e.duration();
+                let seconds = i64::try_from(duration.as_secs())
+                    .map_err(|_| out_of_range())?
+                    .wrapping_neg();
+                match duration.subsec_nanos() {
+                    0 => (seconds, 0),
+                    nanos => (
+                        seconds.checked_sub(1).ok_or_else(out_of_range)?,
+                        1_000_000_000 - nanos,
+                    ),
+                }
+            }
+        };
+        timestamp_value(seconds, nanos)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz> IntoValue for chrono::DateTime<Tz>
+where
+    Tz: chrono::TimeZone,
+{
+    fn into_value(self) -> Result<Value> {
+        timestamp_value(self.timestamp(), self.timestamp_subsec_nanos())
+    }
+}
+
+#[cfg(feature = "time")]
+impl IntoValue for time::OffsetDateTime {
+    fn into_value(self) -> Result<Value> {
+        timestamp_value(self.unix_timestamp(), self.nanosecond())
+    }
+}
+
+fn timestamp_value(seconds: i64, nanos: u32) -> Result<Value> {
+    Ok(value(ValueType::TimestampValue(
+        crate::encoding::timestamp(seconds, nanos)?,
+    )))
+}
+
+impl<T> IntoValue for Option<T>
+where
+    T: IntoValue,
+{
+    fn into_value(self) -> Result<Value> {
+        match self {
+            Some(v) => v.into_value(),
+            None => Ok(value(ValueType::NullValue(0))),
+        }
+    }
+}
+
+fn array_value<I>(values: I) -> Result<Value>
+where
+    I: IntoIterator,
+    I::Item: IntoValue,
+{
+    Ok(value(ValueType::ArrayValue(ArrayValue {
+        values: values
+            .into_iter()
+            .map(IntoValue::into_value)
+            .collect::<Result<Vec<Value>>>()?,
+    })))
+}
+
+impl<T> IntoValue for Vec<T>
+where
+    T: IntoValue,
+{
+    fn into_value(self) -> Result<Value> {
+        array_value(self)
+    }
+}
+
+impl<T> IntoValue for &[T]
+where
+    T: IntoValue + Clone,
+{
+    fn into_value(self) -> Result<Value> {
+        array_value(self.iter().cloned())
+    }
+}
+
+impl<T, const N: usize> IntoValue for [T; N]
+where
+    T: IntoValue,
+{
+    fn into_value(self) -> Result<Value> {
+        array_value(self)
+    }
+}
+
+macro_rules! impl_into_value_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> IntoValue for ($($name,)+)
+        where
+            $($name: IntoValue,)+
+        {
+            #[allow(non_snake_case)]
+            fn into_value(self) -> Result<Value> {
+                let ($($name,)+) = self;
+                Ok(value(ValueType::ArrayValue(ArrayValue {
+                    values: vec![$($name.into_value()?),+],
+                })))
+            }
+        }
+    };
+}
+
+impl_into_value_for_tuple!(A);
+impl_into_value_for_tuple!(A, B);
+impl_into_value_for_tuple!(A, B, C);
+impl_into_value_for_tuple!(A, B, C, D);
+impl_into_value_for_tuple!(A, B, C, D, E);
+impl_into_value_for_tuple!(A, B, C, D, E, F);
+impl_into_value_for_tuple!(A, B, C, D, E, F, G);
+impl_into_value_for_tuple!(A, B, C, D, E, F, G, H);
+impl_into_value_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_into_value_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_into_value_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_into_value_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+fn map_value<I, K, V>(entries: I) -> Result<Value>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: IntoValue,
+{
+    Ok(value(ValueType::MapValue(MapValue {
+        fields: entries
+            .into_iter()
+            .map(|(k, v)| Ok((k.into(), v.into_value()?)))
+            .collect::<Result<_>>()?,
+    })))
+}
+
+impl<K, V, S> IntoValue for std::collections::HashMap<K, V, S>
+where
+    K: Into<String>,
+    V: IntoValue,
+{
+    fn into_value(self) -> Result<Value> {
+        map_value(self)
+    }
+}
+
+impl<K, V> IntoValue for std::collections::BTreeMap<K, V>
+where
+    K: Into<String>,
+    V: IntoValue,
+{
+    fn into_value(self) -> Result<Value> {
+        map_value(self)
     }
 }
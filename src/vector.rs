@@ -0,0 +1,144 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ArrayValue, MapValue, Value, value::ValueType,
+};
+
+use crate::{IntoValue, Result};
+
+/// A Firestore vector value.
+///
+/// A vector is encoded as a map value with `__type__` set to `"__vector__"` and `value` set to an array of doubles.
+///
+/// <https://firebase.google.com/docs/firestore/vector-search>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn example_vector() -> firestore_structured_query::Result<()> {
+/// use firestore_structured_query::{IntoValue, Vector};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+///     value::ValueType, ArrayValue, MapValue, Value,
+/// };
+/// let vector = Vector::new([1.0, 2.0]);
+/// assert_eq!(vector.values(), &[1.0, 2.0]);
+/// assert_eq!(
+///     vector.into_value()?,
+///     Value {
+///         value_type: Some(ValueType::MapValue(MapValue {
+///             fields: [
+///                 (
+///                     "__type__".to_string(),
+///                     Value {
+///                         value_type: Some(ValueType::StringValue("__vector__".to_string())),
+///                     },
+///                 ),
+///                 (
+///                     "value".to_string(),
+///                     Value {
+///                         value_type: Some(ValueType::ArrayValue(ArrayValue {
+///                             values: vec![
+///                                 Value {
+///                                     value_type: Some(ValueType::DoubleValue(1.0)),
+///                                 },
+///                                 Value {
+///                                     value_type: Some(ValueType::DoubleValue(2.0)),
+///                                 },
+///                             ],
+///                         })),
+///                     },
+///                 ),
+///             ]
+///             .into_iter()
+///             .collect(),
+///         })),
+///     }
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vector(Vec<f64>);
+
+impl Vector {
+    /// The maximum number of dimensions of a vector.
+    ///
+    /// <https://firebase.google.com/docs/firestore/vector-search#limitations>
+    pub const MAX_DIMENSION: usize = 2048;
+
+    /// Creates a new vector.
+    pub fn new<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        Self(values.into_iter().collect())
+    }
+
+    /// Returns the values of the vector.
+    pub fn values(&self) -> &[f64] {
+        &self.0
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.0.is_empty() {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::ValueConversion,
+                "vector must not be empty",
+            ));
+        }
+        if self.0.len() > Self::MAX_DIMENSION {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::ValueConversion,
+                format!(
+                    "vector dimension must be less than or equal to {} (actual: {})",
+                    Self::MAX_DIMENSION,
+                    self.0.len()
+                ),
+            ));
+        }
+        if self.0.iter().any(|v| !v.is_finite()) {
+            return Err(crate::Error::with_kind(
+                crate::ErrorKind::ValueConversion,
+                "vector values must be finite",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl std::convert::From<Vec<f64>> for Vector {
+    fn from(values: Vec<f64>) -> Self {
+        Self(values)
+    }
+}
+
+impl IntoValue for Vector {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value {
+            value_type: Some(ValueType::MapValue(MapValue {
+                fields: [
+                    (
+                        "__type__".to_string(),
+                        Value {
+                            value_type: Some(ValueType::StringValue("__vector__".to_string())),
+                        },
+                    ),
+                    (
+                        "value".to_string(),
+                        Value {
+                            value_type: Some(ValueType::ArrayValue(ArrayValue {
+                                values: self
+                                    .0
+                                    .into_iter()
+                                    .map(|v| Value {
+                                        value_type: Some(ValueType::DoubleValue(v)),
+                                    })
+                                    .collect(),
+                            })),
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            })),
+        })
+    }
+}
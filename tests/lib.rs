@@ -2,6 +2,7 @@
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_full_example_with_serde() -> firestore_structured_query::Result<()> {
     use firestore_structured_query::{FieldPath, Filter, Query, to_value};
     use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
@@ -181,6 +181,7 @@ fn test_field_path_array_contains() -> firestore_structured_query::Result<()> {
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_array_contains_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::array_contains (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -262,6 +263,7 @@ fn test_field_path_array_contains_any() -> firestore_structured_query::Result<()
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_array_contains_any_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::array_contains_any (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -367,6 +369,7 @@ fn test_field_path_equal() -> firestore_structured_query::Result<()> {
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_equal_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::equal (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -432,6 +435,7 @@ fn test_field_path_greater_than() -> firestore_structured_query::Result<()> {
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_greater_than_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::greater_than (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -497,6 +501,7 @@ fn test_field_path_greater_than_or_equal() -> firestore_structured_query::Result
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_greater_than_or_equal_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::greater_than_or_equal (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -578,6 +583,7 @@ fn test_field_path_in() -> firestore_structured_query::Result<()> {
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_in_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::r#in (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -743,6 +749,7 @@ fn test_field_path_less_than() -> firestore_structured_query::Result<()> {
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_less_than_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::less_than (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -808,6 +815,7 @@ fn test_field_path_less_than_or_equal() -> firestore_structured_query::Result<()
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_less_than_or_equal_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::less_than_or_equal (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -873,6 +881,7 @@ fn test_field_path_not_equal() -> firestore_structured_query::Result<()> {
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_not_equal_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::not_equal (for T: Serialize)
     use firestore_structured_query::FieldPath;
@@ -954,6 +963,7 @@ fn test_field_path_not_in() -> firestore_structured_query::Result<()> {
 
 #[cfg(feature = "serde")]
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_field_path_not_in_serialize() -> firestore_structured_query::Result<()> {
     // Added: FieldPath::not_in (for T: Serialize)
     use firestore_structured_query::FieldPath;
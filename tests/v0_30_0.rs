@@ -0,0 +1,3647 @@
+#![allow(missing_docs)]
+
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ArrayValue, Document, MapValue, Value, structured_query, value::ValueType,
+};
+
+fn value(value_type: ValueType) -> Value {
+    Value {
+        value_type: Some(value_type),
+    }
+}
+
+fn int(i: i64) -> Value {
+    value(ValueType::IntegerValue(i))
+}
+
+fn map(fields: Vec<(&str, Value)>) -> Value {
+    value(ValueType::MapValue(MapValue {
+        fields: fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    }))
+}
+
+fn double(d: f64) -> Value {
+    value(ValueType::DoubleValue(d))
+}
+
+fn string(s: &str) -> Value {
+    value(ValueType::StringValue(s.to_string()))
+}
+
+fn array(values: Vec<Value>) -> Value {
+    value(ValueType::ArrayValue(ArrayValue { values }))
+}
+
+// a document in the `(default)` database of the `p` project
+fn document(path: &str, fields: Vec<(&str, Value)>) -> Document {
+    Document {
+        name: format!("projects/p/databases/(default)/documents/{}", path),
+        fields: fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+        create_time: None,
+        update_time: None,
+    }
+}
+
+#[test]
+fn test_query_find_nearest() -> firestore_structured_query::Result<()> {
+    // Added: Query::find_nearest
+    // Added: FieldPath::find_nearest
+    // Added: FindNearest
+    // Added: DistanceMeasure
+    // Added: Vector
+    use firestore_structured_query::{DistanceMeasure, FieldPath, Query, Vector};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+        ArrayValue, MapValue, StructuredQuery, Value, structured_query, value::ValueType,
+    };
+    let query1 = Query::collection("collection_id1").find_nearest(
+        FieldPath::raw("embedding")
+            .find_nearest(Vector::new([1.0, 2.0]), DistanceMeasure::Cosine, 10)?
+            .distance_result_field(FieldPath::raw("distance"))
+            .distance_threshold(0.5),
+    );
+    assert_eq!(
+        StructuredQuery::from(query1),
+        StructuredQuery {
+            select: None,
+            from: vec![structured_query::CollectionSelector {
+                collection_id: "collection_id1".to_string(),
+                all_descendants: false,
+            }],
+            r#where: None,
+            order_by: vec![],
+            start_at: None,
+            end_at: None,
+            offset: 0_i32,
+            limit: None,
+            find_nearest: Some(structured_query::FindNearest {
+                vector_field: Some(structured_query::FieldReference {
+                    field_path: "embedding".to_string(),
+                }),
+                query_vector: Some(Value {
+                    value_type: Some(ValueType::MapValue(MapValue {
+                        fields: [
+                            (
+                                "__type__".to_string(),
+                                Value {
+                                    value_type: Some(ValueType::StringValue(
+                                        "__vector__".to_string()
+                                    )),
+                                },
+                            ),
+                            (
+                                "value".to_string(),
+                                Value {
+                                    value_type: Some(ValueType::ArrayValue(ArrayValue {
+                                        values: vec![
+                                            Value {
+                                                value_type: Some(ValueType::DoubleValue(1.0)),
+                                            },
+                                            Value {
+                                                value_type: Some(ValueType::DoubleValue(2.0)),
+                                            },
+                                        ],
+                                    })),
+                                },
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    })),
+                }),
+                distance_measure: structured_query::find_nearest::DistanceMeasure::Cosine as i32,
+                limit: Some(10),
+                distance_result_field: "distance".to_string(),
+                distance_threshold: Some(0.5),
+            }),
+        }
+    );
+
+    let vector_field = FieldPath::raw("embedding");
+    assert!(
+        vector_field
+            .find_nearest(Vector::new([]), DistanceMeasure::Euclidean, 1)
+            .is_err()
+    );
+    assert!(
+        vector_field
+            .find_nearest(Vector::new(vec![1.0; 2049]), DistanceMeasure::Euclidean, 1)
+            .is_err()
+    );
+    assert!(
+        vector_field
+            .find_nearest(Vector::new([f64::INFINITY]), DistanceMeasure::Euclidean, 1)
+            .is_err()
+    );
+    assert!(
+        vector_field
+            .find_nearest(Vector::new([1.0]), DistanceMeasure::Euclidean, -1)
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aggregation_query() -> firestore_structured_query::Result<()> {
+    // Added: Aggregation
+    // Added: AggregationQuery
+    use firestore_structured_query::{Aggregation, AggregationQuery, FieldPath, Query};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+        StructuredAggregationQuery, StructuredQuery, structured_aggregation_query, structured_query,
+    };
+    let query1 = Query::collection("collection_id1");
+    let aggregation_query1 = AggregationQuery::new(
+        query1.clone(),
+        [
+            Aggregation::count_up_to(10),
+            Aggregation::count().alias("field_1"),
+            Aggregation::sum(FieldPath::raw("field1")).alias("sum"),
+            Aggregation::avg(FieldPath::raw("field2")),
+        ],
+    )?;
+    assert_eq!(
+        StructuredAggregationQuery::from(aggregation_query1),
+        StructuredAggregationQuery {
+            aggregations: vec![
+                structured_aggregation_query::Aggregation {
+                    alias: "field_2".to_string(),
+                    operator: Some(structured_aggregation_query::aggregation::Operator::Count(
+                        structured_aggregation_query::aggregation::Count { up_to: Some(10) },
+                    )),
+                },
+                structured_aggregation_query::Aggregation {
+                    alias: "field_1".to_string(),
+                    operator: Some(structured_aggregation_query::aggregation::Operator::Count(
+                        structured_aggregation_query::aggregation::Count { up_to: None },
+                    )),
+                },
+                structured_aggregation_query::Aggregation {
+                    alias: "sum".to_string(),
+                    operator: Some(structured_aggregation_query::aggregation::Operator::Sum(
+                        structured_aggregation_query::aggregation::Sum {
+                            field: Some(structured_query::FieldReference {
+                                field_path: "field1".to_string(),
+                            }),
+                        },
+                    )),
+                },
+                structured_aggregation_query::Aggregation {
+                    alias: "field_3".to_string(),
+                    operator: Some(structured_aggregation_query::aggregation::Operator::Avg(
+                        structured_aggregation_query::aggregation::Avg {
+                            field: Some(structured_query::FieldReference {
+                                field_path: "field2".to_string(),
+                            }),
+                        },
+                    )),
+                },
+            ],
+            query_type: Some(structured_aggregation_query::QueryType::StructuredQuery(
+                StructuredQuery::from(query1.clone()),
+            )),
+        }
+    );
+
+    assert!(
+        AggregationQuery::new(
+            query1,
+            [
+                Aggregation::count().alias("a"),
+                Aggregation::count_up_to(1).alias("a"),
+            ],
+        )
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_request() -> firestore_structured_query::Result<()> {
+    // Added: QueryRequest
+    use firestore_structured_query::{Query, QueryRequest};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+        RunQueryRequest, StructuredQuery, TransactionOptions, run_query_request,
+        transaction_options,
+    };
+    let query1 = Query::collection("collection_id1");
+    let request1 = QueryRequest::new("project_id1", "documents", query1.clone())?
+        .parent_document("collection_id0/document_id0")?
+        .parent_document("collection_id2/document_id2")?
+        .read_time(prost_types::Timestamp {
+            seconds: 1,
+            nanos: 2,
+        })
+        .new_transaction(TransactionOptions {
+            mode: Some(transaction_options::Mode::ReadOnly(
+                transaction_options::ReadOnly {
+                    consistency_selector: None,
+                },
+            )),
+        });
+    assert_eq!(
+        RunQueryRequest::from(request1),
+        RunQueryRequest {
+            parent:
+                "projects/project_id1/databases/documents/documents/collection_id2/document_id2"
+                    .to_string(),
+            explain_options: None,
+            query_type: Some(run_query_request::QueryType::StructuredQuery(
+                StructuredQuery::from(query1),
+            )),
+            consistency_selector: Some(run_query_request::ConsistencySelector::NewTransaction(
+                TransactionOptions {
+                    mode: Some(transaction_options::Mode::ReadOnly(
+                        transaction_options::ReadOnly {
+                            consistency_selector: None,
+                        },
+                    )),
+                },
+            )),
+        }
+    );
+
+    // the parent document must not conflict with the parent of the query
+    let query2 = Query::collection("users/u1/posts");
+    let err = QueryRequest::new("project_id1", "(default)", query2.clone())?
+        .parent_document("users/u2")
+        .unwrap_err();
+    assert_eq!(err.kind(), firestore_structured_query::ErrorKind::Violation);
+    assert_eq!(
+        RunQueryRequest::from(
+            QueryRequest::new("project_id1", "(default)", query2)?.parent_document("users/u1")?
+        )
+        .parent,
+        "projects/project_id1/databases/(default)/documents/users/u1"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_request_new_invalid_parent() {
+    // Added: QueryRequest::new rejects a query whose parent is not a document path
+    use firestore_structured_query::{ErrorKind, Query, QueryRequest};
+    for collection_path in ["users/posts", "/posts"] {
+        let err =
+            QueryRequest::new("p", "(default)", Query::collection(collection_path)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFieldPath);
+    }
+    let err = QueryRequest::new("", "(default)", Query::collection("users")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidFieldPath);
+}
+
+// the users (and other documents) that the evaluation tests query
+fn evaluation_documents() -> Vec<Document> {
+    vec![
+        document(
+            "users/u1",
+            vec![
+                ("age", int(20)),
+                ("tags", array(vec![string("a"), string("b")])),
+                ("profile", map(vec![("x.y", string("p1"))])),
+            ],
+        ),
+        document(
+            "users/u2",
+            vec![
+                ("age", double(20.0)),
+                ("tags", array(vec![string("c")])),
+                ("profile", map(vec![("x.y", string("p2"))])),
+            ],
+        ),
+        document("users/u3", vec![("age", int(30))]),
+        document("users/u4", vec![("age", double(f64::NAN))]),
+        document("users/u5", vec![("name", string("no age"))]),
+        document("groups/g1/users/u6", vec![("age", int(10))]),
+        document("posts/p1", vec![("age", int(20))]),
+    ]
+}
+
+// the document IDs of the documents
+fn names(documents: Vec<Document>) -> Vec<String> {
+    documents
+        .into_iter()
+        .map(|d| d.name.rsplit('/').next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_evaluate() -> firestore_structured_query::Result<()> {
+    // Added: evaluate
+    use firestore_structured_query::{FieldPath, Query, evaluate};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let query = Query::collection("users")
+        .select([FieldPath::raw("profile.`x.y`")])
+        .r#where(FieldPath::raw("age").equal(int(20))?)
+        .limit(1);
+    assert_eq!(
+        evaluate(&StructuredQuery::from(query), evaluation_documents())?,
+        vec![document(
+            "users/u1",
+            vec![("profile", map(vec![("x.y", string("p1"))]))]
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_evaluate_where() -> firestore_structured_query::Result<()> {
+    // Added: Query::evaluate
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    let documents = evaluation_documents();
+
+    // implicit __name__ ordering and numeric comparison across integer and double
+    let query1 = Query::collection("users").r#where(FieldPath::raw("age").equal(int(20))?);
+    assert_eq!(names(query1.evaluate(documents.clone())?), vec!["u1", "u2"]);
+
+    // composite filters, array operators and nested backtick-escaped field paths
+    let query2 = Query::collection("users").r#where(Filter::or([
+        FieldPath::raw("tags").array_contains(string("c"))?,
+        Filter::and([
+            FieldPath::raw("tags").array_contains_any(array(vec![string("a"), string("z")]))?,
+            FieldPath::raw("profile.`x.y`").r#in(array(vec![string("p1")]))?,
+        ]),
+    ]));
+    assert_eq!(names(query2.evaluate(documents.clone())?), vec!["u1", "u2"]);
+
+    // inequality filters order by the inequality field, not_in excludes missing fields
+    let query3 =
+        Query::collection("users").r#where(FieldPath::raw("age").not_in(array(vec![int(20)]))?);
+    assert_eq!(names(query3.evaluate(documents)?), vec!["u4", "u3"]);
+    Ok(())
+}
+
+#[test]
+fn test_query_evaluate_order_by() -> firestore_structured_query::Result<()> {
+    // Added: Query::evaluate
+    use firestore_structured_query::{FieldPath, Query};
+    // collection group / NaN ordering / exclusion of documents without the order_by field
+    let query = Query::collection_group("users").order_by([FieldPath::raw("age").descending()]);
+    assert_eq!(
+        names(query.evaluate(evaluation_documents())?),
+        vec!["u3", "u2", "u1", "u6", "u4"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_evaluate_cursors() -> firestore_structured_query::Result<()> {
+    // Added: Query::evaluate
+    use firestore_structured_query::{FieldPath, Query};
+    let documents = evaluation_documents();
+    let query1 = Query::collection_group("users")
+        .order_by([FieldPath::raw("age").ascending()])
+        .start_after([int(10)])
+        .end_at([int(30)]);
+    assert_eq!(
+        names(query1.clone().evaluate(documents.clone())?),
+        vec!["u1", "u2", "u3"]
+    );
+    assert_eq!(
+        names(query1.offset(1).limit(1).evaluate(documents.clone())?),
+        vec!["u2"]
+    );
+    let query2 = Query::collection("users")
+        .order_by([FieldPath::raw("age").ascending()])
+        .start_at([
+            int(20),
+            value(ValueType::ReferenceValue(
+                "projects/p/databases/(default)/documents/users/u2".to_string(),
+            )),
+        ])
+        .end_before([int(30)]);
+    assert_eq!(names(query2.evaluate(documents)?), vec!["u2"]);
+    Ok(())
+}
+
+#[test]
+fn test_filter_matches() -> firestore_structured_query::Result<()> {
+    // Added: Filter::matches
+    use firestore_structured_query::FieldPath;
+    let documents = evaluation_documents();
+    assert!(FieldPath::raw("age").is_nan()?.matches(&documents[3])?);
+    assert!(!FieldPath::raw("age").is_not_nan()?.matches(&documents[3])?);
+    assert!(
+        !FieldPath::raw("age")
+            .is_not_null()?
+            .matches(&documents[4])?
+    );
+    Ok(())
+}
+
+// a Firestore vector value
+fn vector(values: Vec<f64>) -> Value {
+    map(vec![
+        ("__type__", string("__vector__")),
+        ("value", array(values.into_iter().map(double).collect())),
+    ])
+}
+
+#[test]
+#[allow(clippy::useless_conversion)]
+fn test_ordered_value_ord() {
+    // Added: OrderedValue (Ord)
+    use firestore_structured_query::OrderedValue;
+    use googleapis_tonic_google_firestore_v1::google::r#type::LatLng;
+    let expected = vec![
+        value(ValueType::NullValue(0)),
+        value(ValueType::BooleanValue(false)),
+        value(ValueType::BooleanValue(true)),
+        double(f64::NAN),
+        double(f64::NEG_INFINITY),
+        int(i64::MIN),
+        double(-0.5),
+        int(0),
+        double(0.5),
+        int(i64::MAX),
+        double(9_223_372_036_854_775_808.0),
+        value(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 1,
+            nanos: 0,
+        })),
+        value(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 1,
+            nanos: 1,
+        })),
+        string("Z"),
+        string("a"),
+        string("é"),
+        value(ValueType::BytesValue(vec![0].into())),
+        value(ValueType::BytesValue(vec![0, 0].into())),
+        value(ValueType::ReferenceValue(
+            "projects/p/databases/d/documents/a/b".to_string(),
+        )),
+        value(ValueType::ReferenceValue(
+            "projects/p/databases/d/documents/a/b/c/d".to_string(),
+        )),
+        value(ValueType::ReferenceValue(
+            "projects/p/databases/d/documents/a-/b".to_string(),
+        )),
+        value(ValueType::GeoPointValue(LatLng {
+            latitude: 0.0,
+            longitude: 1.0,
+        })),
+        value(ValueType::GeoPointValue(LatLng {
+            latitude: 1.0,
+            longitude: 0.0,
+        })),
+        array(vec![int(1)]),
+        array(vec![int(1), int(0)]),
+        array(vec![int(2)]),
+        vector(vec![9.0]),
+        vector(vec![1.0, 2.0]),
+        map(vec![("a", int(1))]),
+        map(vec![("a", int(1)), ("b", int(0))]),
+        map(vec![("a", int(2))]),
+        map(vec![("b", int(0))]),
+    ];
+    let mut actual = expected
+        .iter()
+        .rev()
+        .cloned()
+        .map(OrderedValue::new)
+        .collect::<Vec<OrderedValue>>();
+    actual.sort();
+    assert_eq!(
+        actual
+            .iter()
+            .map(|v| format!("{:?}", v.as_value()))
+            .collect::<Vec<String>>(),
+        expected
+            .iter()
+            .map(|v| format!("{:?}", v))
+            .collect::<Vec<String>>()
+    );
+}
+
+#[test]
+fn test_ordered_value_vector_before_map() {
+    // Added: OrderedValue (vectors sort before maps)
+    use firestore_structured_query::OrderedValue;
+    // even before the empty map that sorts first among the maps
+    assert!(OrderedValue::new(vector(vec![f64::MAX; 3])) < OrderedValue::new(map(vec![])));
+}
+
+#[test]
+fn test_ordered_value_eq_and_hash() {
+    // Added: OrderedValue (Eq and Hash)
+    use firestore_structured_query::OrderedValue;
+    let set = [
+        int(1),
+        double(1.0),
+        double(0.0),
+        double(-0.0),
+        double(f64::NAN),
+        double(f64::NAN),
+        map(vec![("a", int(1))]),
+        map(vec![("a", double(1.0))]),
+    ]
+    .into_iter()
+    .map(OrderedValue::new)
+    .collect::<std::collections::HashSet<OrderedValue>>();
+    assert_eq!(set.len(), 4);
+}
+
+// an array of `n` integers
+fn ints(n: i64) -> Value {
+    array((0..n).map(int).collect())
+}
+
+// `FieldPath` constructors reject invalid arguments, so build the invalid filters from protos
+fn unchecked(
+    field_path: &str,
+    op: structured_query::field_filter::Operator,
+    value: Value,
+) -> firestore_structured_query::Result<firestore_structured_query::Filter> {
+    firestore_structured_query::Filter::try_from(structured_query::Filter {
+        filter_type: Some(structured_query::filter::FilterType::FieldFilter(
+            structured_query::FieldFilter {
+                field: Some(structured_query::FieldReference {
+                    field_path: field_path.to_string(),
+                }),
+                op: op as i32,
+                value: Some(value),
+            },
+        )),
+    })
+}
+
+// the locations of the violations of the query
+fn locations(query: &firestore_structured_query::Query) -> Vec<String> {
+    query
+        .violations()
+        .into_iter()
+        .map(|violation| violation.location().to_string())
+        .collect()
+}
+
+#[test]
+fn test_query_validate() -> firestore_structured_query::Result<()> {
+    // Added: Query::validate
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    let query = Query::collection("users")
+        .r#where(Filter::and([
+            FieldPath::raw("a").r#in(ints(30))?,
+            FieldPath::raw("b").not_in(ints(10))?,
+            FieldPath::raw("c").array_contains(int(1))?,
+            FieldPath::raw("d").greater_than(int(1))?,
+        ]))
+        .start_at([int(1), int(2), int(3)])
+        .end_at([int(1)])
+        .limit(0);
+    assert!(query.validate().is_ok());
+    assert!(
+        Query::collection("users")
+            .r#where(Filter::or([
+                FieldPath::raw("a").array_contains(int(1))?,
+                FieldPath::raw("b").array_contains_any(ints(2))?,
+            ]))
+            .validate()
+            .is_ok()
+    );
+    for collection_id in ["", ".", "..", "__a__"] {
+        assert!(Query::collection(collection_id).validate().is_err());
+    }
+    for collection_id in ["__", "a.b", "__a"] {
+        assert!(Query::collection(collection_id).validate().is_ok());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_query_violations_array_values() -> firestore_structured_query::Result<()> {
+    // Added: Query::violations (the values of in, not_in and array_contains_any)
+    use firestore_structured_query::{Filter, Query};
+    use structured_query::field_filter::Operator;
+    assert_eq!(
+        locations(&Query::collection("users").r#where(Filter::and([
+            unchecked("a", Operator::In, ints(31))?,
+            unchecked("b", Operator::ArrayContainsAny, int(1))?,
+            unchecked("c", Operator::In, ints(0))?,
+        ]))),
+        [
+            "where.filters[0]",
+            "where.filters[1]",
+            "where.filters[2]",
+            "where"
+        ]
+    );
+    assert_eq!(
+        locations(&Query::collection("users").r#where(unchecked("a", Operator::NotIn, ints(11))?)),
+        ["where"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_violations_array_contains() -> firestore_structured_query::Result<()> {
+    // Added: Query::violations (at most one array_contains)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    assert_eq!(
+        locations(&Query::collection("users").r#where(Filter::and([
+            FieldPath::raw("a").array_contains(int(1))?,
+            Filter::or([
+                FieldPath::raw("b").array_contains(int(1))?,
+                FieldPath::raw("c").equal(int(1))?,
+            ]),
+        ]))),
+        ["where"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_violations_not_in() -> firestore_structured_query::Result<()> {
+    // Added: Query::violations (at most one not_in)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    assert_eq!(
+        locations(&Query::collection("users").r#where(Filter::or([
+            FieldPath::raw("a").not_in(ints(1))?,
+            FieldPath::raw("b").not_in(ints(1))?,
+        ]))),
+        ["where.filters[1]"]
+    );
+    assert!(
+        Query::collection("users")
+            .r#where(Filter::and([
+                FieldPath::raw("a").not_equal(int(1))?,
+                FieldPath::raw("b").not_equal(int(1))?,
+            ]))
+            .validate()
+            .is_ok()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_violations_disjunctions() -> firestore_structured_query::Result<()> {
+    // Added: Query::violations (at most 30 disjunctions)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    let filter = Filter::and([
+        FieldPath::raw("a").r#in(ints(6))?,
+        FieldPath::raw("b").r#in(ints(5))?,
+    ]);
+    assert!(
+        Query::collection("users")
+            .r#where(filter.clone())
+            .validate()
+            .is_ok()
+    );
+    assert_eq!(
+        locations(
+            &Query::collection("users")
+                .r#where(Filter::or([filter, FieldPath::raw("c").equal(int(1))?]))
+        ),
+        ["where"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_violations_collection_id_cursors_offset_and_limit()
+-> firestore_structured_query::Result<()> {
+    // Added: Query::violations (the collection ID, cursors, offset and limit)
+    use firestore_structured_query::{FieldPath, Query};
+    assert_eq!(
+        locations(
+            &Query::collection_group("a/b")
+                .order_by([FieldPath::raw("a").ascending()])
+                .start_after([int(1), int(2), int(3)])
+                .end_before([int(1), int(2)])
+                .offset(-1)
+                .limit(-1)
+        ),
+        ["from[0].collection_id", "start_at", "offset", "limit"]
+    );
+    Ok(())
+}
+
+// the (field path, direction) pairs of the normalized orders of the query
+fn normalized_order_by(
+    query: firestore_structured_query::Query,
+) -> firestore_structured_query::Result<Vec<(String, i32)>> {
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    Ok(StructuredQuery::from(query.normalize_order_by()?)
+        .order_by
+        .into_iter()
+        .map(|order| (order.field.unwrap().field_path, order.direction))
+        .collect())
+}
+
+#[test]
+fn test_query_normalize_order_by_inequality_fields() -> firestore_structured_query::Result<()> {
+    // Added: Query::normalize_order_by (multiple inequality fields nested in composite filters and sorted by segments)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    let asc = structured_query::Direction::Ascending as i32;
+    assert_eq!(
+        normalized_order_by(Query::collection("c").r#where(Filter::or([
+            Filter::and([
+                FieldPath::raw("b.c").is_not_null()?,
+                FieldPath::raw("`a`").greater_than_or_equal(int(1))?,
+            ]),
+            FieldPath::new(["b", "a"]).not_in(array(vec![int(1)]))?,
+            FieldPath::raw("e").is_nan()?,
+            FieldPath::raw("f").array_contains(int(1))?,
+        ])))?,
+        [
+            ("`a`".to_string(), asc),
+            ("b.a".to_string(), asc),
+            ("b.c".to_string(), asc),
+            ("__name__".to_string(), asc),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_normalize_order_by_existing_orders() -> firestore_structured_query::Result<()> {
+    // Added: Query::normalize_order_by (fields already present in order_by, including __name__)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    let asc = structured_query::Direction::Ascending as i32;
+    let desc = structured_query::Direction::Descending as i32;
+    assert_eq!(
+        normalized_order_by(
+            Query::collection("c")
+                .r#where(Filter::and([
+                    FieldPath::raw("a").less_than(int(1))?,
+                    FieldPath::raw("b").is_not_nan()?,
+                ]))
+                .order_by([
+                    FieldPath::raw("`b`").ascending(),
+                    FieldPath::raw("__name__").descending(),
+                ])
+        )?,
+        [
+            ("`b`".to_string(), asc),
+            ("__name__".to_string(), desc),
+            ("a".to_string(), desc),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_normalize_order_by_name_inequality() -> firestore_structured_query::Result<()> {
+    // Added: Query::normalize_order_by (inequality on __name__)
+    use firestore_structured_query::{FieldPath, Query};
+    let desc = structured_query::Direction::Descending as i32;
+    assert_eq!(
+        normalized_order_by(
+            Query::collection("c")
+                .r#where(FieldPath::raw("__name__").not_equal(int(1))?)
+                .order_by([FieldPath::raw("a").descending()])
+        )?,
+        [("a".to_string(), desc), ("__name__".to_string(), desc)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_normalize_order_by_idempotent() -> firestore_structured_query::Result<()> {
+    // Added: Query::normalize_order_by (idempotent)
+    use firestore_structured_query::{FieldPath, Query};
+    let query = Query::collection("c")
+        .r#where(FieldPath::raw("a").less_than(int(1))?)
+        .normalize_order_by()?;
+    assert_eq!(query.clone().normalize_order_by()?, query);
+    Ok(())
+}
+
+#[test]
+fn test_query_normalize_order_by_invalid_field_path() {
+    // Added: Query::normalize_order_by (invalid field path)
+    use firestore_structured_query::{FieldPath, Query};
+    assert!(
+        Query::collection("c")
+            .order_by([FieldPath::raw("a..b").ascending()])
+            .normalize_order_by()
+            .is_err()
+    );
+}
+
+// a user document with a nested `stats.high score` field
+fn high_score_user(id: &str, score: i64) -> Document {
+    document(
+        &format!("users/{}", id),
+        vec![("stats", map(vec![("high score", int(score))]))],
+    )
+}
+
+fn high_score_users() -> Vec<Document> {
+    vec![
+        high_score_user("u1", 10),
+        high_score_user("u2", 30),
+        high_score_user("u3", 20),
+        high_score_user("u4", 20),
+        high_score_user("u5", 40),
+    ]
+}
+
+#[test]
+fn test_query_start_after_document() -> firestore_structured_query::Result<()> {
+    // Added: Query::start_after_document
+    use firestore_structured_query::{FieldPath, Query};
+    let documents = high_score_users();
+    let query = Query::collection("users")
+        .order_by([FieldPath::new(["stats", "high score"]).descending()])
+        .limit(2);
+    let page1 = query.evaluate(documents.clone())?;
+    assert_eq!(
+        page1,
+        vec![high_score_user("u5", 40), high_score_user("u2", 30)]
+    );
+    let page2 = query
+        .clone()
+        .start_after_document(page1.last().unwrap())?
+        .evaluate(documents.clone())?;
+    assert_eq!(
+        page2,
+        vec![high_score_user("u4", 20), high_score_user("u3", 20)]
+    );
+    let page3 = query
+        .clone()
+        .start_after_document(page2.last().unwrap())?
+        .evaluate(documents.clone())?;
+    assert_eq!(page3, vec![high_score_user("u1", 10)]);
+    Ok(())
+}
+
+#[test]
+fn test_query_start_at_document_and_end_at_document() -> firestore_structured_query::Result<()> {
+    // Added: Query::end_at_document, Query::start_at_document
+    use firestore_structured_query::{FieldPath, Query};
+    let query =
+        Query::collection("users").order_by([FieldPath::new(["stats", "high score"]).descending()]);
+    assert_eq!(
+        query
+            .start_at_document(&high_score_user("u4", 20))?
+            .end_at_document(&high_score_user("u3", 20))?
+            .evaluate(high_score_users())?,
+        vec![high_score_user("u4", 20), high_score_user("u3", 20)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_end_before_document() -> firestore_structured_query::Result<()> {
+    // Added: Query::end_before_document
+    use firestore_structured_query::{FieldPath, Query};
+    let query =
+        Query::collection("users").order_by([FieldPath::new(["stats", "high score"]).descending()]);
+    assert_eq!(
+        query
+            .start_at_document(&high_score_user("u2", 30))?
+            .end_before_document(&high_score_user("u3", 20))?
+            .evaluate(high_score_users())?,
+        vec![high_score_user("u2", 30), high_score_user("u4", 20)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_cursor_document_missing_field() {
+    // Added: Query::start_after_document (the document does not contain an order field)
+    use firestore_structured_query::{FieldPath, Query};
+    let err = Query::collection("users")
+        .order_by([FieldPath::raw("stats.rank").ascending()])
+        .start_after_document(&high_score_user("u1", 10))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "document projects/p/databases/(default)/documents/users/u1 does not contain the field stats.rank"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct Stats {
+    score: i64,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct User {
+    name: String,
+    stats: Stats,
+}
+
+#[cfg(feature = "serde")]
+fn alice() -> User {
+    User {
+        name: "alice".to_string(),
+        stats: Stats { score: 10 },
+    }
+}
+
+#[cfg(feature = "serde")]
+const ALICE_NAME: &str = "projects/p/databases/(default)/documents/users/u1";
+
+#[cfg(feature = "serde")]
+fn score_and_name_query() -> firestore_structured_query::Query {
+    use firestore_structured_query::{FieldPath, Query};
+    Query::collection("users").order_by([
+        FieldPath::raw("stats.score").ascending(),
+        FieldPath::raw("name").descending(),
+    ])
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_query_end_before_struct() -> firestore_structured_query::Result<()> {
+    // Added: Query::end_before_struct
+    use firestore_structured_query::to_value;
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::{Cursor, StructuredQuery};
+    assert_eq!(
+        StructuredQuery::from(score_and_name_query().end_before_struct(&alice(), ALICE_NAME)?)
+            .end_at,
+        Some(Cursor {
+            values: vec![
+                to_value(&10)?,
+                to_value(&"alice")?,
+                value(ValueType::ReferenceValue(ALICE_NAME.to_string())),
+            ],
+            before: true,
+        })
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_query_start_at_struct_not_a_map() {
+    // Added: Query::start_at_struct (the struct is not serialized to a map)
+    assert!(
+        score_and_name_query()
+            .start_at_struct(&1, ALICE_NAME)
+            .is_err()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_query_start_after_struct_missing_field() {
+    // Added: Query::start_after_struct (the struct does not contain an order field)
+    use firestore_structured_query::FieldPath;
+    assert!(
+        score_and_name_query()
+            .order_by([FieldPath::raw("age").ascending()])
+            .start_after_struct(&alice(), ALICE_NAME)
+            .is_err()
+    );
+}
+
+// seven user documents with scores 1, 2, 0, 1, 2, 0, 1
+fn scored_users() -> Vec<Document> {
+    (1..=7)
+        .map(|i| document(&format!("users/u{}", i), vec![("score", int(i % 3))]))
+        .collect()
+}
+
+fn scored_users_query() -> firestore_structured_query::Query {
+    use firestore_structured_query::{FieldPath, Query};
+    Query::collection("users").order_by([FieldPath::raw("score").descending()])
+}
+
+#[test]
+fn test_query_limit_to_last() -> firestore_structured_query::Result<()> {
+    // Added: Query::limit_to_last
+    let documents = scored_users();
+    let query = scored_users_query();
+    let all = query.evaluate(documents.clone())?;
+    let page1 = query.clone().limit(3).evaluate(documents.clone())?;
+    assert_eq!(page1, all[0..3]);
+    let page2 = query
+        .clone()
+        .start_after_document(page1.last().unwrap())?
+        .limit(3)
+        .evaluate(documents.clone())?;
+    assert_eq!(page2, all[3..6]);
+
+    // previous page
+    let previous = query
+        .clone()
+        .end_before_document(page2.first().unwrap())?
+        .limit_to_last(3)
+        .evaluate(documents.clone())?;
+    assert_eq!(previous, page1);
+    let previous = query
+        .clone()
+        .end_before_document(&all[2])?
+        .limit_to_last(3)
+        .evaluate(documents.clone())?;
+    assert_eq!(previous, all[0..2]);
+    Ok(())
+}
+
+#[test]
+fn test_query_limit_clears_limit_to_last() -> firestore_structured_query::Result<()> {
+    // Added: Query::limit_to_last (overwritten by Query::limit)
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let documents = scored_users();
+    let all = scored_users_query().evaluate(documents.clone())?;
+    let query = scored_users_query().limit_to_last(2).limit(2);
+    assert_eq!(query.evaluate(documents)?, all[0..2]);
+    assert_eq!(
+        StructuredQuery::from(query).order_by,
+        StructuredQuery::from(scored_users_query()).order_by
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_limit_to_last_invalid_field_path() -> firestore_structured_query::Result<()> {
+    // Added: Query::limit_to_last (the conversion still adds the implicit orders, the fallible APIs return the error)
+    use firestore_structured_query::{FieldPath, Query, QueryRequest};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let query = Query::collection("users")
+        .r#where(FieldPath::raw("a.`b").greater_than(1)?)
+        .limit_to_last(3);
+    assert_eq!(
+        StructuredQuery::from(query.clone())
+            .order_by
+            .iter()
+            .map(|order| order.field.as_ref().unwrap().field_path.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["a.`b", "__name__"]
+    );
+    assert!(query.clone().reversed().is_err());
+    assert!(query.evaluate(scored_users()).is_err());
+    assert!(QueryRequest::new("p", "(default)", query).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_query_reversed() -> firestore_structured_query::Result<()> {
+    // Added: Query::reversed
+    let documents = scored_users();
+    let query = scored_users_query();
+    let all = query.evaluate(documents.clone())?;
+    let mut reversed_all = all.clone();
+    reversed_all.reverse();
+    assert_eq!(
+        query.clone().reversed()?.evaluate(documents.clone())?,
+        reversed_all
+    );
+    assert_eq!(
+        query
+            .start_at_document(&all[1])?
+            .end_before_document(&all[4])?
+            .reversed()?
+            .evaluate(documents)?,
+        reversed_all[3..6]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_reversed_invalid_field_path() {
+    // Added: Query::reversed (invalid field path)
+    use firestore_structured_query::{FieldPath, Query};
+    assert!(
+        Query::collection("users")
+            .order_by([FieldPath::raw("a..b").ascending()])
+            .reversed()
+            .is_err()
+    );
+}
+
+// asserts that the text format of the query parses back to the query
+fn round_trip(query: &firestore_structured_query::Query) -> firestore_structured_query::Result<()> {
+    use firestore_structured_query::Query;
+    let text = query.to_string();
+    let parsed = text.parse::<Query>()?;
+    assert_eq!(&parsed, query, "{}", text);
+    assert_eq!(parsed.to_string(), text);
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::useless_conversion)]
+fn test_query_display_values() -> firestore_structured_query::Result<()> {
+    // Added: impl Display for Query, impl FromStr for Query (every value type)
+    use firestore_structured_query::{OrderedValue, Query, Vector};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    use googleapis_tonic_google_firestore_v1::google::r#type::LatLng;
+    let values = vec![
+        value(ValueType::NullValue(0)),
+        value(ValueType::BooleanValue(true)),
+        value(ValueType::BooleanValue(false)),
+        value(ValueType::IntegerValue(i64::MIN)),
+        value(ValueType::IntegerValue(i64::MAX)),
+        value(ValueType::DoubleValue(1.5)),
+        value(ValueType::DoubleValue(-0.0)),
+        value(ValueType::DoubleValue(1e300)),
+        value(ValueType::DoubleValue(f64::NAN)),
+        value(ValueType::DoubleValue(f64::INFINITY)),
+        value(ValueType::DoubleValue(f64::NEG_INFINITY)),
+        value(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 1_700_000_000,
+            nanos: 123_000_000,
+        })),
+        value(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: -62_135_596_800,
+            nanos: 1,
+        })),
+        value(ValueType::StringValue("a\"b\\c\n\t\u{1}é😀".to_string())),
+        value(ValueType::BytesValue(vec![0, 1, 2, 254, 255].into())),
+        value(ValueType::ReferenceValue(
+            "projects/p/databases/(default)/documents/users/u1".to_string(),
+        )),
+        value(ValueType::GeoPointValue(LatLng {
+            latitude: 35.5,
+            longitude: -139.0,
+        })),
+        value(ValueType::ArrayValue(ArrayValue {
+            values: vec![
+                value(ValueType::IntegerValue(1)),
+                value(ValueType::ArrayValue(ArrayValue { values: vec![] })),
+            ],
+        })),
+        value(ValueType::MapValue(MapValue {
+            fields: [
+                ("b".to_string(), value(ValueType::IntegerValue(2))),
+                ("a\"".to_string(), value(ValueType::NullValue(0))),
+            ]
+            .into_iter()
+            .collect(),
+        })),
+        firestore_structured_query::IntoValue::into_value(Vector::new([1.0, -2.5]))?,
+    ];
+    let query = Query::collection("users").start_at(values.clone());
+    let text = query.to_string();
+    let parsed = text.parse::<Query>()?;
+    assert_eq!(parsed.to_string(), text);
+    let parsed_values = StructuredQuery::from(parsed).start_at.unwrap().values;
+    assert_eq!(parsed_values.len(), values.len());
+    for (parsed_value, value) in parsed_values.into_iter().zip(values) {
+        // NaN != NaN
+        assert_eq!(OrderedValue::new(parsed_value), OrderedValue::new(value));
+    }
+    assert_eq!(
+        query.to_string(),
+        concat!(
+            r#"SELECT * FROM users START AT (NULL, TRUE, FALSE, -9223372036854775808, 9223372036854775807, "#,
+            r#"1.5, -0.0, 1e300, NaN, Infinity, -Infinity, TIMESTAMP("2023-11-14T22:13:20.123Z"), "#,
+            r#"TIMESTAMP("0001-01-01T00:00:00.000000001Z"), "a\"b\\c\n\t\u{1}é😀", BYTES("AAEC/v8="), "#,
+            r#"REF("projects/p/databases/(default)/documents/users/u1"), GEOPOINT(35.5, -139.0), "#,
+            r#"[1, []], {"a\"": NULL, "b": 2}, VECTOR([1.0, -2.5]))"#
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_display_filters() -> firestore_structured_query::Result<()> {
+    // Added: impl Display for Query, impl FromStr for Query (every operator, field paths and composite filters)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    let query = Query::collection_group("user-profiles")
+        .select([
+            FieldPath::raw("a"),
+            FieldPath::new(["b", "x&y", "`"]),
+            FieldPath::raw("limit"),
+        ])
+        .r#where(Filter::or([
+            Filter::and([
+                FieldPath::raw("f1").less_than(int(1))?,
+                FieldPath::raw("f2").less_than_or_equal(int(2))?,
+                FieldPath::raw("f3").greater_than(int(3))?,
+                FieldPath::raw("f4").greater_than_or_equal(int(4))?,
+                FieldPath::raw("f5").equal(int(5))?,
+                FieldPath::raw("f6").not_equal(int(6))?,
+            ]),
+            Filter::and([
+                FieldPath::raw("f7").array_contains(int(7))?,
+                FieldPath::raw("f8").r#in(array(vec![int(8)]))?,
+                FieldPath::raw("f9").array_contains_any(array(vec![int(9), int(10)]))?,
+                FieldPath::raw("f10").not_in(array(vec![int(11)]))?,
+                Filter::or([
+                    FieldPath::raw("f11").is_nan()?,
+                    FieldPath::raw("f12").is_not_nan()?,
+                ]),
+            ]),
+            Filter::and([
+                FieldPath::raw("f13").is_null()?,
+                FieldPath::raw("__name__").is_not_null()?,
+            ]),
+            Filter::and([FieldPath::raw("order").equal(int(1))?]),
+            Filter::or([]),
+        ]))
+        .order_by([
+            FieldPath::raw("f1").ascending(),
+            FieldPath::raw("f2").descending(),
+        ])
+        .start_after([int(1)])
+        .end_before([int(2), int(3)])
+        .offset(5)
+        .limit_to_last(10);
+    round_trip(&query)?;
+    assert_eq!(
+        query.to_string(),
+        concat!(
+            "SELECT a, b.`x&y`.`\\``, `limit` FROM COLLECTION GROUP user-profiles WHERE ",
+            "(f1 < 1 AND f2 <= 2 AND f3 > 3 AND f4 >= 4 AND f5 = 5 AND f6 != 6) OR ",
+            "(f7 ARRAY_CONTAINS 7 AND f8 IN [8] AND f9 ARRAY_CONTAINS_ANY [9, 10] AND f10 NOT IN [11] AND (f11 IS NAN OR f12 IS NOT NAN)) OR ",
+            "(f13 IS NULL AND __name__ IS NOT NULL) OR AND(`order` = 1) OR OR() ",
+            "ORDER BY f1 ASC, f2 DESC START AFTER (1) END BEFORE (2, 3) LIMIT TO LAST 10 OFFSET 5"
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_display_find_nearest() -> firestore_structured_query::Result<()> {
+    // Added: impl Display for Query, impl FromStr for Query (find_nearest and empty projection)
+    use firestore_structured_query::{DistanceMeasure, FieldPath, Query, Vector};
+    let query = Query::collection("`users`")
+        .select(Vec::<FieldPath>::new())
+        .r#where(FieldPath::raw("a").equal(int(1))?)
+        .find_nearest(
+            FieldPath::raw("embedding")
+                .find_nearest(Vector::new([1.0, 2.0]), DistanceMeasure::DotProduct, 10)?
+                .distance_result_field(FieldPath::raw("distance"))
+                .distance_threshold(0.5),
+        )
+        .order_by([FieldPath::raw("a").ascending()])
+        .limit(3);
+    round_trip(&query)?;
+    round_trip(&query.clone().order_by([structured_query::Order {
+        field: Some(structured_query::FieldReference {
+            field_path: "a".to_string(),
+        }),
+        direction: structured_query::Direction::Unspecified as i32,
+    }]))?;
+    assert_eq!(
+        query.to_string(),
+        "SELECT FROM `\\`users\\`` WHERE a = 1 FIND NEAREST embedding VECTOR([1.0, 2.0]) USING DOT_PRODUCT LIMIT 10 DISTANCE RESULT FIELD `distance` DISTANCE THRESHOLD 0.5 ORDER BY a ASC LIMIT 3"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_from_str_lenient() -> firestore_structured_query::Result<()> {
+    // Added: impl FromStr for Query (lenient input)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    assert_eq!(
+        "select *  from users where a == 1 and `b`.c in [1, 2.0] or d is not null order by a, `b` desc limit 1 offset 2 start at (1)"
+            .parse::<Query>()?,
+        Query::collection("users")
+            .r#where(Filter::or([
+                Filter::and([
+                    FieldPath::raw("a").equal(int(1))?,
+                    FieldPath::raw("b.c").r#in(value(ValueType::ArrayValue(ArrayValue {
+                        values: vec![int(1), value(ValueType::DoubleValue(2.0))]
+                    })))?,
+                ]),
+                FieldPath::raw("d").is_not_null()?,
+            ]))
+            .order_by([
+                // a missing direction is kept unspecified
+                structured_query::Order {
+                    field: Some(structured_query::FieldReference {
+                        field_path: "a".to_string(),
+                    }),
+                    direction: structured_query::Direction::Unspecified as i32,
+                },
+                structured_query::Order::from(FieldPath::raw("b").descending()),
+            ])
+            .start_at([int(1)])
+            .limit(1)
+            .offset(2)
+    );
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::useless_conversion)]
+fn test_query_from_str_literals() -> firestore_structured_query::Result<()> {
+    // Added: impl FromStr for Query (TIMESTAMP, BYTES and escaped strings)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    assert_eq!(
+        StructuredQuery::from(
+            r#"SELECT * FROM c WHERE t = TIMESTAMP("2006-01-02T15:04:05.5+07:00") AND b = BYTES("QUI") AND s = "\u{1F600}""#
+                .parse::<Query>()?
+        )
+        .r#where,
+        Some(structured_query::Filter::from(Filter::and([
+            FieldPath::raw("t").equal(value(ValueType::TimestampValue(
+                prost_types::Timestamp {
+                    seconds: 1_136_189_045,
+                    nanos: 500_000_000
+                }
+            )))?,
+            FieldPath::raw("b").equal(value(ValueType::BytesValue(b"AB".to_vec().into())))?,
+            FieldPath::raw("s").equal(value(ValueType::StringValue("😀".to_string())))?,
+        ])))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_from_str_errors() {
+    // Added: impl FromStr for Query (errors)
+    use firestore_structured_query::Query;
+    for (text, message) in [
+        (
+            "SELECT * FROM",
+            "expected collection ID at position 13, found end of input",
+        ),
+        (
+            "SELECT * FROM users WHERE a",
+            "expected operator at position 27, found end of input",
+        ),
+        (
+            "SELECT * FROM users LIMIT 1 LIMIT 2",
+            "duplicate LIMIT clause at position 28",
+        ),
+        (
+            "SELECT * FROM users LIMIT 2147483648",
+            "expected 32-bit integer at position 26, found \"2147483648\"",
+        ),
+        (
+            "SELECT * FROM users WHERE a = 9223372036854775808",
+            "expected 64-bit integer at position 30, found \"9223372036854775\"",
+        ),
+        (
+            "SELECT * FROM users WHERE a = \"b",
+            "expected closing \" at position 32, found end of input",
+        ),
+        (
+            "SELECT * FROM users WHERE a = TIMESTAMP(\"10000-01-01T00:00:00Z\")",
+            "invalid timestamp: 10000-01-01T00:00:00Z at position 40",
+        ),
+        (
+            "SELECT * FROM users WHERE a = BYTES(\"QQ=\")",
+            "invalid base64: QQ= at position 36",
+        ),
+        (
+            "SELECT * FROM users ORDER BY a DESC extra",
+            "expected end of input at position 36, found \"extra\"",
+        ),
+    ] {
+        assert_eq!(
+            text.parse::<Query>().unwrap_err().to_string(),
+            message,
+            "{}",
+            text
+        );
+    }
+}
+
+fn field_reference(field_path: &str) -> structured_query::FieldReference {
+    structured_query::FieldReference {
+        field_path: field_path.to_string(),
+    }
+}
+
+fn field_filter_proto(op: i32, value: Option<Value>) -> structured_query::Filter {
+    structured_query::Filter {
+        filter_type: Some(structured_query::filter::FilterType::FieldFilter(
+            structured_query::FieldFilter {
+                field: Some(field_reference("a")),
+                op,
+                value,
+            },
+        )),
+    }
+}
+
+fn composite_filter_proto(
+    op: i32,
+    filters: Vec<structured_query::Filter>,
+) -> structured_query::Filter {
+    structured_query::Filter {
+        filter_type: Some(structured_query::filter::FilterType::CompositeFilter(
+            structured_query::CompositeFilter { op, filters },
+        )),
+    }
+}
+
+fn null() -> Value {
+    value(ValueType::NullValue(0))
+}
+
+// a query that uses every part of StructuredQuery
+fn full_query() -> firestore_structured_query::Result<firestore_structured_query::Query> {
+    "SELECT a, `b c` FROM COLLECTION GROUP users WHERE (a = 1 OR b IN [1, 2]) AND c IS NOT NULL FIND NEAREST e VECTOR([1.0]) USING COSINE LIMIT 3 ORDER BY a DESC START AT (1) END BEFORE (2) LIMIT 10 OFFSET 1"
+        .parse::<firestore_structured_query::Query>()
+}
+
+#[test]
+fn test_query_try_from_structured_query() -> firestore_structured_query::Result<()> {
+    // Added: impl TryFrom<StructuredQuery> for Query
+    use firestore_structured_query::Query;
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let query = full_query()?;
+    assert_eq!(
+        Query::try_from(StructuredQuery::from(query.clone()))?,
+        query
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_try_from_structured_query_invalid() -> firestore_structured_query::Result<()> {
+    // Added: impl TryFrom<StructuredQuery> for Query (invalid queries)
+    use firestore_structured_query::Query;
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let base = StructuredQuery::from(Query::collection("users"));
+    let mut find_nearest = StructuredQuery::from(full_query()?).find_nearest.unwrap();
+    find_nearest.distance_measure = 0;
+    let invalid = vec![
+        StructuredQuery {
+            from: vec![],
+            ..base.clone()
+        },
+        StructuredQuery {
+            from: [base.from.clone(), base.from.clone()].concat(),
+            ..base.clone()
+        },
+        StructuredQuery {
+            select: Some(structured_query::Projection {
+                fields: vec![field_reference("")],
+            }),
+            ..base.clone()
+        },
+        StructuredQuery {
+            r#where: Some(field_filter_proto(100, Some(null()))),
+            ..base.clone()
+        },
+        StructuredQuery {
+            order_by: vec![structured_query::Order {
+                field: Some(field_reference("a")),
+                direction: -1,
+            }],
+            ..base.clone()
+        },
+        StructuredQuery {
+            find_nearest: Some(find_nearest),
+            ..base.clone()
+        },
+    ];
+    for structured_query in invalid {
+        assert!(Query::try_from(structured_query).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_query_try_from_structured_query_limit_to_last() -> firestore_structured_query::Result<()> {
+    // Added: impl TryFrom<StructuredQuery> for Query (limit_to_last is converted into the reversed query)
+    use firestore_structured_query::{FieldPath, Query};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let query = Query::collection("users")
+        .order_by([FieldPath::raw("a").ascending()])
+        .limit_to_last(1);
+    assert_eq!(
+        Query::try_from(StructuredQuery::from(query))?,
+        Query::collection("users")
+            .order_by([
+                FieldPath::raw("a").descending(),
+                FieldPath::raw("__name__").descending()
+            ])
+            .limit(1)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_order_try_from() -> firestore_structured_query::Result<()> {
+    // Added: impl TryFrom<structured_query::Order> for Order
+    use firestore_structured_query::Order;
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    for order in StructuredQuery::from(full_query()?).order_by {
+        assert_eq!(
+            structured_query::Order::from(Order::try_from(order.clone())?),
+            order
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_field_path_try_from_field_reference() -> firestore_structured_query::Result<()> {
+    // Added: impl TryFrom<structured_query::FieldReference> for FieldPath
+    use firestore_structured_query::FieldPath;
+    assert_eq!(
+        FieldPath::try_from(field_reference("a.`b c`"))?,
+        FieldPath::new(["a", "b c"])
+    );
+    assert!(FieldPath::try_from(field_reference("a.`b")).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_filter_try_from() -> firestore_structured_query::Result<()> {
+    // Added: impl TryFrom<structured_query::Filter> for Filter
+    use firestore_structured_query::Filter;
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let filter = StructuredQuery::from(full_query()?).r#where.unwrap();
+    assert_eq!(
+        structured_query::Filter::from(Filter::try_from(filter.clone())?),
+        filter
+    );
+    let equal = structured_query::field_filter::Operator::Equal as i32;
+    let and = structured_query::composite_filter::Operator::And as i32;
+    assert!(Filter::try_from(field_filter_proto(equal, Some(null()))).is_ok());
+    assert!(Filter::try_from(composite_filter_proto(and, vec![])).is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_filter_try_from_invalid() {
+    // Added: impl TryFrom<structured_query::Filter> for Filter (invalid filters)
+    use firestore_structured_query::Filter;
+    let equal = structured_query::field_filter::Operator::Equal as i32;
+    let and = structured_query::composite_filter::Operator::And as i32;
+    for (filter, message) in [
+        (
+            field_filter_proto(0, Some(null())),
+            "invalid field filter operator: 0",
+        ),
+        (
+            field_filter_proto(100, Some(null())),
+            "invalid field filter operator: 100",
+        ),
+        (
+            field_filter_proto(equal, None),
+            "field filter value is required",
+        ),
+        (
+            composite_filter_proto(and, vec![composite_filter_proto(3, vec![])]),
+            "invalid composite filter operator: 3",
+        ),
+        (
+            composite_filter_proto(and, vec![structured_query::Filter { filter_type: None }]),
+            "invalid query: filter type is required",
+        ),
+        (
+            structured_query::Filter {
+                filter_type: Some(structured_query::filter::FilterType::UnaryFilter(
+                    structured_query::UnaryFilter {
+                        op: structured_query::unary_filter::Operator::IsNull as i32,
+                        operand_type: None,
+                    },
+                )),
+            },
+            "filter field is required",
+        ),
+        (
+            structured_query::Filter {
+                filter_type: Some(structured_query::filter::FilterType::UnaryFilter(
+                    structured_query::UnaryFilter {
+                        op: structured_query::unary_filter::Operator::IsNull as i32,
+                        operand_type: Some(structured_query::unary_filter::OperandType::Field(
+                            field_reference("a.`b"),
+                        )),
+                    },
+                )),
+            },
+            "invalid field path `a.`b`: unterminated backtick",
+        ),
+    ] {
+        assert_eq!(Filter::try_from(filter).unwrap_err().to_string(), message);
+    }
+}
+
+#[test]
+fn test_query_collection_id_and_all_descendants() -> firestore_structured_query::Result<()> {
+    // Added: Query::all_descendants, Query::collection_id
+    use firestore_structured_query::Query;
+    let query = full_query()?;
+    assert_eq!(query.collection_id(), "users");
+    assert!(query.all_descendants());
+    assert!(!Query::collection("users").all_descendants());
+    Ok(())
+}
+
+#[test]
+fn test_query_projection() -> firestore_structured_query::Result<()> {
+    // Added: Query::projection
+    use firestore_structured_query::{FieldPath, Query};
+    assert_eq!(
+        full_query()?.projection(),
+        Some(vec![FieldPath::raw("a"), FieldPath::new(["b c"])])
+    );
+    assert_eq!(Query::collection("users").projection(), None);
+    Ok(())
+}
+
+#[test]
+fn test_query_limit_value_offset_value_and_is_limit_to_last()
+-> firestore_structured_query::Result<()> {
+    // Added: Query::is_limit_to_last, Query::limit_value, Query::offset_value
+    use firestore_structured_query::{FieldPath, Query};
+    let query = full_query()?;
+    assert_eq!(query.limit_value(), Some(10));
+    assert_eq!(query.offset_value(), 1);
+    assert!(!query.is_limit_to_last());
+    let query = Query::collection("users")
+        .order_by([FieldPath::raw("a").ascending()])
+        .limit_to_last(2);
+    assert!(query.is_limit_to_last());
+    assert_eq!(query.limit_value(), Some(2));
+    Ok(())
+}
+
+#[test]
+fn test_query_start_cursor_and_end_cursor() -> firestore_structured_query::Result<()> {
+    // Added: Query::end_cursor, Query::start_cursor
+    use firestore_structured_query::{FieldPath, Query};
+    let query = full_query()?;
+    let start_cursor = query.start_cursor().unwrap();
+    assert_eq!(start_cursor.values().len(), 1);
+    assert!(start_cursor.before());
+    let end_cursor = query.end_cursor().unwrap();
+    assert_eq!(end_cursor.values().len(), 1);
+    assert!(end_cursor.before());
+
+    // limit_to_last keeps the original cursors
+    let query = Query::collection("users")
+        .order_by([FieldPath::raw("a").ascending()])
+        .start_at([int(1)])
+        .limit_to_last(2);
+    assert!(query.start_cursor().is_some());
+    assert!(query.end_cursor().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_query_orders() -> firestore_structured_query::Result<()> {
+    // Added: Query::orders, Order::direction, Order::field_path, FieldPath::as_str
+    use firestore_structured_query::{Direction, FieldPath, Query};
+    let orders = full_query()?.orders()?;
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].field_path().as_str(), "a");
+    assert_eq!(orders[0].direction(), Direction::Descending);
+
+    // limit_to_last keeps the original orders
+    let query = Query::collection("users")
+        .order_by([FieldPath::raw("a").ascending()])
+        .limit_to_last(2);
+    assert_eq!(query.orders()?[0].direction(), Direction::Ascending);
+    Ok(())
+}
+
+#[test]
+fn test_query_filter() -> firestore_structured_query::Result<()> {
+    // Added: Query::filter
+    use firestore_structured_query::{Filter, Query};
+    assert!(full_query()?.filter()?.is_some());
+    assert_eq!(Query::collection("users").filter()?, None::<Filter>);
+    // an invalid protobuf filter is reported by Query::filter
+    let query = Query::collection("users").r#where(structured_query::Filter { filter_type: None });
+    assert!(query.filter().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_filter_field_paths() -> firestore_structured_query::Result<()> {
+    // Added: Filter::field_paths
+    use firestore_structured_query::FieldPath;
+    assert_eq!(
+        full_query()?.filter()?.unwrap().field_paths(),
+        vec![
+            FieldPath::raw("a"),
+            FieldPath::raw("b"),
+            FieldPath::raw("c")
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_filter_kind() -> firestore_structured_query::Result<()> {
+    // Added: Filter::kind
+    use firestore_structured_query::{
+        CompositeOperator, FieldOperator, FieldPath, FilterKind, UnaryOperator,
+    };
+    let filter = full_query()?.filter()?.unwrap();
+    let FilterKind::Composite { op, filters } = filter.kind() else {
+        panic!("expected a composite filter");
+    };
+    assert_eq!(op, CompositeOperator::And);
+    assert_eq!(filters.len(), 2);
+    let FilterKind::Composite { op, filters: ors } = filters[0].kind() else {
+        panic!("expected a composite filter");
+    };
+    assert_eq!(op, CompositeOperator::Or);
+    assert_eq!(
+        ors[0].kind(),
+        FilterKind::Field {
+            field_path: FieldPath::raw("a"),
+            op: FieldOperator::Equal,
+            value: int(1),
+        }
+    );
+    assert!(matches!(
+        ors[1].kind(),
+        FilterKind::Field {
+            op: FieldOperator::In,
+            ..
+        }
+    ));
+    assert_eq!(
+        filters[1].kind(),
+        FilterKind::Unary {
+            field_path: FieldPath::raw("c"),
+            op: UnaryOperator::IsNotNull,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_operators_into_protobuf_operators() {
+    // Added: impl From<FieldOperator> for structured_query::field_filter::Operator,
+    //        impl From<Direction> for structured_query::Direction
+    use firestore_structured_query::{Direction, FieldOperator};
+    assert_eq!(
+        structured_query::field_filter::Operator::from(FieldOperator::NotIn),
+        structured_query::field_filter::Operator::NotIn
+    );
+    assert_eq!(
+        structured_query::Direction::from(Direction::Ascending),
+        structured_query::Direction::Ascending
+    );
+}
+
+// the filter of `SELECT * FROM c WHERE <s>`
+fn parse_filter(s: &str) -> firestore_structured_query::Result<firestore_structured_query::Filter> {
+    Ok(format!("SELECT * FROM c WHERE {}", s)
+        .parse::<firestore_structured_query::Query>()?
+        .filter()?
+        .unwrap())
+}
+
+// the text format of the query filtered by the filter, to compare filters regardless of their nesting
+fn filter_to_string(filter: firestore_structured_query::Filter) -> String {
+    firestore_structured_query::Query::collection("c")
+        .r#where(filter)
+        .to_string()
+}
+
+#[test]
+fn test_filter_flatten() -> firestore_structured_query::Result<()> {
+    // Added: Filter::flatten
+    assert_eq!(
+        parse_filter("AND(AND(a = 1, AND(b = 2)), OR(c = 3), d = 4)")?.flatten(),
+        parse_filter("AND(a = 1, b = 2, OR(c = 3), d = 4)")?
+    );
+    assert_eq!(
+        parse_filter("OR(OR(), a = 1)")?.flatten(),
+        parse_filter("OR(a = 1)")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_filter_simplify() -> firestore_structured_query::Result<()> {
+    // Added: Filter::simplify
+    for (input, expected) in [
+        ("AND(a = 1)", "a = 1"),
+        ("AND(AND(a = 1), OR(a = 1))", "a = 1"),
+        ("a = 1 AND b = 2 AND a = 1", "a = 1 AND b = 2"),
+        (
+            "(a = 1 OR b = 2) OR (b = 2 OR c = 3)",
+            "a = 1 OR b = 2 OR c = 3",
+        ),
+        ("AND(a = 1, AND())", "a = 1"),
+        ("OR(a = 1, OR())", "a = 1"),
+        ("AND(a = 1, OR())", "OR()"),
+        ("OR(a = 1, AND())", "AND()"),
+        ("AND(AND(), AND())", "AND()"),
+        ("OR(OR(OR()))", "OR()"),
+    ] {
+        assert_eq!(
+            filter_to_string(parse_filter(input)?.simplify()),
+            filter_to_string(parse_filter(expected)?),
+            "{}",
+            input
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_filter_disjunctive_normal_form() -> firestore_structured_query::Result<()> {
+    // Added: Filter::disjunction_count, Filter::disjunctive_normal_form
+    for (input, expected, count) in [
+        ("a = 1", "a = 1", 1),
+        ("a = 1 AND b = 2", "a = 1 AND b = 2", 1),
+        (
+            "(a = 1 OR b = 2) AND (c = 3 OR d = 4)",
+            "a = 1 AND c = 3 OR a = 1 AND d = 4 OR b = 2 AND c = 3 OR b = 2 AND d = 4",
+            4,
+        ),
+        (
+            "(a = 1 OR b = 2) AND (a = 1 OR c = 3)",
+            "a = 1 OR a = 1 AND c = 3 OR b = 2 AND a = 1 OR b = 2 AND c = 3",
+            4,
+        ),
+        (
+            "(a = 1 OR a = 1) AND b IN [1, 2, 3]",
+            "a = 1 AND b IN [1, 2, 3]",
+            6,
+        ),
+        ("(a = 1 OR AND()) AND b = 2", "b = 2", 2),
+        ("(a = 1 OR b = 2) AND OR()", "OR()", 0),
+    ] {
+        let input_filter = parse_filter(input)?;
+        assert_eq!(input_filter.disjunction_count(), count, "{}", input);
+        assert_eq!(
+            filter_to_string(input_filter.disjunctive_normal_form()),
+            filter_to_string(parse_filter(expected)?),
+            "{}",
+            input
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_filter_disjunction_count_over_limit() -> firestore_structured_query::Result<()> {
+    // Added: Filter::disjunction_count (the server-side limit is observable before sending)
+    use firestore_structured_query::Query;
+    let wide = parse_filter(
+        "(a = 1 OR a = 2 OR a = 3 OR a = 4 OR a = 5 OR a = 6) AND (b = 1 OR b = 2 OR b = 3 OR b = 4 OR b = 5 OR b = 6)",
+    )?;
+    assert_eq!(wide.disjunction_count(), 36);
+    assert!(!Query::collection("c").r#where(wide).violations().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_filter_not() -> firestore_structured_query::Result<()> {
+    // Added: Filter::not
+    for (input, expected) in [
+        ("a < 1", "a >= 1"),
+        ("a <= 1", "a > 1"),
+        ("a > 1", "a <= 1"),
+        ("a >= 1", "a < 1"),
+        ("a = 1", "a != 1"),
+        ("a != 1", "a = 1"),
+        ("a IN [1, 2]", "a NOT IN [1, 2]"),
+        ("a NOT IN [1, 2]", "a IN [1, 2]"),
+        ("a IS NULL", "a IS NOT NULL"),
+        ("a IS NOT NULL", "a IS NULL"),
+        ("a IS NAN", "a IS NOT NAN"),
+        ("a IS NOT NAN", "a IS NAN"),
+        ("a = 1 AND b = 2", "a != 1 OR b != 2"),
+        (
+            "a = 1 OR b < 2 AND c IS NULL",
+            "a != 1 AND (b >= 2 OR c IS NOT NULL)",
+        ),
+        ("AND()", "OR()"),
+        ("OR()", "AND()"),
+    ] {
+        let negated = parse_filter(input)?.not()?;
+        assert_eq!(negated, parse_filter(expected)?, "{}", input);
+        // double negation is the identity
+        assert_eq!(negated.not()?, parse_filter(input)?, "{}", input);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_filter_not_array_contains() -> firestore_structured_query::Result<()> {
+    // Added: Filter::not (array_contains and array_contains_any cannot be negated)
+    for (input, message) in [
+        (
+            "a ARRAY_CONTAINS 1",
+            "ARRAY_CONTAINS filter cannot be negated (field: a)",
+        ),
+        (
+            "b = 1 OR a ARRAY_CONTAINS_ANY [1]",
+            "ARRAY_CONTAINS_ANY filter cannot be negated (field: a)",
+        ),
+    ] {
+        assert_eq!(parse_filter(input)?.not().unwrap_err().to_string(), message);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_filter_not_in_over_limit() -> firestore_structured_query::Result<()> {
+    // Added: Filter::not (`in` with more than 10 values cannot be negated into `not_in`)
+    use firestore_structured_query::{ErrorKind, FieldPath};
+    let values = (0..11).map(|i| i.to_string()).collect::<Vec<String>>();
+    assert!(
+        parse_filter(&format!("a IN [{}]", values[..10].join(", ")))?
+            .not()
+            .is_ok()
+    );
+    let err = parse_filter(&format!("b = 1 OR a IN [{}]", values.join(", ")))?
+        .not()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.field_path(), Some(&FieldPath::raw("a")));
+    assert_eq!(err.operator(), Some("NOT_IN"));
+    assert_eq!(err.position(), Some("filters[1]"));
+    assert_eq!(err.violations().len(), 1);
+    assert_eq!(err.violations()[0].location(), "filters[1]");
+    assert_eq!(
+        err.violations()[0].message(),
+        "NOT_IN supports up to 10 values (actual: 11)"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_filter_not_matches() -> firestore_structured_query::Result<()> {
+    // Added: Filter::not (the negation matches the documents that have a comparable value and do not match the filter)
+    for input in ["a < 2", "a = 2 OR a > 3", "a IN [1, 3]", "a NOT IN [2]"] {
+        let filter = parse_filter(input)?;
+        let negated = filter.clone().not()?;
+        for a in 0..5 {
+            let document = document("c/d", vec![("a", int(a))]);
+            assert_ne!(
+                filter.matches(&document)?,
+                negated.matches(&document)?,
+                "{} {}",
+                input,
+                a
+            );
+        }
+    }
+    Ok(())
+}
+
+fn is_null(
+    field_path: &str,
+) -> firestore_structured_query::Result<firestore_structured_query::Filter> {
+    firestore_structured_query::FieldPath::raw(field_path).is_null()
+}
+
+#[test]
+fn test_filter_bitand() -> firestore_structured_query::Result<()> {
+    // Added: impl BitAnd for Filter
+    use firestore_structured_query::Filter;
+    let (a, b, c, d) = (is_null("a")?, is_null("b")?, is_null("c")?, is_null("d")?);
+    assert_eq!(
+        (a.clone() & b.clone()) & (c.clone() & d.clone()),
+        Filter::and([a.clone(), b.clone(), c, d])
+    );
+    // only the top-level composites are merged
+    assert_eq!(
+        Filter::and([Filter::and([a.clone()])]) & b.clone(),
+        Filter::and([Filter::and([a.clone()]), b])
+    );
+    // an empty AND is the identity of &
+    assert_eq!(Filter::and([]) & a.clone(), Filter::and([a]));
+    Ok(())
+}
+
+#[test]
+fn test_filter_bitor() -> firestore_structured_query::Result<()> {
+    // Added: impl BitOr for Filter
+    use firestore_structured_query::Filter;
+    let (a, b, c, d) = (is_null("a")?, is_null("b")?, is_null("c")?, is_null("d")?);
+    assert_eq!(
+        (a.clone() | b.clone()) | (c.clone() | d.clone()),
+        Filter::or([a.clone(), b.clone(), c.clone(), d.clone()])
+    );
+    assert_eq!(
+        a.clone() & b.clone() | c.clone() & d.clone(),
+        Filter::or([Filter::and([a, b]), Filter::and([c, d])])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_filter_not_operator() -> firestore_structured_query::Result<()> {
+    // Added: impl Not for Filter
+    use firestore_structured_query::{FieldPath, Filter};
+    assert_eq!(
+        (!(is_null("a")? & is_null("b")?))?,
+        Filter::or([
+            FieldPath::raw("a").is_not_null()?,
+            FieldPath::raw("b").is_not_null()?
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_and_where_and_or_where() -> firestore_structured_query::Result<()> {
+    // Added: Query::and_where, Query::or_where
+    use firestore_structured_query::{Filter, Query};
+    let (a, b, c, d) = (is_null("a")?, is_null("b")?, is_null("c")?, is_null("d")?);
+    assert_eq!(
+        Query::collection("users")
+            .or_where(a.clone())
+            .or_where(b.clone() | c.clone())
+            .and_where(d.clone()),
+        Query::collection("users").r#where(Filter::and([Filter::or([a, b, c]), d]))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_then_order_by() {
+    // Added: Query::then_order_by
+    use firestore_structured_query::{FieldPath, Query};
+    assert_eq!(
+        Query::collection("users").then_order_by([FieldPath::raw("a").ascending()]),
+        Query::collection("users").order_by([FieldPath::raw("a").ascending()])
+    );
+}
+
+#[test]
+fn test_query_scopes() -> firestore_structured_query::Result<()> {
+    // Added: Query::and_where, Query::then_order_by (reusable query scopes)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    let (a, b) = (is_null("a")?, is_null("b")?);
+    let not_deleted = |query: Query| query.and_where(is_null("deleted").unwrap());
+    let by_name = |query: Query| query.then_order_by([FieldPath::raw("name").ascending()]);
+    let query = by_name(not_deleted(
+        Query::collection("users")
+            .r#where(a.clone() | b.clone())
+            .order_by([FieldPath::raw("age").descending()]),
+    ));
+    assert_eq!(
+        query,
+        Query::collection("users")
+            .r#where(Filter::and([Filter::or([a, b]), is_null("deleted")?]))
+            .order_by([
+                FieldPath::raw("age").descending(),
+                FieldPath::raw("name").ascending()
+            ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_path_parse() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::parse, impl FromStr for FieldPath, impl Display for FieldPath, FieldPath::segments
+    use firestore_structured_query::FieldPath;
+    for (input, segments) in [
+        ("a", vec!["a"]),
+        ("_a1.b_2", vec!["_a1", "b_2"]),
+        ("`a`", vec!["a"]),
+        ("`x&y`.`a.b`", vec!["x&y", "a.b"]),
+        (r#"`a\`b`.`c\\d`"#, vec!["a`b", r#"c\d"#]),
+        ("`0`.` `.`日本`", vec!["0", " ", "日本"]),
+        ("__name__", vec!["__name__"]),
+    ] {
+        let field_path = FieldPath::parse(input)?;
+        assert_eq!(field_path.segments()?, segments, "{}", input);
+        assert_eq!(field_path, FieldPath::new(segments.clone()), "{}", input);
+        // the canonical form round-trips
+        assert_eq!(field_path.to_string().parse::<FieldPath>()?, field_path);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_field_path_parse_invalid() {
+    // Added: FieldPath::parse (invalid field paths)
+    use firestore_structured_query::FieldPath;
+    for (input, message) in [
+        ("", "invalid field path ``: empty segment"),
+        ("a.", "invalid field path `a.`: empty segment"),
+        (".a", "invalid field path `.a`: empty segment"),
+        ("a..b", "invalid field path `a..b`: empty segment"),
+        ("``", "invalid field path ````: empty segment"),
+        ("`a", "invalid field path ``a`: unterminated backtick"),
+        (
+            "`a`b",
+            "invalid field path ``a`b`: unexpected character after backtick",
+        ),
+        ("a`b`", "invalid field path `a`b``: unexpected backtick"),
+        (
+            r#"`a\b`"#,
+            r#"invalid field path ``a\b``: invalid escape sequence"#,
+        ),
+        (
+            "a-b",
+            "invalid field path `a-b`: segment `a-b` must be quoted with backticks",
+        ),
+        (
+            "a.1b",
+            "invalid field path `a.1b`: segment `1b` must be quoted with backticks",
+        ),
+        (
+            "a b",
+            "invalid field path `a b`: segment `a b` must be quoted with backticks",
+        ),
+    ] {
+        assert_eq!(
+            FieldPath::parse(input).unwrap_err().to_string(),
+            message,
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_field_path_child() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::child (building nested map paths at runtime)
+    use firestore_structured_query::{ErrorKind, FieldPath};
+    let root = FieldPath::raw("settings");
+    let field_path = root.child("user@example.com")?.child("enabled")?;
+    assert_eq!(field_path.as_str(), "settings.`user@example.com`.enabled");
+    assert_eq!(
+        field_path.segments()?,
+        vec!["settings", "user@example.com", "enabled"]
+    );
+    assert_eq!(root.child("a`b")?.to_string(), r#"settings.`a\`b`"#);
+    let err = root.child("").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidFieldPath);
+    assert_eq!(err.field_path(), Some(&root));
+    Ok(())
+}
+
+#[test]
+fn test_field_path_parent() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::parent
+    use firestore_structured_query::FieldPath;
+    let root = FieldPath::raw("settings");
+    let field_path = root.child("user@example.com")?.child("enabled")?;
+    assert_eq!(field_path.parent(), Some(root.child("user@example.com")?));
+    assert_eq!(root.parent(), None);
+    assert_eq!(FieldPath::raw("a..b").parent(), None);
+    Ok(())
+}
+
+#[test]
+fn test_field_path_starts_with() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::starts_with
+    use firestore_structured_query::FieldPath;
+    let root = FieldPath::raw("settings");
+    let field_path = root.child("user@example.com")?.child("enabled")?;
+    assert!(field_path.starts_with(&root));
+    assert!(field_path.starts_with(&root.child("user@example.com")?));
+    assert!(!field_path.starts_with(&root.child("user")?));
+    assert!(!field_path.starts_with(&FieldPath::raw("a..b")));
+    Ok(())
+}
+
+#[test]
+fn test_field_path_join() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::join
+    use firestore_structured_query::FieldPath;
+    let root = FieldPath::raw("settings");
+    assert_eq!(
+        root.join(&FieldPath::new(["user@example.com", "enabled"])),
+        root.child("user@example.com")?.child("enabled")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_path_document_id() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::document_id
+    use firestore_structured_query::FieldPath;
+    assert_eq!(FieldPath::document_id().segments()?, vec!["__name__"]);
+    Ok(())
+}
+
+const DOCUMENTS_ROOT: &str = "projects/p/databases/(default)/documents";
+
+fn reference(name: &str) -> Value {
+    value(ValueType::ReferenceValue(name.to_string()))
+}
+
+#[test]
+fn test_field_path_id_equal() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::id_equal
+    use firestore_structured_query::FieldPath;
+    let id = FieldPath::document_id();
+    assert_eq!(
+        id.id_equal(DOCUMENTS_ROOT, "users", "u1")?,
+        id.equal(reference(&format!("{}/users/u1", DOCUMENTS_ROOT)))?
+    );
+    // parent document
+    assert_eq!(
+        id.id_equal(&format!("{}/rooms/r1", DOCUMENTS_ROOT), "messages", "m1")?,
+        id.equal(reference(&format!(
+            "{}/rooms/r1/messages/m1",
+            DOCUMENTS_ROOT
+        )))?
+    );
+    // the quoted __name__ is the same field path
+    assert!(
+        FieldPath::raw("`__name__`")
+            .id_equal(DOCUMENTS_ROOT, "users", "u1")
+            .is_ok()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_path_id_in() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::id_in (collection group queries match the documents in any parent)
+    use firestore_structured_query::{FieldPath, Query};
+    let filter = FieldPath::document_id().id_in(
+        DOCUMENTS_ROOT,
+        "messages",
+        ["rooms/r1/messages/m1", "rooms/r2/messages/m2"],
+    )?;
+    let documents = [
+        document("rooms/r1/messages/m1", vec![]),
+        document("rooms/r1/messages/m2", vec![]),
+        document("rooms/r2/messages/m2", vec![]),
+    ];
+    let results = Query::collection_group("messages")
+        .r#where(filter)
+        .evaluate(documents.clone())?;
+    assert_eq!(
+        results.iter().map(|d| d.name.as_str()).collect::<Vec<_>>(),
+        vec![documents[0].name.as_str(), documents[2].name.as_str()]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_path_id_range() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::id_range
+    use firestore_structured_query::{ErrorKind, FieldPath, Filter};
+    let id = FieldPath::document_id();
+    assert_eq!(
+        id.id_range(DOCUMENTS_ROOT, "users", "a"..="c")?,
+        Filter::and([
+            id.greater_than_or_equal(reference(&format!("{}/users/a", DOCUMENTS_ROOT)))?,
+            id.less_than_or_equal(reference(&format!("{}/users/c", DOCUMENTS_ROOT)))?,
+        ])
+    );
+    assert_eq!(
+        id.id_range(DOCUMENTS_ROOT, "users", ("a".to_string())..)?,
+        id.greater_than_or_equal(reference(&format!("{}/users/a", DOCUMENTS_ROOT)))?
+    );
+    let err = id
+        .id_range::<_, &str>(DOCUMENTS_ROOT, "users", ..)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.field_path(), Some(&id));
+    assert_eq!(
+        err.violations()[0].message(),
+        "id_range requires at least one bound"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_path_id_filters_invalid() {
+    // Added: FieldPath::id_equal, FieldPath::id_in (invalid field paths, parents, collection IDs and document IDs)
+    use firestore_structured_query::FieldPath;
+    let id = FieldPath::document_id();
+    for (result, message) in [
+        (
+            FieldPath::raw("id").id_equal(DOCUMENTS_ROOT, "users", "u1"),
+            "document ID filters require the field path __name__ (actual: id)",
+        ),
+        (
+            id.id_equal("projects/p/databases/(default)", "users", "u1"),
+            "invalid parent: projects/p/databases/(default)",
+        ),
+        (
+            id.id_equal(&format!("{}/rooms", DOCUMENTS_ROOT), "users", "u1"),
+            "invalid parent: projects/p/databases/(default)/documents/rooms",
+        ),
+        (
+            id.id_equal(DOCUMENTS_ROOT, "a/b", "u1"),
+            "collection ID must not contain '/': a/b",
+        ),
+        (
+            id.id_equal(DOCUMENTS_ROOT, "users", ""),
+            "document ID must not be empty",
+        ),
+        (
+            id.id_equal(DOCUMENTS_ROOT, "users", "__id__"),
+            "document ID must not match __.*__: __id__",
+        ),
+        (
+            id.id_equal(DOCUMENTS_ROOT, "messages", "rooms/r1/users/u1"),
+            "invalid document path: rooms/r1/users/u1 (must be a document in the collection messages)",
+        ),
+        (
+            id.id_equal(DOCUMENTS_ROOT, "messages", "rooms/../messages/m1"),
+            "invalid path rooms/../messages/m1: document ID must not be '.' or '..': ..",
+        ),
+        (
+            id.id_equal(&format!("{}/rooms/__r__", DOCUMENTS_ROOT), "users", "u1"),
+            "invalid parent: projects/p/databases/(default)/documents/rooms/__r__",
+        ),
+        (
+            id.id_in(DOCUMENTS_ROOT, "users", ["u1", "a/b/c"]),
+            "invalid document path: a/b/c (must be a document in the collection users)",
+        ),
+    ] {
+        assert_eq!(result.unwrap_err().to_string(), message);
+    }
+}
+
+#[test]
+fn test_database_name() -> firestore_structured_query::Result<()> {
+    // Added: DatabaseName::new, DatabaseName::database_id, DatabaseName::project_id
+    use firestore_structured_query::DatabaseName;
+    let database_name = DatabaseName::new("p", "(default)")?;
+    assert_eq!(database_name.project_id(), "p");
+    assert_eq!(database_name.database_id(), "(default)");
+    assert_eq!(
+        database_name.documents_path(),
+        "projects/p/databases/(default)/documents"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_collection_name() -> firestore_structured_query::Result<()> {
+    // Added: DatabaseName::collection, CollectionName::database_name, CollectionName::document,
+    //        CollectionName::parent, CollectionName::parent_path
+    use firestore_structured_query::DatabaseName;
+    let database_name = DatabaseName::new("p", "(default)")?;
+    let users = database_name.collection("users")?;
+    assert_eq!(users.parent(), None);
+    assert_eq!(
+        users.parent_path(),
+        "projects/p/databases/(default)/documents"
+    );
+    assert_eq!(users.document("u1")?, database_name.document("users/u1")?);
+    let posts = users.document("u1")?.collection("posts")?;
+    assert_eq!(posts.parent(), Some(users.document("u1")?));
+    assert_eq!(posts.database_name(), &database_name);
+    assert_eq!(
+        posts.parent_path(),
+        "projects/p/databases/(default)/documents/users/u1"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_document_name() -> firestore_structured_query::Result<()> {
+    // Added: DatabaseName::document, DocumentName::collection, DocumentName::collection_id,
+    //        DocumentName::database_name, DocumentName::document_id, DocumentName::parent, DocumentName::path
+    use firestore_structured_query::DatabaseName;
+    let database_name = DatabaseName::new("p", "(default)")?;
+    let users = database_name.collection("users")?;
+    let user = database_name.document("users/u1")?;
+    assert_eq!(user.parent(), users);
+    let post = user.collection("posts")?.document("post1")?;
+    assert_eq!(post.database_name(), &database_name);
+    assert_eq!(post.collection_id(), "posts");
+    assert_eq!(post.document_id(), "post1");
+    assert_eq!(post.path(), "users/u1/posts/post1");
+    assert_eq!(post.parent().parent(), Some(user));
+    Ok(())
+}
+
+#[test]
+fn test_resource_names_from_str_and_display() -> firestore_structured_query::Result<()> {
+    // Added: impl FromStr for CollectionName, DatabaseName and DocumentName, impl Display for CollectionName,
+    //        DatabaseName and DocumentName
+    use firestore_structured_query::{CollectionName, DatabaseName, DocumentName};
+    assert_eq!(
+        "projects/p/databases/(default)"
+            .parse::<DatabaseName>()?
+            .to_string(),
+        "projects/p/databases/(default)"
+    );
+    for s in [
+        "projects/p/databases/(default)/documents/users",
+        "projects/p/databases/(default)/documents/users/u1/posts",
+    ] {
+        assert_eq!(s.parse::<CollectionName>()?.to_string(), s);
+    }
+    for s in [
+        "projects/p/databases/(default)/documents/users/u1",
+        "projects/p/databases/(default)/documents/users/u1/posts/post1",
+    ] {
+        assert_eq!(s.parse::<DocumentName>()?.to_string(), s);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_resource_names_invalid() -> firestore_structured_query::Result<()> {
+    // Added: CollectionName, DatabaseName, DocumentName (invalid names, paths and IDs)
+    use firestore_structured_query::{CollectionName, DatabaseName, DocumentName, ErrorKind};
+    let database_name = DatabaseName::new("p", "(default)")?;
+    let users = database_name.collection("users")?;
+    let user = users.document("u1")?;
+    for (result, message) in [
+        (
+            "projects/p/databases".parse::<DatabaseName>().map(|_| ()),
+            "invalid database name: projects/p/databases",
+        ),
+        (
+            DatabaseName::new("", "(default)").map(|_| ()),
+            "invalid project ID: ",
+        ),
+        (
+            "projects/p/databases/(default)/documents/users/u1"
+                .parse::<CollectionName>()
+                .map(|_| ()),
+            "invalid collection path: users/u1",
+        ),
+        (
+            "projects/p/databases/(default)/documents/users"
+                .parse::<DocumentName>()
+                .map(|_| ()),
+            "invalid document path: users",
+        ),
+        (
+            "projects/p/databases/(default)/users/u1"
+                .parse::<DocumentName>()
+                .map(|_| ()),
+            "invalid resource name: projects/p/databases/(default)/users/u1",
+        ),
+        (
+            database_name.document("users//u1").map(|_| ()),
+            "invalid path users//u1: document ID must not be empty",
+        ),
+        (
+            database_name.document("users/__u__").map(|_| ()),
+            "invalid path users/__u__: document ID must not match __.*__: __u__",
+        ),
+        (
+            users.document("a/b").map(|_| ()),
+            "document ID must not contain '/': a/b",
+        ),
+        (
+            user.collection("..").map(|_| ()),
+            "collection ID must not be '.' or '..': ..",
+        ),
+    ] {
+        let err = result.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFieldPath);
+        assert_eq!(err.to_string(), message);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_document_name_into_value() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for DocumentName
+    use firestore_structured_query::{DatabaseName, FieldPath, IntoValue};
+    let database_name = DatabaseName::new("p", "(default)")?;
+    let user = database_name.document("users/u1")?;
+    assert_eq!(user.clone().into_value()?, reference(&user.to_string()));
+    assert_eq!(
+        FieldPath::document_id().equal(user)?,
+        FieldPath::document_id().id_equal(&database_name.documents_path(), "users", "u1")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_collection_path() -> firestore_structured_query::Result<()> {
+    // Added: Query::collection with a collection path, Query::parent
+    use firestore_structured_query::{Query, QueryRequest};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::RunQueryRequest;
+    let query = Query::collection("users/u1/posts").limit(10);
+    assert_eq!(query.parent(), Some("users/u1"));
+    assert_eq!(query.collection_id(), "posts");
+    assert_eq!(
+        RunQueryRequest::from(QueryRequest::new("p", "(default)", query.clone())?).parent,
+        "projects/p/databases/(default)/documents/users/u1"
+    );
+    assert_eq!(query.to_string(), "SELECT * FROM `users/u1/posts` LIMIT 10");
+    assert_eq!(query.to_string().parse::<Query>()?, query);
+    assert!(query.validate().is_ok());
+    assert_eq!(
+        Query::collection("users/u1/posts/p1/comments").parent(),
+        Some("users/u1/posts/p1")
+    );
+    assert_eq!(Query::collection("users").parent(), None);
+    Ok(())
+}
+
+#[test]
+fn test_query_collection_path_violations() {
+    // Added: Query::violations (the parent must be a document path)
+    use firestore_structured_query::Query;
+    assert_eq!(
+        Query::collection("users/posts")
+            .violations()
+            .into_iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>(),
+        vec!["parent: parent must be a document path: users"]
+    );
+}
+
+#[test]
+fn test_query_evaluate_collection_path() -> firestore_structured_query::Result<()> {
+    // Added: Query::evaluate (the evaluation is limited to the collection under the parent)
+    use firestore_structured_query::Query;
+    let documents = [
+        document("users/u1/posts/post1", vec![]),
+        document("users/u2/posts/post2", vec![]),
+        document("posts/post3", vec![]),
+    ];
+    assert_eq!(
+        Query::collection("users/u1/posts").evaluate(documents.clone())?,
+        vec![documents[0].clone()]
+    );
+    assert_eq!(
+        Query::collection("posts").evaluate(documents.clone())?,
+        vec![documents[2].clone()]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_into_value_bool() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for bool
+    use firestore_structured_query::IntoValue;
+    assert_eq!(true.into_value()?, value(ValueType::BooleanValue(true)));
+    Ok(())
+}
+
+#[test]
+fn test_into_value_integers() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for integers
+    use firestore_structured_query::IntoValue;
+    assert_eq!(1_i8.into_value()?, int(1));
+    assert_eq!(2_u32.into_value()?, int(2));
+    assert_eq!((i64::MAX as u64).into_value()?, int(i64::MAX));
+    assert_eq!(
+        u64::MAX.into_value().unwrap_err().to_string(),
+        "u64 value 18446744073709551615 is out of range of integer_value (i64)"
+    );
+    assert!(i128::MIN.into_value().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_into_value_floats() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for floats
+    use firestore_structured_query::IntoValue;
+    assert_eq!(1.5_f32.into_value()?, double(1.5));
+    Ok(())
+}
+
+#[test]
+fn test_into_value_strings() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for strings
+    use firestore_structured_query::IntoValue;
+    assert_eq!("a".into_value()?, string("a"));
+    assert_eq!("b".to_string().into_value()?, string("b"));
+    assert_eq!(std::path::PathBuf::from("c/d").into_value()?, string("c/d"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        assert!(
+            std::ffi::OsString::from_vec(vec![0xff])
+                .into_value()
+                .is_err()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::useless_conversion)]
+fn test_into_value_bytes() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for bytes
+    use firestore_structured_query::IntoValue;
+    let bytes = value(ValueType::BytesValue(vec![1_u8, 2].into()));
+    assert_eq!(vec![1_u8, 2].into_value()?, bytes);
+    assert_eq!((&[1_u8, 2][..]).into_value()?, bytes);
+    Ok(())
+}
+
+#[test]
+fn test_into_value_option() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for Option
+    use firestore_structured_query::IntoValue;
+    assert_eq!(None::<i64>.into_value()?, value(ValueType::NullValue(0)));
+    assert_eq!(Some(1).into_value()?, int(1));
+    Ok(())
+}
+
+#[test]
+fn test_into_value_arrays() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for arrays, slices and Vec
+    use firestore_structured_query::IntoValue;
+    let expected = array(vec![int(1), int(2)]);
+    assert_eq!(vec![1, 2].into_value()?, expected);
+    assert_eq!([1, 2].into_value()?, expected);
+    assert_eq!((&[1, 2][..]).into_value()?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_into_value_tuples() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for tuples
+    use firestore_structured_query::IntoValue;
+    assert_eq!(
+        (1, "a", None::<bool>).into_value()?,
+        array(vec![int(1), string("a"), value(ValueType::NullValue(0))])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_into_value_maps() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for HashMap and BTreeMap
+    use firestore_structured_query::IntoValue;
+    let expected = map(vec![("k", int(1))]);
+    assert_eq!(
+        std::collections::HashMap::from([("k", 1)]).into_value()?,
+        expected
+    );
+    assert_eq!(
+        std::collections::BTreeMap::from([("k".to_string(), 1)]).into_value()?,
+        expected
+    );
+    Ok(())
+}
+
+#[test]
+fn test_into_value_lat_lng() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for LatLng
+    use firestore_structured_query::IntoValue;
+    use googleapis_tonic_google_firestore_v1::google::r#type::LatLng;
+    let lat_lng = LatLng {
+        latitude: 35.0,
+        longitude: 139.0,
+    };
+    assert_eq!(
+        lat_lng.into_value()?,
+        value(ValueType::GeoPointValue(lat_lng))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_into_value_in_filters() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue (the native values can be used in the filters)
+    use firestore_structured_query::FieldPath;
+    assert_eq!(
+        FieldPath::raw("a").equal(1)?,
+        FieldPath::raw("a").equal(int(1))?
+    );
+    assert_eq!(
+        FieldPath::raw("b").r#in(["x", "y"])?,
+        FieldPath::raw("b").r#in(array(vec![string("x"), string("y")]))?
+    );
+    Ok(())
+}
+
+fn timestamp(seconds: i64, nanos: i32) -> Value {
+    value(ValueType::TimestampValue(prost_types::Timestamp {
+        seconds,
+        nanos,
+    }))
+}
+
+#[test]
+fn test_into_value_timestamp() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for Timestamp (truncated to microseconds)
+    use firestore_structured_query::IntoValue;
+    assert_eq!(
+        prost_types::Timestamp {
+            seconds: 1,
+            nanos: 123_456_789,
+        }
+        .into_value()?,
+        timestamp(1, 123_456_000)
+    );
+    assert_eq!(
+        prost_types::Timestamp {
+            seconds: 1,
+            nanos: -1,
+        }
+        .into_value()?,
+        timestamp(0, 999_999_000)
+    );
+    assert!(
+        prost_types::Timestamp {
+            seconds: 253_402_300_800,
+            nanos: 0,
+        }
+        .into_value()
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_into_value_system_time() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for SystemTime
+    use firestore_structured_query::{FieldPath, IntoValue};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    assert_eq!(
+        (UNIX_EPOCH + Duration::new(1_700_000_000, 1_999)).into_value()?,
+        timestamp(1_700_000_000, 1_000)
+    );
+    assert_eq!(
+        (UNIX_EPOCH - Duration::new(1, 500_000_000)).into_value()?,
+        timestamp(-2, 500_000_000)
+    );
+    assert!(
+        (UNIX_EPOCH + Duration::from_secs(253_402_300_800))
+            .into_value()
+            .is_err()
+    );
+    let now = SystemTime::now();
+    assert_eq!(
+        FieldPath::raw("created_at").greater_than(now)?,
+        FieldPath::raw("created_at").greater_than(now.into_value()?)?
+    );
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_into_value_chrono_date_time() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for chrono::DateTime
+    use chrono::{FixedOffset, TimeZone, Utc};
+    use firestore_structured_query::IntoValue;
+    assert_eq!(
+        Utc.timestamp_opt(1_700_000_000, 123_456_789)
+            .unwrap()
+            .into_value()?,
+        timestamp(1_700_000_000, 123_456_000)
+    );
+    assert_eq!(
+        FixedOffset::east_opt(9 * 3_600)
+            .unwrap()
+            .timestamp_opt(1_700_000_000, 0)
+            .unwrap()
+            .into_value()?,
+        timestamp(1_700_000_000, 0)
+    );
+    assert!(
+        Utc.timestamp_opt(-62_135_596_801, 0)
+            .unwrap()
+            .into_value()
+            .is_err()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_into_value_time_offset_date_time() -> firestore_structured_query::Result<()> {
+    // Added: IntoValue for time::OffsetDateTime
+    use firestore_structured_query::IntoValue;
+    use time::OffsetDateTime;
+    assert_eq!(
+        (OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap()
+            + time::Duration::nanoseconds(123_456_789))
+        .into_value()?,
+        timestamp(1_700_000_000, 123_456_000)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_firestore_value_literals() -> firestore_structured_query::Result<()> {
+    // Added: firestore_value! (literals)
+    use firestore_structured_query::firestore_value;
+    assert_eq!(firestore_value!(null)?, value(ValueType::NullValue(0)));
+    assert_eq!(
+        firestore_value!(true)?,
+        value(ValueType::BooleanValue(true))
+    );
+    assert_eq!(firestore_value!(-1)?, int(-1));
+    assert_eq!(firestore_value!(1.5)?, double(1.5));
+    assert_eq!(firestore_value!("a")?, string("a"));
+    assert_eq!(firestore_value!([])?, array(vec![]));
+    assert_eq!(firestore_value!({})?, map(vec![]));
+    Ok(())
+}
+
+#[test]
+fn test_firestore_value_nested() -> firestore_structured_query::Result<()> {
+    // Added: firestore_value! (nested literals and interpolated expressions)
+    use firestore_structured_query::{IntoValue, firestore_value};
+    let name = "bob".to_string();
+    let key = "dynamic";
+    let tags = vec!["x", "y"];
+    assert_eq!(
+        firestore_value!({
+            "name": name.clone(),
+            "age": 20 + 1,
+            "tags": tags,
+            "scores": [1, -2, 3.5, null, [true], {"k": "v"}],
+            (key): {"nested": null},
+            "len": name.len() as i64,
+        })?,
+        map(vec![
+            ("name", string("bob")),
+            ("age", int(21)),
+            ("tags", vec!["x", "y"].into_value()?),
+            (
+                "scores",
+                array(vec![
+                    int(1),
+                    int(-2),
+                    double(3.5),
+                    value(ValueType::NullValue(0)),
+                    array(vec![value(ValueType::BooleanValue(true))]),
+                    map(vec![("k", string("v"))]),
+                ]),
+            ),
+            (
+                "dynamic",
+                map(vec![("nested", value(ValueType::NullValue(0)))])
+            ),
+            ("len", int(3)),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_firestore_value_explicit_forms() -> firestore_structured_query::Result<()> {
+    // Added: firestore_value! (timestamp, bytes, geo_point, reference and vector)
+    use firestore_structured_query::{IntoValue, Vector, firestore_value};
+    use googleapis_tonic_google_firestore_v1::google::r#type::LatLng;
+    assert_eq!(
+        firestore_value!([
+            timestamp("2006-01-02T15:04:05.123456789+07:00"),
+            timestamp(1, 999),
+            bytes(b"abc"),
+            geo_point(35.0, 139.0),
+            reference("projects/p/databases/(default)/documents/users/u1"),
+            vector([1.0, 2.0]),
+        ])?,
+        array(vec![
+            timestamp(1_136_189_045, 123_456_000),
+            timestamp(1, 0),
+            b"abc"[..].into_value()?,
+            value(ValueType::GeoPointValue(LatLng {
+                latitude: 35.0,
+                longitude: 139.0,
+            })),
+            reference("projects/p/databases/(default)/documents/users/u1"),
+            Vector::new([1.0, 2.0]).into_value()?,
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_firestore_value_invalid() {
+    // Added: firestore_value! (invalid values)
+    use firestore_structured_query::firestore_value;
+    assert!(firestore_value!(timestamp("2006-01-02")).is_err());
+    assert!(firestore_value!(geo_point(91.0, 0.0)).is_err());
+    assert!(firestore_value!(reference("users/u1")).is_err());
+    assert!(firestore_value!([1, u64::MAX]).is_err());
+}
+
+#[test]
+fn test_firestore_value_in_filters() -> firestore_structured_query::Result<()> {
+    // Added: firestore_value! (the values can be used in the filters)
+    use firestore_structured_query::{FieldPath, firestore_value};
+    assert_eq!(
+        FieldPath::raw("a").r#in(firestore_value!([1, 2])?)?,
+        FieldPath::raw("a").r#in(array(vec![int(1), int(2)]))?
+    );
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_query() -> firestore_structured_query::Result<()> {
+    // Added: serde::Serialize and serde::Deserialize for Query (json feature)
+    use firestore_structured_query::{FieldPath, Filter, Query, firestore_value};
+    let query = Query::collection_group("users")
+        .select([FieldPath::raw("name")])
+        .r#where(Filter::and([
+            FieldPath::raw("age").greater_than_or_equal(20)?,
+            FieldPath::raw("deleted_at").is_null()?,
+        ]))
+        .order_by([FieldPath::raw("age").descending()])
+        .start_at([firestore_value!(20)?])
+        .offset(1)
+        .limit(10);
+    let json = serde_json::to_value(&query).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "select": { "fields": [{ "fieldPath": "name" }] },
+            "from": [{ "collectionId": "users", "allDescendants": true }],
+            "where": {
+                "compositeFilter": {
+                    "op": "AND",
+                    "filters": [
+                        {
+                            "fieldFilter": {
+                                "field": { "fieldPath": "age" },
+                                "op": "GREATER_THAN_OR_EQUAL",
+                                "value": { "integerValue": "20" }
+                            }
+                        },
+                        {
+                            "unaryFilter": {
+                                "op": "IS_NULL",
+                                "field": { "fieldPath": "deleted_at" }
+                            }
+                        }
+                    ]
+                }
+            },
+            "orderBy": [{ "field": { "fieldPath": "age" }, "direction": "DESCENDING" }],
+            "startAt": { "values": [{ "integerValue": "20" }], "before": true },
+            "offset": 1,
+            "limit": 10
+        })
+    );
+    assert_eq!(serde_json::from_value::<Query>(json).unwrap(), query);
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_snake_case() -> firestore_structured_query::Result<()> {
+    // Added: serde::Deserialize for Query and Filter (snake_case names, numeric int64 and enum numbers are also accepted)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    assert_eq!(
+        serde_json::from_value::<Query>(serde_json::json!({
+            "from": [{ "collection_id": "users" }],
+            "order_by": [{ "field": { "field_path": "age" }, "direction": 1 }],
+            "limit": 10
+        }))
+        .unwrap(),
+        Query::collection("users")
+            .order_by([FieldPath::raw("age").ascending()])
+            .limit(10)
+    );
+    assert_eq!(
+        serde_json::from_value::<Filter>(serde_json::json!({
+            "fieldFilter": {
+                "field": { "fieldPath": "a" },
+                "op": "EQUAL",
+                "value": { "integerValue": 1 }
+            }
+        }))
+        .unwrap(),
+        FieldPath::raw("a").equal(1)?
+    );
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_invalid_query() {
+    // Added: serde::Deserialize for Query (invalid queries are rejected)
+    use firestore_structured_query::Query;
+    for invalid in [
+        serde_json::json!({ "from": [] }),
+        serde_json::json!({ "from": [{ "collectionId": "users" }], "unknown": 1 }),
+        serde_json::json!({ "from": [{ "collectionId": "users" }], "limit": "x" }),
+        serde_json::json!({
+            "from": [{ "collectionId": "users" }],
+            "where": { "unaryFilter": { "op": "IS_SOMETHING", "field": { "fieldPath": "a" } } }
+        }),
+    ] {
+        assert!(serde_json::from_value::<Query>(invalid).is_err());
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_find_nearest() -> firestore_structured_query::Result<()> {
+    // Added: serde::Serialize and serde::Deserialize for Query (find_nearest)
+    use firestore_structured_query::{DistanceMeasure, FieldPath, Query, Vector};
+    let query = Query::collection("docs").find_nearest(
+        FieldPath::raw("embedding")
+            .find_nearest(Vector::new([1.0, 2.0]), DistanceMeasure::Cosine, 3)?
+            .distance_threshold(0.5),
+    );
+    let json = serde_json::to_value(&query).unwrap();
+    assert_eq!(json["findNearest"]["distanceMeasure"], "COSINE");
+    assert_eq!(json["findNearest"]["limit"], 3);
+    assert_eq!(serde_json::from_value::<Query>(json).unwrap(), query);
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_order() {
+    // Added: serde::Serialize and serde::Deserialize for Order
+    use firestore_structured_query::{FieldPath, Order};
+    let order = FieldPath::raw("a").ascending();
+    let json = serde_json::to_value(&order).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "field": { "fieldPath": "a" }, "direction": "ASCENDING" })
+    );
+    assert_eq!(serde_json::from_value::<Order>(json).unwrap(), order);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_value() -> firestore_structured_query::Result<()> {
+    // Added: serde::Serialize and serde::Deserialize for JsonValue
+    use firestore_structured_query::{JsonValue, OrderedValue, firestore_value};
+    let value = firestore_value!({
+        "null": null,
+        "boolean": true,
+        "integer": -1,
+        "double": 1.5,
+        "nan": f64::NAN,
+        "timestamp": timestamp("2006-01-02T15:04:05.123Z"),
+        "string": "s",
+        "bytes": bytes(b"\x00\xff"),
+        "reference": reference("projects/p/databases/(default)/documents/c/d"),
+        "geo_point": geo_point(1.0, 0.0),
+        "array": [1, "a"],
+        "map": {},
+    })?;
+    let json = serde_json::to_value(JsonValue::from(value.clone())).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "mapValue": {
+                "fields": {
+                    "array": {
+                        "arrayValue": {
+                            "values": [{ "integerValue": "1" }, { "stringValue": "a" }]
+                        }
+                    },
+                    "boolean": { "booleanValue": true },
+                    "bytes": { "bytesValue": "AP8=" },
+                    "double": { "doubleValue": 1.5 },
+                    "geo_point": { "geoPointValue": { "latitude": 1.0 } },
+                    "integer": { "integerValue": "-1" },
+                    "map": { "mapValue": {} },
+                    "nan": { "doubleValue": "NaN" },
+                    "null": { "nullValue": null },
+                    "reference": {
+                        "referenceValue": "projects/p/databases/(default)/documents/c/d"
+                    },
+                    "string": { "stringValue": "s" },
+                    "timestamp": { "timestampValue": "2006-01-02T15:04:05.123Z" }
+                }
+            }
+        })
+    );
+    let decoded = Value::from(serde_json::from_value::<JsonValue>(json).unwrap());
+    // NaN != NaN
+    assert_eq!(OrderedValue::from(decoded), OrderedValue::from(value));
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_value_lenient() -> firestore_structured_query::Result<()> {
+    // Added: serde::Deserialize for JsonValue (unpadded base64 and multiple value types)
+    use firestore_structured_query::{JsonValue, firestore_value};
+    assert_eq!(
+        Value::from(
+            serde_json::from_value::<JsonValue>(serde_json::json!({ "bytesValue": "AP8" }))
+                .unwrap()
+        ),
+        firestore_value!(bytes(b"\x00\xff"))?
+    );
+    assert!(
+        serde_json::from_value::<JsonValue>(
+            serde_json::json!({ "stringValue": "a", "integerValue": "1" })
+        )
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_error_value_conversion() {
+    // Added: ErrorKind::ValueConversion, Error::field_path, Error::operator, Error::position
+    use firestore_structured_query::{ErrorKind, FieldPath};
+    let err = FieldPath::raw("a").r#in([1, u64::MAX]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValueConversion);
+    assert_eq!(err.field_path(), Some(&FieldPath::raw("a")));
+    assert_eq!(err.operator(), Some("IN"));
+    assert_eq!(err.position(), None);
+}
+
+#[test]
+fn test_error_value_conversion_in_gql_literals() {
+    // Added: ErrorKind::ValueConversion (the kind of the value conversion errors in GQL literals)
+    use firestore_structured_query::{ErrorKind, Query};
+    for s in [
+        r#"SELECT * FROM c WHERE a = TIMESTAMP("10000-01-01T00:00:00Z")"#,
+        r#"SELECT * FROM c WHERE a = BYTES("QQ=")"#,
+    ] {
+        let err = s.parse::<Query>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueConversion);
+    }
+}
+
+#[test]
+fn test_error_invalid_field_path() {
+    // Added: ErrorKind::InvalidFieldPath
+    use firestore_structured_query::{ErrorKind, FieldPath, Order};
+    let err = FieldPath::parse("a..b").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidFieldPath);
+    assert_eq!(err.field_path(), Some(&FieldPath::raw("a..b")));
+    let err = Order::try_from(structured_query::Order {
+        field: Some(field_reference("a.`b")),
+        direction: 1,
+    })
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidFieldPath);
+    assert_eq!(err.field_path(), Some(&FieldPath::raw("a.`b")));
+}
+
+#[test]
+fn test_error_unsupported_operator() -> firestore_structured_query::Result<()> {
+    // Added: ErrorKind::UnsupportedOperator (with the position in the filter tree)
+    use firestore_structured_query::{ErrorKind, FieldPath, Filter};
+    let filter = Filter::or([
+        FieldPath::raw("a").equal(1)?,
+        Filter::and([
+            FieldPath::raw("b").is_null()?,
+            FieldPath::raw("c").array_contains_any([1, 2])?,
+        ]),
+    ]);
+    let err = filter.not().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnsupportedOperator);
+    assert_eq!(err.field_path(), Some(&FieldPath::raw("c")));
+    assert_eq!(err.operator(), Some("ARRAY_CONTAINS_ANY"));
+    assert_eq!(err.position(), Some("filters[1].filters[1]"));
+    Ok(())
+}
+
+#[test]
+fn test_error_position_in_structured_query() -> firestore_structured_query::Result<()> {
+    // Added: Error::position (the position in the StructuredQuery)
+    use firestore_structured_query::{ErrorKind, FieldPath, Filter, Query};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let mut structured_query =
+        StructuredQuery::from(Query::collection("c").r#where(Filter::and([
+            FieldPath::raw("a").equal(1)?,
+            FieldPath::raw("b").equal(2)?,
+        ])));
+    if let Some(structured_query::Filter {
+        filter_type: Some(structured_query::filter::FilterType::CompositeFilter(composite)),
+    }) = &mut structured_query.r#where
+        && let Some(structured_query::Filter {
+            filter_type: Some(structured_query::filter::FilterType::FieldFilter(field_filter)),
+        }) = composite.filters.get_mut(1)
+    {
+        field_filter.op = 99;
+    }
+    let err = Query::try_from(structured_query).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnsupportedOperator);
+    assert_eq!(err.field_path(), Some(&FieldPath::raw("b")));
+    assert_eq!(err.operator(), Some("99"));
+    assert_eq!(err.position(), Some("where.filters[1]"));
+    assert_eq!(err.to_string(), "invalid field filter operator: 99");
+    Ok(())
+}
+
+#[test]
+fn test_error_invalid_cursor() {
+    // Added: ErrorKind::InvalidCursor
+    use firestore_structured_query::{ErrorKind, FieldPath, Query};
+    let err = Query::collection("c")
+        .order_by([FieldPath::raw("x").ascending()])
+        .start_after_document(&document("c/d", vec![]))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidCursor);
+    assert_eq!(err.field_path(), Some(&FieldPath::raw("x")));
+}
+
+#[test]
+fn test_error_violations() {
+    // Added: ErrorKind::Violation, Error::violations
+    use firestore_structured_query::{Aggregation, AggregationQuery, ErrorKind, Query};
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    let err = Query::collection("c")
+        .limit(-1)
+        .offset(-1)
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(
+        err.violations()
+            .iter()
+            .map(|violation| violation.location())
+            .collect::<Vec<&str>>(),
+        vec!["offset", "limit"]
+    );
+    let err = AggregationQuery::new(
+        Query::collection("c"),
+        [
+            Aggregation::count().alias("a"),
+            Aggregation::count().alias("a"),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.violations()[0].location(), "aggregations[1].alias");
+    let err = Query::try_from(StructuredQuery::default()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.violations()[0].location(), "from");
+}
+
+#[test]
+fn test_error_filter_type_required() {
+    // Added: ErrorKind::Violation (a filter without a filter type)
+    use firestore_structured_query::{ErrorKind, Filter};
+    let err = Filter::try_from(structured_query::Filter { filter_type: None }).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.position(), Some(""));
+    let err = Filter::try_from(composite_filter_proto(
+        structured_query::composite_filter::Operator::And as i32,
+        vec![structured_query::Filter { filter_type: None }],
+    ))
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.violations()[0].location(), "filters[0]");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_error_json() {
+    // Added: JSON errors are reported through serde_json::Error, which keeps only the message
+    use firestore_structured_query::{Filter, Order};
+    let err = serde_json::from_value::<Order>(serde_json::json!({
+        "field": { "fieldPath": "a" },
+        "direction": "UP"
+    }))
+    .unwrap_err();
+    assert_eq!(err.to_string(), "unknown direction value: UP");
+    let err = serde_json::from_value::<Filter>(serde_json::json!({
+        "fieldFilter": { "field": { "fieldPath": "a" }, "op": "EQUAL", "value": { "nullValue": null } },
+        "unaryFilter": { "field": { "fieldPath": "a" }, "op": "IS_NULL" }
+    }))
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: Filter must have at most one of compositeFilter, fieldFilter and unaryFilter"
+    );
+}
+
+#[test]
+fn test_error_other() {
+    // Added: ErrorKind::Other
+    use firestore_structured_query::ErrorKind;
+    let err = firestore_structured_query::Error::new("message");
+    assert_eq!(err.kind(), ErrorKind::Other);
+    assert_eq!(err.violations(), &[]);
+}
+
+#[test]
+fn test_field_path_equal_null_and_nan() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::equal and FieldPath::not_equal create UnaryFilter for null and NaN
+    use firestore_structured_query::FieldPath;
+    assert_eq!(
+        FieldPath::raw("a").equal(None::<i64>)?,
+        FieldPath::raw("a").is_null()?
+    );
+    assert_eq!(
+        FieldPath::raw("a").equal(f64::NAN)?,
+        FieldPath::raw("a").is_nan()?
+    );
+    assert_eq!(
+        FieldPath::raw("a").not_equal(None::<i64>)?,
+        FieldPath::raw("a").is_not_null()?
+    );
+    assert_eq!(
+        FieldPath::raw("a").not_equal(f64::NAN)?,
+        FieldPath::raw("a").is_not_nan()?
+    );
+    assert!(matches!(
+        structured_query::Filter::from(FieldPath::raw("a").equal(1)?).filter_type,
+        Some(structured_query::filter::FilterType::FieldFilter(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_field_path_array_operators() {
+    // Added: FieldPath::r#in, FieldPath::not_in and FieldPath::array_contains_any validate the value
+    use firestore_structured_query::FieldPath;
+    assert!(FieldPath::raw("a").r#in(vec![0; 30]).is_ok());
+    assert!(FieldPath::raw("a").array_contains_any(vec![0; 30]).is_ok());
+    assert!(FieldPath::raw("a").not_in(vec![0; 10]).is_ok());
+}
+
+#[test]
+fn test_field_path_array_operators_invalid() {
+    // Added: FieldPath::r#in, FieldPath::not_in and FieldPath::array_contains_any (invalid values)
+    use firestore_structured_query::{ErrorKind, FieldPath};
+    for (result, message) in [
+        (FieldPath::raw("a").r#in(1), "IN requires an array value"),
+        (
+            FieldPath::raw("a").r#in(Vec::<i64>::new()),
+            "IN requires a non-empty array",
+        ),
+        (
+            FieldPath::raw("a").r#in(vec![0; 31]),
+            "IN supports up to 30 values (actual: 31)",
+        ),
+        (
+            FieldPath::raw("a").array_contains_any(vec![0; 31]),
+            "ARRAY_CONTAINS_ANY supports up to 30 values (actual: 31)",
+        ),
+        (
+            FieldPath::raw("a").not_in(vec![0; 11]),
+            "NOT_IN supports up to 10 values (actual: 11)",
+        ),
+    ] {
+        let err = result.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Violation);
+        assert_eq!(err.field_path(), Some(&FieldPath::raw("a")));
+        assert_eq!(err.violations().len(), 1);
+        assert_eq!(err.violations()[0].location(), "");
+        assert_eq!(err.violations()[0].message(), message);
+        assert_eq!(err.to_string(), format!("invalid query: {}", message));
+    }
+}
+
+#[test]
+fn test_field_path_range_operators_vector() -> firestore_structured_query::Result<()> {
+    // Added: FieldPath::less_than and the other range operators reject vector values
+    use firestore_structured_query::{ErrorKind, FieldPath, IntoValue, Vector};
+    let err = FieldPath::raw("v")
+        .less_than(Vector::new([1.0]))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.operator(), Some("LESS_THAN"));
+    assert_eq!(
+        err.violations()[0].message(),
+        "LESS_THAN does not support vector values"
+    );
+    assert!(
+        FieldPath::raw("v")
+            .equal(Vector::new([1.0]).into_value()?)
+            .is_ok()
+    );
+    Ok(())
+}
+
+fn index_order(
+    field_path: &str,
+    direction: firestore_structured_query::Direction,
+) -> firestore_structured_query::IndexField {
+    firestore_structured_query::IndexField::Order {
+        field_path: firestore_structured_query::FieldPath::raw(field_path),
+        direction,
+    }
+}
+
+// a query that needs an index with equality, array and order_by fields
+fn posts_query() -> firestore_structured_query::Result<firestore_structured_query::Query> {
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    Ok(Query::collection_group("posts")
+        .r#where(Filter::and([
+            FieldPath::raw("tags").array_contains("rust")?,
+            FieldPath::raw("published").equal(true)?,
+            FieldPath::raw("author").is_null()?,
+        ]))
+        .order_by([FieldPath::raw("created_at").descending()]))
+}
+
+// a query that needs an index with a vector field
+fn vector_search_query() -> firestore_structured_query::Result<firestore_structured_query::Query> {
+    use firestore_structured_query::{DistanceMeasure, FieldPath, Query, Vector};
+    Ok(Query::collection("docs")
+        .r#where(FieldPath::raw("category").equal("a")?)
+        .find_nearest(FieldPath::raw("embedding").find_nearest(
+            Vector::new([1.0, 2.0, 3.0]),
+            DistanceMeasure::Cosine,
+            10,
+        )?))
+}
+
+#[test]
+fn test_query_composite_indexes_single_field() -> firestore_structured_query::Result<()> {
+    // Added: Query::composite_indexes (queries served by single-field indexes)
+    use firestore_structured_query::{FieldPath, Filter, Query};
+    for query in [
+        Query::collection("users"),
+        Query::collection("users").order_by([FieldPath::raw("a").descending()]),
+        Query::collection("users").r#where(Filter::and([
+            FieldPath::raw("a").equal(1)?,
+            FieldPath::raw("b").r#in([2, 3])?,
+            FieldPath::raw("c").array_contains(4)?,
+        ])),
+        Query::collection("users")
+            .r#where(FieldPath::raw("a").equal(1)?)
+            .order_by([FieldPath::raw("a").ascending()]),
+        Query::collection("users").r#where(Filter::and([
+            FieldPath::raw("a").greater_than(1)?,
+            FieldPath::raw("a").less_than(5)?,
+        ])),
+    ] {
+        assert_eq!(query.composite_indexes()?, vec![], "{}", query);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_query_composite_indexes_equality_array_and_order_by()
+-> firestore_structured_query::Result<()> {
+    // Added: Query::composite_indexes, Index::collection_group, Index::fields, Index::query_scope
+    use firestore_structured_query::{Direction, FieldPath, IndexField, QueryScope};
+    let indexes = posts_query()?.composite_indexes()?;
+    assert_eq!(indexes.len(), 1);
+    assert_eq!(indexes[0].collection_group(), "posts");
+    assert_eq!(indexes[0].query_scope(), QueryScope::CollectionGroup);
+    assert_eq!(
+        indexes[0].fields(),
+        &[
+            index_order("author", Direction::Ascending),
+            index_order("published", Direction::Ascending),
+            IndexField::ArrayContains {
+                field_path: FieldPath::raw("tags"),
+            },
+            index_order("created_at", Direction::Descending),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_composite_indexes_inequality() -> firestore_structured_query::Result<()> {
+    // Added: Query::composite_indexes (inequality fields)
+    use firestore_structured_query::{Direction, FieldPath, Filter, Query, QueryScope};
+    let indexes = Query::collection("users")
+        .r#where(Filter::and([
+            FieldPath::raw("b").not_equal(1)?,
+            FieldPath::raw("a").equal(2)?,
+        ]))
+        .composite_indexes()?;
+    assert_eq!(indexes[0].query_scope(), QueryScope::Collection);
+    assert_eq!(
+        indexes[0].fields(),
+        &[
+            index_order("a", Direction::Ascending),
+            index_order("b", Direction::Ascending),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_composite_indexes_document_id() -> firestore_structured_query::Result<()> {
+    // Added: Query::composite_indexes (`__name__`)
+    use firestore_structured_query::{Direction, FieldPath, Query};
+    let query = Query::collection("users").order_by([
+        FieldPath::raw("a").ascending(),
+        FieldPath::document_id().descending(),
+    ]);
+    assert_eq!(
+        query.composite_indexes()?[0].fields(),
+        &[
+            index_order("a", Direction::Ascending),
+            index_order("__name__", Direction::Descending),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_composite_indexes_disjunctions() -> firestore_structured_query::Result<()> {
+    // Added: Query::composite_indexes (one index per disjunction)
+    use firestore_structured_query::{Direction, FieldPath, Filter, Query};
+    let indexes = Query::collection("users")
+        .r#where(Filter::or([
+            FieldPath::raw("a").equal(1)?,
+            FieldPath::raw("a").equal(2)?,
+            FieldPath::raw("b").equal(3)?,
+        ]))
+        .order_by([FieldPath::raw("c").ascending()])
+        .composite_indexes()?;
+    assert_eq!(indexes.len(), 2);
+    assert_eq!(
+        indexes[0].fields(),
+        &[
+            index_order("a", Direction::Ascending),
+            index_order("c", Direction::Ascending),
+        ]
+    );
+    assert_eq!(
+        indexes[1].fields(),
+        &[
+            index_order("b", Direction::Ascending),
+            index_order("c", Direction::Ascending),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_composite_indexes_too_many_disjunctions() -> firestore_structured_query::Result<()> {
+    // Added: Query::composite_indexes (too many disjunctions are rejected before expanding the filter)
+    use firestore_structured_query::{ErrorKind, FieldPath, Filter, Query};
+    let err = Query::collection("c")
+        .r#where(Filter::and([
+            FieldPath::raw("a").r#in([1, 2, 3, 4, 5, 6])?,
+            FieldPath::raw("b").r#in([1, 2, 3, 4, 5, 6])?,
+        ]))
+        .composite_indexes()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Violation);
+    assert_eq!(err.violations()[0].location(), "where");
+    Ok(())
+}
+
+#[test]
+fn test_query_composite_indexes_vector_search() -> firestore_structured_query::Result<()> {
+    // Added: Query::composite_indexes (vector search), IndexField::field_path
+    use firestore_structured_query::{Direction, FieldPath, IndexField};
+    let indexes = vector_search_query()?.composite_indexes()?;
+    assert_eq!(
+        indexes[0].fields(),
+        &[
+            index_order("category", Direction::Ascending),
+            IndexField::Vector {
+                field_path: FieldPath::raw("embedding"),
+                dimension: 3,
+            },
+        ]
+    );
+    assert_eq!(
+        indexes[0].fields()[1].field_path(),
+        &FieldPath::raw("embedding")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_indexes_json() -> firestore_structured_query::Result<()> {
+    // Added: indexes_json
+    use firestore_structured_query::{FieldPath, Query, indexes_json};
+    assert_eq!(
+        indexes_json([])?,
+        "{\n  \"indexes\": [],\n  \"fieldOverrides\": []\n}\n"
+    );
+    let query = Query::collection("users").order_by([
+        FieldPath::new(["a\"b"]).ascending(),
+        FieldPath::raw("c").ascending(),
+    ]);
+    assert_eq!(
+        indexes_json([
+            &posts_query()?,
+            &vector_search_query()?,
+            &posts_query()?,
+            &query
+        ])?,
+        r#"{
+  "indexes": [
+    {
+      "collectionGroup": "posts",
+      "queryScope": "COLLECTION_GROUP",
+      "fields": [
+        {
+          "fieldPath": "author",
+          "order": "ASCENDING"
+        },
+        {
+          "fieldPath": "published",
+          "order": "ASCENDING"
+        },
+        {
+          "fieldPath": "tags",
+          "arrayConfig": "CONTAINS"
+        },
+        {
+          "fieldPath": "created_at",
+          "order": "DESCENDING"
+        }
+      ]
+    },
+    {
+      "collectionGroup": "docs",
+      "queryScope": "COLLECTION",
+      "fields": [
+        {
+          "fieldPath": "category",
+          "order": "ASCENDING"
+        },
+        {
+          "fieldPath": "embedding",
+          "vectorConfig": {
+            "dimension": 3,
+            "flat": {}
+          }
+        }
+      ]
+    },
+    {
+      "collectionGroup": "users",
+      "queryScope": "COLLECTION",
+      "fields": [
+        {
+          "fieldPath": "`a\"b`",
+          "order": "ASCENDING"
+        },
+        {
+          "fieldPath": "c",
+          "order": "ASCENDING"
+        }
+      ]
+    }
+  ],
+  "fieldOverrides": []
+}
+"#
+    );
+    Ok(())
+}